@@ -4,7 +4,7 @@ use iced::{
 	widget::{button, column, container, row, text, Space},
 	Alignment, Border, Element, Length, Padding, Shadow, Task, Theme,
 };
-use meals_database::{MealPlan, RestDatabase};
+use meals_database::Database;
 use std::{collections::HashMap, sync::Arc};
 
 use crate::{
@@ -35,12 +35,12 @@ const DAY_FONT_SIZE: u32 = 18;
 const DAY_SPACING: u16 = 4;
 
 pub struct Calendar {
-	meals_database: Arc<RestDatabase<MealPlan>>,
+	meals_database: Arc<dyn Database>,
 	start: NaiveDate,
 }
 
 impl Calendar {
-	pub fn new(meals_database: Arc<RestDatabase<MealPlan>>) -> Self {
+	pub fn new(meals_database: Arc<dyn Database>) -> Self {
 		let start = Local::now().date_naive().with_day(1).unwrap();
 
 		Self {
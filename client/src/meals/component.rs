@@ -1,23 +1,29 @@
 use bytes::Bytes;
 use chrono::{Days, NaiveDate};
-use futures::{executor::block_on, select, FutureExt, SinkExt};
+use futures::{executor::block_on, SinkExt};
 use iced::{
 	stream,
 	widget::{button, column, container, row, text},
 	Alignment, Element, Length, Task,
 };
-use meals_database::{MealPlan, MealPlanMessage, MealStub, RestDatabase};
+use meals_database::{
+	Database, DatabaseConfig, DatabaseError, MealFilter, MealPlanChange, MealPlanMessage, MealStub,
+	MemoryDatabase,
+};
 use std::sync::Arc;
 use uuid::Uuid;
 
 use crate::{
 	calendar::Calendar,
+	pt,
 	scrollable_menu::{ScrollableMenu, ScrollableMenuMessage},
 	styles::{primary_button, subdued_button},
-	Message,
+	Message, ICONS,
 };
 
-use super::{shopping_list_component::ShoppingList, MealsChooser, MealsList, RandomMealChooser};
+use super::{
+	shopping_list_component::ShoppingList, update_task, MealsChooser, MealsList, RandomMealChooser,
+};
 
 #[derive(Clone, Debug)]
 pub enum CalendarState {
@@ -31,11 +37,13 @@ pub struct Meals {
 	calendar: Calendar,
 	calendar_state: CalendarState,
 	meals_chooser: MealsChooser,
-	meals_database: Arc<RestDatabase<MealPlan>>,
+	meals_database: Arc<dyn Database>,
 	meals_list: MealsList,
 	meals_list_menu: ScrollableMenu,
 	random_meal_chooser: RandomMealChooser,
 	shopping_list: ShoppingList,
+	/// Why the meals database couldn't be opened, in which case an empty meal plan is shown.
+	startup_error: Option<String>,
 }
 
 #[derive(Clone, Debug)]
@@ -57,6 +65,8 @@ pub enum MealsMessage {
 		date: NaiveDate,
 		id: Uuid,
 	},
+	/// Drops the oldest write queued while offline, which conflicts with someone else's change.
+	DiscardQueuedChange,
 	FailedImage {
 		url: String,
 	},
@@ -77,20 +87,44 @@ pub enum MealsMessage {
 		date: NaiveDate,
 		id: Uuid,
 	},
+	MergeShoppingLists {
+		from_id: Uuid,
+		into_id: Uuid,
+	},
 	MoveMealsBackward,
 	MoveMealsForward,
+	/// Sends the oldest write queued while offline even though it conflicts with someone else's change.
+	OverwriteQueuedChange,
 	PruneShoppingList {
-		shopping_list_index: usize,
+		shopping_list_id: Uuid,
 	},
 	RandomizeMeal,
+	RemoveShoppingListItem {
+		id: Uuid,
+		shopping_list_id: Uuid,
+	},
 	ResetChooser,
 	Scrollable(ScrollableMenuMessage),
 	SetCalendarState(CalendarState),
 	SelectMealForDate {
 		date: NaiveDate,
 		id: Uuid,
+		servings: Option<usize>,
 	},
 	SearchMeal(String),
+	SetChooserServings {
+		id: Uuid,
+		servings: usize,
+	},
+	SetMealFilter(MealFilter),
+	SetServings {
+		date: NaiveDate,
+		id: Uuid,
+		servings: usize,
+	},
+	/// Ranks the chooser's meals by how many of their ingredients are checked off on the shopping
+	/// lists.
+	ToggleCookWith,
 	ToggleLeftovers {
 		date: NaiveDate,
 		id: Uuid,
@@ -103,9 +137,10 @@ pub enum MealsMessage {
 		id: Uuid,
 	},
 	ToggleShoppingListItem {
-		name: String,
-		shopping_list_index: usize,
+		id: Uuid,
+		shopping_list_id: Uuid,
 	},
+	Undo,
 	Updated,
 }
 
@@ -117,13 +152,15 @@ impl From<ScrollableMenuMessage> for MealsMessage {
 
 impl Meals {
 	pub fn new() -> (Self, Task<Message>) {
-		let (meals_database, mut meals_receiver) = block_on(RestDatabase::new(
-			&std::env::var("BANSHEELONG2_GET_ALL_MEALS_URL").unwrap(),
-			&std::env::var("BANSHEELONG2_REPLACE_URL").unwrap(),
-			&std::env::var("BANSHEELONG2_WS_URL").unwrap(),
-		));
+		let (meals_database, startup_error) = match block_on(Self::open_database()) {
+			Ok(meals_database) => (meals_database, None),
+			Err(error) => {
+				log::error!("Could not open meals database: {}", error);
 
-		let meals_database = Arc::new(meals_database);
+				let meals_database: Arc<dyn Database> = Arc::new(MemoryDatabase::default());
+				(meals_database, Some(error.to_string()))
+			}
+		};
 
 		let (meals_list, meals_list_task) = MealsList::new(meals_database.clone());
 		let (meals_chooser, meals_chooser_task) = MealsChooser::new(meals_database.clone());
@@ -144,6 +181,7 @@ impl Meals {
 				meals_list_menu,
 				random_meal_chooser,
 				shopping_list,
+				startup_error,
 			},
 			Task::batch([
 				meals_list_task,
@@ -152,18 +190,19 @@ impl Meals {
 				meals_list_menu_task,
 				Task::stream(stream::channel(100, |mut output| async move {
 					loop {
-						select! {
-							_ = meals_database.recv_loop().fuse() => {}
-							message = meals_receiver.recv().fuse() => {
-								let Some(message) = message else {
-									unreachable!();
-								};
-
-								match message {
-									MealPlanMessage::Update => output.send(Message::Meals(MealsMessage::Updated)).await.unwrap()
+						let message = match meals_database.next_message().await {
+							MealPlanMessage::Changed { change, .. } => match *change {
+								MealPlanChange::MealCompleted { date, id }
+								| MealPlanChange::MealMoved { date, id, .. }
+								| MealPlanChange::MealUnplanned { date, id } => {
+									Message::Meals(MealsMessage::CloseOpenMeal { date, id })
 								}
-							}
-						}
+								_ => Message::Noop,
+							},
+							MealPlanMessage::Update => Message::Meals(MealsMessage::Updated),
+						};
+
+						output.send(message).await.unwrap();
 					}
 				})),
 				Task::done(Message::Meals(MealsMessage::Updated)),
@@ -171,80 +210,51 @@ impl Meals {
 		)
 	}
 
+	async fn open_database() -> Result<Arc<dyn Database>, DatabaseError> {
+		let config = match DatabaseConfig::from_env()? {
+			Some(config) => config,
+			None => DatabaseConfig::rest_from_env()?,
+		};
+
+		config.open().await
+	}
+
 	pub fn update(&mut self, event: MealsMessage) -> Task<Message> {
 		match event {
 			MealsMessage::AddLeftoversForMeal { date, id } => {
-				let mut meal_plan = self.meals_database.get_mut();
-
 				let date = date.checked_add_days(Days::new(1)).unwrap();
 
-				meal_plan
-					.planned_meals
-					.entry(date)
-					.or_default()
-					.push(MealStub {
-						date,
-						id,
-						leftovers: true,
-					});
-
-				drop(meal_plan);
-
 				let meals_database = self.meals_database.clone();
-				Task::future(async move {
-					meals_database.save().await;
-					Message::Noop
+				update_task(async move {
+					meals_database
+						.plan_meal(MealStub {
+							date,
+							id,
+							leftovers: true,
+							servings: None,
+						})
+						.await
 				})
 			}
 			MealsMessage::AddMonth(_) => self.calendar.update(event),
 			MealsMessage::CompletePlannedMeal { date, id } => {
-				let mut meal_plan = self.meals_database.get_mut();
-				let vec = meal_plan.planned_meals.get_mut(&date).unwrap();
-				let meal_id = vec.iter().find(|meal_stub| meal_stub.id == id).unwrap().id;
-
-				vec.retain(|meal_stub| meal_stub.id != id);
-
-				if vec.len() == 0 {
-					meal_plan.planned_meals.remove(&date);
-				}
-
-				meal_plan
-					.completed_meals
-					.entry(date)
-					.or_default()
-					.push(meal_id);
-
-				drop(meal_plan);
-
 				let meals_database = self.meals_database.clone();
 				Task::batch([
-					Task::future(async move {
-						meals_database.save().await;
-						Message::Noop
-					}),
+					update_task(async move { meals_database.complete_meal(date, id).await }),
 					Task::done(Message::Meals(MealsMessage::CloseOpenMeal { date, id })),
 				])
 			}
 			MealsMessage::DeletePlannedMeal { date, id } => {
-				let mut meal_plan = self.meals_database.get_mut();
-				let vec = meal_plan.planned_meals.get_mut(&date).unwrap();
-				vec.retain(|meal_stub| meal_stub.id != id);
-
-				if vec.len() == 0 {
-					meal_plan.planned_meals.remove(&date);
-				}
-
-				drop(meal_plan);
-
 				let meals_database = self.meals_database.clone();
 				Task::batch([
-					Task::future(async move {
-						meals_database.save().await;
-						Message::Noop
-					}),
+					update_task(async move { meals_database.unplan_meal(date, id).await }),
 					Task::done(Message::Meals(MealsMessage::CloseOpenMeal { date, id })),
 				])
 			}
+			MealsMessage::DiscardQueuedChange => {
+				let meals_database = self.meals_database.clone();
+				update_task(async move { meals_database.discard_queued_change().await })
+			}
 			MealsMessage::FailedImage { .. } | MealsMessage::Image { .. } => {
 				self.meals_list.update(event.clone());
 				self.meals_chooser.update(event.clone());
@@ -253,41 +263,15 @@ impl Meals {
 			}
 			MealsMessage::MoveMealBackward { date, id }
 			| MealsMessage::MoveMealForward { date, id } => {
-				let mut meal_plan = self.meals_database.get_mut();
-				let vec = meal_plan.planned_meals.get_mut(&date).unwrap();
-				let mut meal_stub = vec
-					.iter()
-					.find(|meal_stub| meal_stub.id == id)
-					.unwrap()
-					.clone();
-
-				vec.retain(|meal_stub| meal_stub.id != id);
-
-				if vec.len() == 0 {
-					meal_plan.planned_meals.remove(&date);
-				}
-
 				let new_date = if let MealsMessage::MoveMealBackward { .. } = event {
 					date.checked_sub_days(Days::new(1)).unwrap()
 				} else {
 					date.checked_add_days(Days::new(1)).unwrap()
 				};
 
-				meal_stub.date = new_date;
-				meal_plan
-					.planned_meals
-					.entry(new_date)
-					.or_default()
-					.push(meal_stub);
-
-				drop(meal_plan);
-
 				let meals_database = self.meals_database.clone();
 				Task::batch([
-					Task::future(async move {
-						meals_database.save().await;
-						Message::Noop
-					}),
+					update_task(async move { meals_database.move_meal(date, id, new_date).await }),
 					self.meals_list
 						.update(MealsMessage::CloseOpenMeal { date, id }),
 					self.meals_list
@@ -295,45 +279,18 @@ impl Meals {
 				])
 			}
 			MealsMessage::MoveMealsBackward | MealsMessage::MoveMealsForward => {
-				let mut meal_plan = self.meals_database.get_mut();
-
-				let mut new_meals = vec![];
-
-				for planned_meals in meal_plan.planned_meals.values() {
-					for planned_meal in planned_meals.iter() {
-						let mut date = planned_meal.date;
-
-						if let MealsMessage::MoveMealsBackward = event {
-							date = date.checked_sub_days(Days::new(1)).unwrap();
-						} else {
-							date = date.checked_add_days(Days::new(1)).unwrap();
-						}
-
-						new_meals.push(MealStub {
-							date,
-							id: planned_meal.id,
-							leftovers: planned_meal.leftovers,
-						});
-					}
-				}
-
-				meal_plan.planned_meals.clear();
-
-				for new_meal in new_meals {
-					meal_plan
-						.planned_meals
-						.entry(new_meal.date)
-						.or_default()
-						.push(new_meal);
-				}
-
-				drop(meal_plan);
+				let days = if let MealsMessage::MoveMealsBackward = event {
+					-1
+				} else {
+					1
+				};
 
 				let meals_database = self.meals_database.clone();
-				Task::future(async move {
-					meals_database.save().await;
-					Message::Noop
-				})
+				update_task(async move { meals_database.shift_meals(days).await })
+			}
+			MealsMessage::OverwriteQueuedChange => {
+				let meals_database = self.meals_database.clone();
+				update_task(async move { meals_database.overwrite_queued_change().await })
 			}
 			MealsMessage::RandomizeMeal => self.random_meal_chooser.update(event),
 			MealsMessage::Scrollable(ref message) => {
@@ -345,27 +302,18 @@ impl Meals {
 					self.meals_chooser.update(event)
 				}
 			}
-			MealsMessage::SelectMealForDate { date, id: meal_id } => {
-				let mut meal_plan = self.meals_database.get_mut();
-				let meal_id = meal_plan.all_meals.get(&meal_id).unwrap().id;
-
-				meal_plan
-					.planned_meals
-					.entry(date)
-					.or_default()
-					.push(MealStub {
-						date,
-						id: meal_id,
-						leftovers: false,
-					});
-
-				drop(meal_plan);
-
+			MealsMessage::SelectMealForDate { date, id, servings } => {
 				let meals_database = self.meals_database.clone();
 				Task::batch([
-					Task::future(async move {
-						meals_database.save().await;
-						Message::Noop
+					update_task(async move {
+						meals_database
+							.plan_meal(MealStub {
+								date,
+								id,
+								leftovers: false,
+								servings,
+							})
+							.await
 					}),
 					Task::done(Message::Meals(MealsMessage::SetCalendarState(
 						CalendarState::Calendar,
@@ -406,37 +354,51 @@ impl Meals {
 					_ => Task::none(),
 				}
 			}
+			MealsMessage::SetMealFilter(..) => {
+				self.meals_chooser.update(event.clone());
+				self.random_meal_chooser.update(event)
+			}
+			MealsMessage::SetServings { date, id, servings } => {
+				let meals_database = self.meals_database.clone();
+				update_task(
+					async move { meals_database.set_servings(date, id, Some(servings)).await },
+				)
+			}
 			MealsMessage::ToggleLeftovers { date, id } => {
-				let mut meal_plan = self.meals_database.get_mut();
+				let meal_plan = self.meals_database.get();
 
-				let meal_stub = meal_plan
+				let leftovers = meal_plan
 					.planned_meals
-					.get_mut(&date)
+					.get(&date)
 					.unwrap()
-					.iter_mut()
+					.iter()
 					.find(|meal_stub| meal_stub.id == id)
-					.unwrap();
-
-				meal_stub.leftovers = !meal_stub.leftovers;
+					.unwrap()
+					.leftovers;
 
 				drop(meal_plan);
 
 				let meals_database = self.meals_database.clone();
-				Task::future(async move {
-					meals_database.save().await;
-					Message::Noop
-				})
+				update_task(async move { meals_database.set_leftovers(date, id, !leftovers).await })
 			}
 			MealsMessage::ToggleOpenMeal { .. } | MealsMessage::CloseOpenMeal { .. } => {
 				self.meals_list.update(event)
 			}
 			MealsMessage::ToggleOpenMealInChooser { .. }
+			| MealsMessage::ToggleCookWith
 			| MealsMessage::ResetChooser
+			| MealsMessage::SetChooserServings { .. }
 			| MealsMessage::SearchMeal(..) => self.meals_chooser.update(event),
 			MealsMessage::ToggleShoppingListItem { .. }
 			| MealsMessage::GenerateShoppingList
 			| MealsMessage::GenerateShoppingListForMeal { .. }
-			| MealsMessage::PruneShoppingList { .. } => self.shopping_list.update(event),
+			| MealsMessage::MergeShoppingLists { .. }
+			| MealsMessage::PruneShoppingList { .. }
+			| MealsMessage::RemoveShoppingListItem { .. } => self.shopping_list.update(event),
+			MealsMessage::Undo => {
+				let meals_database = self.meals_database.clone();
+				update_task(async move { meals_database.undo().await })
+			}
 			MealsMessage::Updated => {
 				let meals_database = self.meals_database.clone();
 				/*Task::future(async move {
@@ -446,48 +408,48 @@ impl Meals {
 				})*/
 
 				Task::stream(stream::channel(100, |mut output| async move {
-					let meal_plan = meals_database.get();
-					let old_meals = meal_plan.planned_meals.clone();
-					drop(meal_plan);
+					let old_meals = meals_database.get().planned_meals.clone();
+
+					if let Err(error) = meals_database.load().await {
+						log::error!("Could not load meals database: {:?}", error);
+						return;
+					}
 
-					meals_database.load().await;
 					log::info!("Updated meals database");
 
-					let meal_plan = meals_database.get();
-					for (date, planned_meals1) in old_meals.iter() {
-						let Some(planned_meals2) = meal_plan.planned_meals.get(&date) else {
-							for planned_meal in planned_meals1 {
-								output
-									.send(Message::Meals(MealsMessage::CloseOpenMeal {
-										date: planned_meal.date,
-										id: planned_meal.id,
-									}))
-									.await
-									.unwrap();
-							}
+					let mut closed_meals = vec![];
+					{
+						let meal_plan = meals_database.get();
+						for (date, planned_meals1) in old_meals.iter() {
+							let Some(planned_meals2) = meal_plan.planned_meals.get(&date) else {
+								for planned_meal in planned_meals1 {
+									closed_meals.push((planned_meal.date, planned_meal.id));
+								}
 
-							continue;
-						};
+								continue;
+							};
 
-						for planned_meal1 in planned_meals1.iter() {
-							let mut found = false;
-							for planned_meal2 in planned_meals2.iter() {
-								if planned_meal1 == planned_meal2 {
-									found = true;
+							for planned_meal1 in planned_meals1.iter() {
+								let mut found = false;
+								for planned_meal2 in planned_meals2.iter() {
+									if planned_meal1 == planned_meal2 {
+										found = true;
+									}
 								}
-							}
 
-							if !found {
-								output
-									.send(Message::Meals(MealsMessage::CloseOpenMeal {
-										date: *date,
-										id: planned_meal1.id,
-									}))
-									.await
-									.unwrap();
+								if !found {
+									closed_meals.push((*date, planned_meal1.id));
+								}
 							}
 						}
 					}
+
+					for (date, id) in closed_meals {
+						output
+							.send(Message::Meals(MealsMessage::CloseOpenMeal { date, id }))
+							.await
+							.unwrap();
+					}
 				}))
 			}
 		}
@@ -495,6 +457,10 @@ impl Meals {
 
 	pub fn view(&self) -> Element<MealsMessage> {
 		let mut column = column(vec![]).spacing(10);
+		if let Some(connection_status) = self.connection_status() {
+			column = column.push(connection_status);
+		}
+
 		if let Some(shopping_list) = self.shopping_list.view() {
 			column = column.push(shopping_list);
 		}
@@ -537,6 +503,15 @@ impl Meals {
 					.width(Length::Fill)
 					.style(|theme, _status| subdued_button(theme))
 					.into(),
+					button(
+						container(text!("Undo last change"))
+							.align_x(Alignment::Center)
+							.width(Length::Fill),
+					)
+					.on_press(MealsMessage::Undo)
+					.width(Length::Fill)
+					.style(|theme, _status| subdued_button(theme))
+					.into(),
 					button(
 						container(text!("Generate shopping list"))
 							.align_x(Alignment::Center)
@@ -563,4 +538,65 @@ impl Meals {
 		.width(720)
 		.into()
 	}
+
+	fn connection_status(&self) -> Option<Element<MealsMessage>> {
+		if let Some(startup_error) = &self.startup_error {
+			return Some(
+				row!(
+					text("\u{e000}").font(ICONS).size(pt(20)),
+					text!("Could not open meals database: {}", startup_error).size(pt(14))
+				)
+				.spacing(8)
+				.align_y(Alignment::Center)
+				.into(),
+			);
+		}
+
+		let queued_changes = self.meals_database.queued_changes();
+		if self.meals_database.is_connected() {
+			// connected with writes still queued means the first one conflicts with someone else's
+			if queued_changes == 0 {
+				return None;
+			}
+
+			return Some(
+				column!(
+					row!(
+						text("\u{e000}").font(ICONS).size(pt(20)),
+						text("A change made offline conflicts with one made elsewhere")
+							.size(pt(14))
+					)
+					.spacing(8)
+					.align_y(Alignment::Center),
+					row!(
+						button(text!("Keep mine"))
+							.on_press(MealsMessage::OverwriteQueuedChange)
+							.style(|theme, _status| primary_button(theme)),
+						button(text!("Discard mine"))
+							.on_press(MealsMessage::DiscardQueuedChange)
+							.style(|theme, _status| subdued_button(theme))
+					)
+					.spacing(10)
+				)
+				.spacing(8)
+				.into(),
+			);
+		}
+
+		let status = match queued_changes {
+			0 => "Offline".to_string(),
+			1 => "Offline, 1 change waiting to sync".to_string(),
+			queued_changes => format!("Offline, {} changes waiting to sync", queued_changes),
+		};
+
+		Some(
+			row!(
+				text("\u{e2c1}").font(ICONS).size(pt(20)),
+				text(status).size(pt(14))
+			)
+			.spacing(8)
+			.align_y(Alignment::Center)
+			.into(),
+		)
+	}
 }
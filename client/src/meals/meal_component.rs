@@ -1,25 +1,23 @@
 use iced::{
-	widget::{checkbox, column, container, image, row, text, Column, Space},
-	Element, Length, Theme,
+	widget::{button, checkbox, column, container, image, row, text, Column, Space},
+	Alignment, Element, Length, Theme,
+};
+use meals_database::{
+	format_amount_compound, Ingredient, MealInfo, MealNutrition, MealPlan, MealStub, RecipeStep,
 };
-use meals_database::{Ingredient, MealInfo, MealStub, RecipeStep};
 
-use crate::styles::checkbox_style;
+use crate::{
+	pt,
+	styles::{checkbox_style, invisible_button},
+	ICONS,
+};
 
 use super::MealsMessage;
 
-fn view_ingredient<'a, 'b: 'a>(
-	index: usize,
-	ingredient: &'a Ingredient,
-) -> Element<'b, MealsMessage> {
+fn view_ingredient<'b>(index: usize, ingredient: &Ingredient) -> Element<'b, MealsMessage> {
 	row![
 		container(text!("{}.", index + 1)).width(30),
-		container(text!(
-			"{} {}",
-			ingredient.amount.value,
-			ingredient.amount.units
-		))
-		.width(90),
+		container(text(format_amount_compound(&ingredient.amount))).width(130),
 		container(text(ingredient.name.clone()))
 	]
 	.into()
@@ -36,10 +34,72 @@ fn view_recipe_step<'a, 'b: 'a>(
 	.into()
 }
 
+/// "Serves N", with buttons to change N if `on_change` is given.
+fn view_servings<'b>(
+	servings: usize,
+	on_change: Option<impl Fn(usize) -> MealsMessage>,
+) -> Element<'b, MealsMessage> {
+	let Some(on_change) = on_change else {
+		return text!("Serves {}", servings).into();
+	};
+
+	let servings_button = |icon: &str, servings: usize| {
+		button(text(icon.to_string()).font(ICONS).size(pt(20)))
+			.on_press_maybe(Some(on_change(servings)).filter(|_| servings > 0))
+			.style(|theme, _status| invisible_button(theme))
+			.padding(0)
+	};
+
+	row![
+		text!("Serves {}", servings),
+		servings_button("\u{e15b}", servings.saturating_sub(1)),
+		servings_button("\u{e145}", servings + 1),
+	]
+	.align_y(Alignment::Center)
+	.spacing(10)
+	.into()
+}
+
+/// The meal's nutrition, if the meal plan has a nutrition table to go on.
+pub fn meal_nutrition(meal_plan: &MealPlan, meal_info: &MealInfo) -> Option<MealNutrition> {
+	if meal_plan.nutrition.is_empty() {
+		return None;
+	}
+
+	Some(meal_plan.meal_nutrition(meal_info))
+}
+
+fn view_nutrition<'b>(nutrition: &MealNutrition) -> Element<'b, MealsMessage> {
+	let nutrients = &nutrition.per_serving;
+
+	column![
+		text!("Per serving: {:.0} kcal", nutrients.calories),
+		text!(
+			"{:.0} g protein, {:.0} g carbs, {:.0} g fat, {:.0} g fiber, {:.0} mg sodium",
+			nutrients.protein,
+			nutrients.carbs,
+			nutrients.fat,
+			nutrients.fiber,
+			nutrients.sodium
+		)
+		.size(pt(14)),
+	]
+	.push_maybe(
+		(!nutrition.missing.is_empty())
+			.then(|| text!("Not counted: {}", nutrition.missing.join(", ")).size(pt(14))),
+	)
+	.spacing(2)
+	.into()
+}
+
+/// Shows a recipe. Planned meals show the ingredients for the servings in `meal_stub`, otherwise
+/// `servings` is used if given, with buttons to change it. `nutrition` comes from `meal_nutrition`.
 pub fn meal_contents<'a, 'b: 'a>(
 	meal_info: &'a MealInfo,
 	image_handle: Option<&'a image::Handle>,
 	meal_stub: Option<&'a MealStub>,
+	servings: Option<usize>,
+	nutrition: Option<MealNutrition>,
 	header: Option<Element<'b, MealsMessage>>,
 ) -> Column<'b, MealsMessage> {
 	let image = if let Some(handle) = image_handle {
@@ -51,10 +111,31 @@ pub fn meal_contents<'a, 'b: 'a>(
 			.style(|theme: &Theme| theme.palette().background.into())
 	};
 
+	let servings_view = if let Some(meal_stub) = meal_stub {
+		let date = meal_stub.date;
+		let id = meal_stub.id;
+		view_servings(
+			meal_info.servings_for(meal_stub),
+			Some(move |servings| MealsMessage::SetServings { date, id, servings }),
+		)
+	} else if let Some(servings) = servings {
+		let id = meal_info.id;
+		view_servings(
+			servings,
+			Some(move |servings| MealsMessage::SetChooserServings { id, servings }),
+		)
+	} else {
+		view_servings(meal_info.serving_size, None::<fn(usize) -> MealsMessage>)
+	};
+
+	let scaled_ingredients = match meal_stub {
+		Some(meal_stub) => meal_info.ingredients_for(meal_stub),
+		None => meal_info.scaled_ingredients(servings.unwrap_or(meal_info.serving_size)),
+	};
+
 	let ingredients = column(vec![container(text("Ingredients:")).into()])
 		.extend(
-			meal_info
-				.ingredients
+			scaled_ingredients
 				.iter()
 				.enumerate()
 				.map(|(index, ingredient)| view_ingredient(index, ingredient)),
@@ -91,7 +172,8 @@ pub fn meal_contents<'a, 'b: 'a>(
 		.push_maybe(header)
 		.push(image)
 		.push(text(meal_info.name.clone()))
-		.push(text!("Serves {}", meal_info.serving_size))
+		.push(servings_view)
+		.push_maybe(nutrition.as_ref().map(view_nutrition))
 		.push(checkbox)
 		.push(
 			container(
@@ -0,0 +1,88 @@
+use iced::{
+	widget::{button, column, row, text},
+	Element, Length,
+};
+use meals_database::{Diet, MealFilter, MealPlan};
+
+use crate::styles::{subdued_button, success_button};
+
+use super::MealsMessage;
+
+/// Meals that take longer than this aren't quick.
+const QUICK_MINUTES: u32 = 30;
+
+const BUTTONS_PER_ROW: usize = 4;
+
+fn filter_button<'a>(
+	label: String,
+	selected: bool,
+	filter: MealFilter,
+) -> Element<'a, MealsMessage> {
+	button(text(label).center())
+		.on_press(MealsMessage::SetMealFilter(filter))
+		.width(Length::Fill)
+		.style(move |theme, _status| {
+			if selected {
+				success_button(theme)
+			} else {
+				subdued_button(theme)
+			}
+		})
+		.into()
+}
+
+/// Toggles for the diets, quick meals and every cuisine in the meal plan.
+pub fn meal_filter<'a>(meal_plan: &MealPlan, filter: &MealFilter) -> Element<'a, MealsMessage> {
+	let mut buttons = vec![];
+
+	for diet in Diet::ALL {
+		let selected = filter.diets.contains(&diet);
+
+		let mut toggled = filter.clone();
+		if selected {
+			toggled.diets.retain(|other| *other != diet);
+		} else {
+			toggled.diets.push(diet);
+		}
+
+		buttons.push(filter_button(diet.to_string(), selected, toggled));
+	}
+
+	let quick = filter.max_minutes.is_some();
+	buttons.push(filter_button(
+		"Quick".into(),
+		quick,
+		MealFilter {
+			max_minutes: if quick { None } else { Some(QUICK_MINUTES) },
+			..filter.clone()
+		},
+	));
+
+	for cuisine in meal_plan.cuisines() {
+		let selected = filter
+			.cuisine
+			.as_ref()
+			.is_some_and(|other| other.eq_ignore_ascii_case(&cuisine));
+
+		buttons.push(filter_button(
+			cuisine.clone(),
+			selected,
+			MealFilter {
+				cuisine: if selected { None } else { Some(cuisine) },
+				..filter.clone()
+			},
+		));
+	}
+
+	let mut rows: Vec<Element<MealsMessage>> = vec![];
+	let mut buttons = buttons.into_iter().peekable();
+	while buttons.peek().is_some() {
+		rows.push(
+			row(buttons.by_ref().take(BUTTONS_PER_ROW))
+				.spacing(5)
+				.into(),
+		);
+	}
+
+	column(rows).spacing(5).padding([0, 10]).into()
+}
@@ -1,10 +1,9 @@
 use chrono::NaiveDate;
-use fuzzy_matcher::{skim::SkimMatcherV2, FuzzyMatcher};
 use iced::{
 	widget::{button, column, container, image, row, text, Space},
 	Alignment, Border, Element, Length, Padding, Shadow, Task, Theme,
 };
-use meals_database::{MealInfo, MealPlan, RestDatabase};
+use meals_database::{Database, MealFilter, MealInfo, MealPlan};
 use std::{
 	collections::{HashMap, HashSet},
 	sync::Arc,
@@ -16,32 +15,39 @@ use crate::{
 	scrollable_menu::ScrollableMenu,
 	styles::{
 		green_button, invisible_button, keyboard_button, keyboard_button_focused, primary_button,
+		subdued_button, success_button,
 	},
 	Message, ICONS,
 };
 
-use super::{meal_contents, CalendarState, MealsMessage};
+use super::{meal_contents, meal_filter, meal_nutrition, CalendarState, MealsMessage};
 
 pub struct MealsChooser {
+	cook_with: bool,
 	current_date: NaiveDate,
+	filter: MealFilter,
 	images: HashMap<String, image::Handle>,
-	meals_database: Arc<RestDatabase<MealPlan>>,
+	meals_database: Arc<dyn Database>,
 	pub menu: ScrollableMenu,
 	opened_meals: HashSet<Uuid>,
 	search: Option<String>,
+	servings: HashMap<Uuid, usize>,
 }
 
 impl MealsChooser {
-	pub fn new(meals_database: Arc<RestDatabase<MealPlan>>) -> (Self, Task<Message>) {
+	pub fn new(meals_database: Arc<dyn Database>) -> (Self, Task<Message>) {
 		let (menu, task) = ScrollableMenu::new();
 		(
 			Self {
+				cook_with: false,
 				current_date: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+				filter: MealFilter::default(),
 				images: HashMap::new(),
 				meals_database,
 				menu,
 				opened_meals: HashSet::new(),
 				search: None,
+				servings: HashMap::new(),
 			},
 			task,
 		)
@@ -59,8 +65,10 @@ impl MealsChooser {
 				Task::none()
 			}
 			MealsMessage::ResetChooser => {
+				self.cook_with = false;
 				self.opened_meals.clear();
 				self.search = None;
+				self.servings.clear();
 				Task::none()
 			}
 			MealsMessage::Scrollable(message) => self.menu.update(message),
@@ -81,6 +89,18 @@ impl MealsChooser {
 				self.search = Some(format!("{}{}", search, character));
 				Task::none()
 			}
+			MealsMessage::SetChooserServings { id, servings } => {
+				self.servings.insert(id, servings);
+				Task::none()
+			}
+			MealsMessage::SetMealFilter(filter) => {
+				self.filter = filter;
+				Task::none()
+			}
+			MealsMessage::ToggleCookWith => {
+				self.cook_with = !self.cook_with;
+				Task::none()
+			}
 			MealsMessage::ToggleOpenMealInChooser { id } => {
 				if self.opened_meals.contains(&id) {
 					self.opened_meals.remove(&id);
@@ -111,10 +131,21 @@ impl MealsChooser {
 			.into()
 	}
 
-	fn view_meal(&self, meal_info: &MealInfo) -> Element<MealsMessage> {
+	/// `missing` lists the ingredients that aren't checked off, when ranking by them.
+	fn view_meal(
+		&self,
+		meal_plan: &MealPlan,
+		meal_info: &MealInfo,
+		missing: Option<&[String]>,
+	) -> Element<MealsMessage> {
 		if !self.opened_meals.contains(&meal_info.id) {
+			let mut label = column![text!("{}", meal_info.name)];
+			if let Some(missing) = missing.filter(|missing| !missing.is_empty()) {
+				label = label.push(text!("Missing: {}", missing.join(", ")).size(pt(14)));
+			}
+
 			return container(
-				button(row![text!("{}", meal_info.name)].spacing(10))
+				button(label)
 					.on_press(MealsMessage::ToggleOpenMealInChooser { id: meal_info.id })
 					.padding([10, 0])
 					.style(|theme: &Theme, _status| button::Style {
@@ -130,16 +161,25 @@ impl MealsChooser {
 			.into();
 		}
 
+		let servings = self
+			.servings
+			.get(&meal_info.id)
+			.cloned()
+			.unwrap_or(meal_info.serving_size);
+
 		let meal_contents = meal_contents(
 			meal_info,
 			self.images.get(&meal_info.image),
 			None,
+			Some(servings),
+			meal_nutrition(meal_plan, meal_info),
 			Some(
 				row![
 					button(text!("\u{e145}").font(ICONS).size(pt(30)))
 						.on_press(MealsMessage::SelectMealForDate {
 							date: self.current_date,
 							id: meal_info.id,
+							servings: self.servings.get(&meal_info.id).cloned(),
 						})
 						.style(|theme, _status| invisible_button(theme))
 						.padding(0),
@@ -190,20 +230,53 @@ impl MealsChooser {
 		.spacing(10);
 
 		let meal_plan = self.meals_database.get();
-		let matcher = SkimMatcherV2::default();
 
-		let mut meals = meal_plan.all_meals.values().collect::<Vec<_>>();
-		meals.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+		meals_list = meals_list.push(meal_filter(&meal_plan, &self.filter));
+
+		let cook_with = self.cook_with;
+		meals_list = meals_list.push(
+			button(text("Cook with checked items").center())
+				.on_press(MealsMessage::ToggleCookWith)
+				.width(Length::Fill)
+				.style(move |theme, _status| {
+					if cook_with {
+						success_button(theme)
+					} else {
+						subdued_button(theme)
+					}
+				}),
+		);
+
+		let meals = if let Some(search) = &self.search {
+			meal_plan
+				.search(search)
+				.into_iter()
+				.filter_map(|result| meal_plan.all_meals.get(&result.id))
+				.filter(|meal_info| self.filter.matches(meal_info))
+				.collect()
+		} else {
+			meal_plan.filter_meals(&self.filter)
+		};
 
-		for meal_info in meals.iter() {
-			if let Some(search) = &self.search {
-				let matched = matcher.fuzzy_match(&meal_info.name.to_lowercase(), &search);
-				if let None = matched {
+		if self.cook_with {
+			for cookable_meal in meal_plan.cookable_meals(&meal_plan.checked_items()) {
+				let Some(meal_info) = meals
+					.iter()
+					.find(|meal_info| meal_info.id == cookable_meal.id)
+				else {
 					continue;
-				}
-			}
+				};
 
-			meals_list = meals_list.push(self.view_meal(meal_info));
+				meals_list = meals_list.push(self.view_meal(
+					&meal_plan,
+					meal_info,
+					Some(&cookable_meal.missing),
+				));
+			}
+		} else {
+			for meal_info in meals {
+				meals_list = meals_list.push(self.view_meal(&meal_plan, meal_info, None));
+			}
 		}
 
 		let buttons = vec![
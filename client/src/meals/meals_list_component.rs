@@ -3,7 +3,7 @@ use iced::{
 	widget::{button, column, container, image, row, text, Space},
 	Alignment, Border, Color, Element, Length, Padding, Shadow, Task, Theme,
 };
-use meals_database::{MealInfo, MealPlan, MealStub, RestDatabase};
+use meals_database::{Database, MealInfo, MealPlan, MealStub};
 use std::{
 	collections::{HashMap, HashSet},
 	sync::Arc,
@@ -12,17 +12,17 @@ use uuid::Uuid;
 
 use crate::{pt, styles::invisible_button, widgets::circle, Message, ICONS};
 
-use super::{get_meal_color, meal_contents, MealsMessage};
+use super::{get_meal_color, meal_contents, meal_nutrition, MealsMessage};
 
 pub struct MealsList {
 	images: HashMap<String, image::Handle>,
-	meals_database: Arc<RestDatabase<MealPlan>>,
+	meals_database: Arc<dyn Database>,
 	opened_meals: HashSet<(NaiveDate, Uuid)>,
 	width: u16,
 }
 
 impl MealsList {
-	pub fn new(meals_database: Arc<RestDatabase<MealPlan>>) -> (Self, Task<Message>) {
+	pub fn new(meals_database: Arc<dyn Database>) -> (Self, Task<Message>) {
 		(
 			Self {
 				images: HashMap::new(),
@@ -64,6 +64,7 @@ impl MealsList {
 	fn view_meal(
 		&self,
 		meal_id_to_color: &mut HashMap<Uuid, Color>,
+		meal_plan: &MealPlan,
 		meal_info: &MealInfo,
 		meal_stub: &MealStub,
 	) -> Element<MealsMessage> {
@@ -116,6 +117,8 @@ impl MealsList {
 			meal_info,
 			self.images.get(&meal_info.image),
 			Some(meal_stub),
+			None,
+			meal_nutrition(meal_plan, meal_info),
 			Some(
 				row![
 					button(text("\u{e872}").font(ICONS).size(pt(30)))
@@ -178,8 +181,12 @@ impl MealsList {
 			let meals = meal_plan.planned_meals.get(key).unwrap();
 			for meal_stub in meals.iter() {
 				let meal_info = meal_plan.all_meals.get(&meal_stub.id).unwrap();
-				meals_list =
-					meals_list.push(self.view_meal(&mut meal_id_to_color, meal_info, meal_stub));
+				meals_list = meals_list.push(self.view_meal(
+					&mut meal_id_to_color,
+					&meal_plan,
+					meal_info,
+					meal_stub,
+				));
 			}
 		}
 
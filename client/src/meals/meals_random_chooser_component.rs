@@ -3,8 +3,7 @@ use iced::{
 	widget::{button, column, container, image, row, text},
 	Alignment, Element, Length, Task, Theme,
 };
-use meals_database::{MealInfo, MealPlan, RestDatabase};
-use rand::seq::IteratorRandom;
+use meals_database::{Database, MealInfo, MealPlan, SuggestionOptions};
 use std::{collections::HashMap, sync::Arc};
 use uuid::Uuid;
 
@@ -14,18 +13,19 @@ use crate::{
 	Message,
 };
 
-use super::{meal_contents, CalendarState, MealsMessage};
+use super::{meal_contents, meal_filter, meal_nutrition, CalendarState, MealsMessage};
 
 pub struct RandomMealChooser {
 	current_date: NaiveDate,
 	current_meal_id: Uuid,
 	images: HashMap<String, image::Handle>,
-	meals_database: Arc<RestDatabase<MealPlan>>,
+	meals_database: Arc<dyn Database>,
 	pub menu: ScrollableMenu,
+	suggestion_options: SuggestionOptions,
 }
 
 impl RandomMealChooser {
-	pub fn new(meals_database: Arc<RestDatabase<MealPlan>>) -> (Self, Task<Message>) {
+	pub fn new(meals_database: Arc<dyn Database>) -> (Self, Task<Message>) {
 		let (menu, task) = ScrollableMenu::new();
 		(
 			Self {
@@ -34,6 +34,10 @@ impl RandomMealChooser {
 				images: HashMap::new(),
 				meals_database,
 				menu,
+				suggestion_options: SuggestionOptions {
+					prefer_shopping_list: true,
+					..Default::default()
+				},
 			},
 			task,
 		)
@@ -41,6 +45,7 @@ impl RandomMealChooser {
 
 	pub fn set_current_date(&mut self, date: NaiveDate) {
 		self.current_date = date;
+		self.suggestion_options.exclude.clear();
 	}
 
 	pub fn update(&mut self, event: MealsMessage) -> Task<Message> {
@@ -51,12 +56,30 @@ impl RandomMealChooser {
 				Task::none()
 			}
 			MealsMessage::RandomizeMeal => {
+				// re-rolling never shows the same meal twice until every meal has been shown
+				self.suggestion_options.exclude.insert(self.current_meal_id);
+
 				let meal_plan = self.meals_database.get();
-				let meal = meal_plan
-					.all_meals
-					.values()
-					.choose(&mut rand::thread_rng())
-					.unwrap();
+				let mut rng = rand::thread_rng();
+				let meal = match meal_plan.suggest_meal(
+					self.current_date,
+					&self.suggestion_options,
+					&mut rng,
+				) {
+					Some(meal) => meal,
+					None => {
+						self.suggestion_options.exclude.clear();
+						match meal_plan.suggest_meal(
+							self.current_date,
+							&self.suggestion_options,
+							&mut rng,
+						) {
+							Some(meal) => meal,
+							// nothing matches the filter
+							None => return Task::none(),
+						}
+					}
+				};
 
 				let url = meal.image.clone();
 				self.current_meal_id = meal.id;
@@ -67,12 +90,24 @@ impl RandomMealChooser {
 				})
 			}
 			MealsMessage::Scrollable(message) => self.menu.update(message),
+			MealsMessage::SetMealFilter(filter) => {
+				self.suggestion_options.filter = filter;
+				self.suggestion_options.exclude.clear();
+				Task::done(Message::Meals(MealsMessage::RandomizeMeal))
+			}
 			_ => unreachable!(),
 		}
 	}
 
-	fn view_meal(&self, meal_info: &MealInfo) -> Element<MealsMessage> {
-		let meal_contents = meal_contents(meal_info, self.images.get(&meal_info.image), None, None);
+	fn view_meal(&self, meal_plan: &MealPlan, meal_info: &MealInfo) -> Element<MealsMessage> {
+		let meal_contents = meal_contents(
+			meal_info,
+			self.images.get(&meal_info.image),
+			None,
+			None,
+			meal_nutrition(meal_plan, meal_info),
+			None,
+		);
 		container(meal_contents)
 			.width(Length::Fill)
 			.padding(10)
@@ -119,6 +154,7 @@ impl RandomMealChooser {
 		container(
 			self.menu.view(
 				column![
+					meal_filter(&meal_plan, &self.suggestion_options.filter),
 					row![
 						button(text!("Next").center())
 							.on_press(MealsMessage::RandomizeMeal)
@@ -128,12 +164,13 @@ impl RandomMealChooser {
 							.on_press(MealsMessage::SelectMealForDate {
 								date: self.current_date,
 								id: meal_plan.all_meals.get(current_meal_id).unwrap().id,
+								servings: None,
 							})
 							.width(Length::Fill)
 							.style(|theme, _style| success_button(theme)),
 					]
 					.spacing(5),
-					self.view_meal(meal_info)
+					self.view_meal(&meal_plan, meal_info)
 				]
 				.spacing(10)
 				.into(),
@@ -1,5 +1,6 @@
 mod component;
 mod meal_component;
+mod meal_filter_component;
 mod meals_chooser_component;
 mod meals_list_component;
 mod meals_random_chooser_component;
@@ -9,15 +10,22 @@ pub use component::CalendarState;
 pub use component::Meals;
 pub use component::MealsMessage;
 pub use meal_component::meal_contents;
+pub use meal_component::meal_nutrition;
+pub use meal_filter_component::meal_filter;
 pub use meals_chooser_component::MealsChooser;
 pub use meals_list_component::MealsList;
 pub use meals_random_chooser_component::RandomMealChooser;
 
 use iced::color;
 use iced::Color;
+use iced::Task;
+use meals_database::DatabaseError;
 use std::collections::HashMap;
+use std::future::Future;
 use uuid::Uuid;
 
+use crate::Message;
+
 pub const COLORS: [Color; 9] = [
 	color!(0xE059E0), // #E059E0
 	color!(0x9D87FF), // #9D87FF
@@ -37,3 +45,16 @@ pub fn get_meal_color(meal_id_to_color: &mut HashMap<Uuid, Color>, uuid: &Uuid)
 
 	meal_id_to_color.get(uuid).unwrap().clone()
 }
+
+pub fn update_task<F>(future: F) -> Task<Message>
+where
+	F: Future<Output = Result<(), DatabaseError>> + Send + 'static,
+{
+	Task::future(async move {
+		if let Err(error) = future.await {
+			log::error!("Could not update meals database: {:?}", error);
+		}
+
+		Message::Noop
+	})
+}
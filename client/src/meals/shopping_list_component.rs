@@ -2,63 +2,104 @@ use iced::{
 	widget::{button, checkbox, column, container, row, text},
 	Element, Length, Task, Theme,
 };
-use meals_database::{MealPlan, RestDatabase, ShoppingListInfo, ShoppingListItem};
+use meals_database::{format_amount, Database, MealPlan, ShoppingListInfo, ShoppingListItem};
 use std::sync::Arc;
+use uuid::Uuid;
 
 use crate::{
-	styles::{checkbox_style, primary_button},
+	styles::{checkbox_style, primary_button, subdued_button},
 	Message,
 };
 
-use super::MealsMessage;
+use super::{update_task, MealsMessage};
 
 pub struct ShoppingList {
-	meals_database: Arc<RestDatabase<MealPlan>>,
+	meals_database: Arc<dyn Database>,
 }
 
 impl ShoppingList {
-	pub fn new(meals_database: Arc<RestDatabase<MealPlan>>) -> Self {
+	pub fn new(meals_database: Arc<dyn Database>) -> Self {
 		Self { meals_database }
 	}
 
 	fn view_shopping_list_item(
 		&self,
-		shopping_list_index: usize,
+		shopping_list_id: Uuid,
 		item: &ShoppingListItem,
 	) -> Element<MealsMessage> {
-		let name = item.name.clone();
+		let id = item.id;
 		row![
 			checkbox("", item.have)
 				.on_toggle(move |_toggle| MealsMessage::ToggleShoppingListItem {
-					name: name.clone(),
-					shopping_list_index,
+					id,
+					shopping_list_id,
 				})
 				.style(|_theme, status| checkbox_style(status)),
 			container(text!("{}", item.name)).width(250),
-			container(text!("{} {}", item.amount.value, item.amount.units))
+			container(text(format_amount(&item.amount))).width(Length::Fill),
+			button(text!("✕"))
+				.on_press(MealsMessage::RemoveShoppingListItem {
+					id,
+					shopping_list_id,
+				})
+				.style(|theme, _status| subdued_button(theme))
 		]
 		.into()
 	}
 
 	fn view_shopping_list(
 		&self,
+		meal_plan: &MealPlan,
 		shopping_list: &ShoppingListInfo,
 		shopping_list_index: usize,
 	) -> Element<MealsMessage> {
+		let mut buttons = row![button(text!("Prune"))
+			.on_press(MealsMessage::PruneShoppingList {
+				shopping_list_id: shopping_list.id,
+			})
+			.style(|theme, _status| primary_button(theme))]
+		.spacing(10);
+
+		// lists are merged upwards, into the one above
+		if let Some(previous) = shopping_list_index
+			.checked_sub(1)
+			.and_then(|index| meal_plan.shopping_list.get(index))
+		{
+			buttons = buttons.push(
+				button(text!("Merge up"))
+					.on_press(MealsMessage::MergeShoppingLists {
+						from_id: shopping_list.id,
+						into_id: previous.id,
+					})
+					.style(|theme, _status| primary_button(theme)),
+			);
+		}
+
+		let name = if shopping_list.name.is_empty() {
+			format!("Shopping list #{}", shopping_list_index + 1)
+		} else {
+			shopping_list.name.clone()
+		};
+
 		container(
 			column![
+				text(name),
 				column(
-					shopping_list
-						.items
-						.iter()
-						.map(|item| self.view_shopping_list_item(shopping_list_index, &item)),
+					meal_plan
+						.group_by_aisle(&shopping_list.items)
+						.into_iter()
+						.map(|(aisle, items)| {
+							column![text!("{}", aisle)]
+								.extend(items.into_iter().map(|item| {
+									self.view_shopping_list_item(shopping_list.id, item)
+								}))
+								.spacing(5)
+								.into()
+						})
 				)
+				.spacing(15)
 				.width(Length::Fill),
-				row![button(text!("Prune"))
-					.on_press(MealsMessage::PruneShoppingList {
-						shopping_list_index
-					})
-					.style(|theme, _status| primary_button(theme))]
+				buttons
 			]
 			.spacing(20),
 		)
@@ -69,82 +110,55 @@ impl ShoppingList {
 	}
 
 	pub fn update(&self, message: MealsMessage) -> Task<Message> {
+		let meals_database = self.meals_database.clone();
 		match message {
 			MealsMessage::GenerateShoppingList => {
-				let mut meal_plan = self.meals_database.get_mut();
-
-				let new_shopping_list = meal_plan.generate_shopping_list();
-				if let Some(new_shopping_list) = new_shopping_list {
-					meal_plan.shopping_list.push(new_shopping_list);
-				}
-
-				drop(meal_plan);
-
-				let meals_database = self.meals_database.clone();
-				Task::future(async move {
-					meals_database.save().await;
-					Message::Noop
-				})
+				update_task(async move { meals_database.generate_shopping_list().await })
 			}
-			MealsMessage::GenerateShoppingListForMeal { date, id } => {
-				let mut meal_plan = self.meals_database.get_mut();
-				let shopping_list = meal_plan.generate_shopping_list_for_meal(date, id);
-				meal_plan.shopping_list.push(shopping_list);
-				drop(meal_plan);
-
-				let meals_database = self.meals_database.clone();
-				Task::future(async move {
-					meals_database.save().await;
-					Message::Noop
-				})
+			MealsMessage::GenerateShoppingListForMeal { date, id } => update_task(async move {
+				meals_database
+					.generate_shopping_list_for_meal(date, id)
+					.await
+			}),
+			MealsMessage::PruneShoppingList { shopping_list_id } => {
+				update_task(
+					async move { meals_database.prune_shopping_list(shopping_list_id).await },
+				)
 			}
-			MealsMessage::PruneShoppingList {
-				shopping_list_index,
-			} => {
-				let mut meal_plan = self.meals_database.get_mut();
-
-				let shopping_list = meal_plan
-					.shopping_list
-					.get_mut(shopping_list_index)
-					.unwrap();
-
-				shopping_list.items.retain(|item| !item.have);
-
-				if shopping_list.items.len() == 0 {
-					meal_plan.shopping_list.remove(shopping_list_index);
-				}
-
-				drop(meal_plan);
-
-				let meals_database = self.meals_database.clone();
-				Task::future(async move {
-					meals_database.save().await;
-					Message::Noop
-				})
+			MealsMessage::MergeShoppingLists { from_id, into_id } => {
+				update_task(
+					async move { meals_database.merge_shopping_lists(from_id, into_id).await },
+				)
 			}
+			MealsMessage::RemoveShoppingListItem {
+				id,
+				shopping_list_id,
+			} => update_task(async move {
+				meals_database
+					.remove_shopping_list_item(shopping_list_id, id)
+					.await
+			}),
 			MealsMessage::ToggleShoppingListItem {
-				name,
-				shopping_list_index,
+				id,
+				shopping_list_id,
 			} => {
-				let mut meal_plan = self.meals_database.get_mut();
+				let meal_plan = self.meals_database.get();
 
-				let shopping_list_item = meal_plan
-					.shopping_list
-					.get_mut(shopping_list_index)
+				let have = meal_plan
+					.shopping_list_by_id(shopping_list_id)
 					.unwrap()
 					.items
-					.iter_mut()
-					.find(|item| item.name == name)
-					.unwrap();
-
-				shopping_list_item.have = !shopping_list_item.have;
+					.iter()
+					.find(|item| item.id == id)
+					.unwrap()
+					.have;
 
 				drop(meal_plan);
 
-				let meals_database = self.meals_database.clone();
-				Task::future(async move {
-					meals_database.save().await;
-					Message::Noop
+				update_task(async move {
+					meals_database
+						.set_shopping_list_item(shopping_list_id, id, !have)
+						.await
 				})
 			}
 			_ => unreachable!(),
@@ -163,7 +177,7 @@ impl ShoppingList {
 						.iter()
 						.enumerate()
 						.map(|(index, shopping_list)| {
-							self.view_shopping_list(shopping_list, index)
+							self.view_shopping_list(&meal_plan, shopping_list, index)
 						}),
 				)
 				.spacing(10)
@@ -1,10 +1,11 @@
+use chrono::NaiveDate;
 use meals_database::{
-	normalize_recipe_json, Amount, Ingredient, MealInfo, MealPlan, MealPlanMessage, RecipeJSON,
-	RecipeStep, RestDatabase, Units,
+	export_recipes, format_amount, import_recipes, parse_nutrition_csv, scrape_recipe,
+	shopping_list_to_markdown, shopping_list_to_text, Aisle, Amount, CatalogEntry, DatabaseConfig,
+	DatabaseError, Diet, Frequency, Ingredient, MealFilter, MealInfo, PantryItem, ParsedIngredient,
+	RecipeFormat, RecipeStep, ShoppingListItem, Staple, Units,
 };
-use std::process::Command;
 use std::{io::Write, str::FromStr};
-use tokio::sync::mpsc::Receiver;
 use uuid::Uuid;
 
 #[tokio::main]
@@ -12,15 +13,9 @@ async fn main() {
 	/*env_logger::init();
 
 	let (mut database, mut receiver): (RestDatabase<MealPlan>, Receiver<MealPlanMessage>) =
-		RestDatabase::new(
-			"http://0.0.0.0:8001/rest/meals/all",
-			"http://0.0.0.0:8001/rest/meals/replace",
-			"ws://0.0.0.0:8001/ws/meals-events",
-		)
-		.await;
+		RestDatabase::new("http://0.0.0.0:8001/rest", "ws://0.0.0.0:8001/ws/meals-events").await;
 
-	database.load().await;
-	database.save().await;
+	database.load().await.unwrap();
 
 	select! {
 		message = receiver.recv().fuse() => {
@@ -31,14 +26,15 @@ async fn main() {
 		}
 	}*/
 
-	let (database, _): (RestDatabase<MealPlan>, Receiver<MealPlanMessage>) = RestDatabase::new(
-		"http://bansheestorage-alt:8001/rest/meals/all",
-		"http://bansheestorage-alt:8001/rest/meals/replace",
-		"ws://bansheestorage-alt:8001/ws/meals-events",
-	)
-	.await;
-
-	database.load().await;
+	let database = DatabaseConfig::from_env()
+		.unwrap()
+		.unwrap_or(DatabaseConfig::Rest {
+			rest_url: "http://bansheestorage-alt:8001/rest".into(),
+			ws_url: "ws://bansheestorage-alt:8001/ws/meals-events".into(),
+		})
+		.open()
+		.await
+		.unwrap();
 
 	loop {
 		println!("1. Enter recipe");
@@ -47,80 +43,84 @@ async fn main() {
 		println!("4. List recipes");
 		println!("5. Search recipes");
 		println!("6. Add via URL");
-		println!("7. Exit");
+		println!("7. Edit ingredient catalog");
+		println!("8. Edit pantry");
+		println!("9. Edit staples");
+		println!("10. Edit extra shopping list items");
+		println!("11. Export shopping list");
+		println!("12. Show statistics");
+		println!("13. Find recipes by ingredients");
+		println!("14. Import nutrition CSV");
+		println!("15. Import recipes");
+		println!("16. Export recipes");
+		println!("17. Exit");
 
 		let option = readline();
 		match option.as_str() {
 			"1" => {
 				let recipe = enter_recipe();
-				database.get_mut().all_meals.insert(recipe.id, recipe);
-				database.save().await;
+				report(database.save_meal(recipe).await);
 			}
 			"2" => {
-				let mut meal_plan = database.get_mut();
-				let mut index_to_meal_id = vec![];
+				let index_to_meal_id = {
+					let meal_plan = database.get();
+					let mut index_to_meal_id = vec![];
 
-				let mut meals = meal_plan
-					.all_meals
-					.values_mut()
-					.collect::<Vec<&mut MealInfo>>();
+					let mut meals = meal_plan.all_meals.values().collect::<Vec<&MealInfo>>();
 
-				meals.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+					meals.sort_by_key(|meal| meal.name.to_lowercase());
 
-				for meal in meals.iter_mut() {
-					let index = index_to_meal_id.len();
+					for meal in meals.iter() {
+						let index = index_to_meal_id.len();
 
-					println!("#{:<5} {}", index + 1, meal.name);
-					index_to_meal_id.push(meal);
-				}
+						println!("#{:<5} {}", index + 1, meal.name);
+						index_to_meal_id.push((meal.id, meal.name.clone()));
+					}
+
+					index_to_meal_id
+				};
 
 				let index: usize = read_number().unwrap();
-				let meal = index_to_meal_id.get_mut(index - 1).unwrap();
+				let (meal_id, meal_name) = index_to_meal_id.get(index - 1).unwrap().clone();
 
-				println!("Are you sure you want to delete '{}'? Y/n", meal.name);
+				println!("Are you sure you want to delete '{}'? Y/n", meal_name);
 
 				let answer = readline();
-				if answer.to_lowercase() == "y" {
-					println!("Removed '{}'", meal.name);
-
-					let meal_id = meal.id.clone();
-					meal_plan.remove_meal(meal_id);
+				if answer.to_lowercase() == "y" && report(database.delete_meal(meal_id).await) {
+					println!("Removed '{}'", meal_name);
 				}
-
-				drop(meal_plan);
-				database.save().await;
 			}
 			"3" => {
-				let mut meal_plan = database.get_mut();
-				let mut index_to_meal_id = vec![];
+				let index_to_meal = {
+					let meal_plan = database.get();
+					let mut index_to_meal = vec![];
 
-				let mut meals = meal_plan
-					.all_meals
-					.values_mut()
-					.collect::<Vec<&mut MealInfo>>();
+					let mut meals = meal_plan.all_meals.values().collect::<Vec<&MealInfo>>();
 
-				meals.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+					meals.sort_by_key(|meal| meal.name.to_lowercase());
 
-				for meal in meals.iter_mut() {
-					let index = index_to_meal_id.len();
+					for meal in meals.iter() {
+						let index = index_to_meal.len();
 
-					println!("#{:<5} {}", index + 1, meal.name);
-					index_to_meal_id.push(meal);
-				}
+						println!("#{:<5} {}", index + 1, meal.name);
+						index_to_meal.push((*meal).clone());
+					}
+
+					index_to_meal
+				};
 
 				let index: usize = read_number().unwrap();
-				let meal = index_to_meal_id.get_mut(index - 1).unwrap();
+				let mut meal = index_to_meal.get(index - 1).unwrap().clone();
 
 				println!("Edit '{}':", meal.name);
-				edit_meal(meal);
+				edit_meal(&mut meal);
 
-				drop(meal_plan);
-				database.save().await;
+				report(database.save_meal(meal).await);
 			}
 			"4" => {
 				let meal_plan = database.get();
 				let mut meals = meal_plan.all_meals.values().collect::<Vec<_>>();
-				meals.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+				meals.sort_by_key(|meal| meal.name.to_lowercase());
 
 				for meal in meals.iter() {
 					println!("{}", meal.name);
@@ -129,18 +129,14 @@ async fn main() {
 			}
 			"5" => {
 				let meal_plan = database.get();
-				let mut meals = meal_plan.all_meals.values().collect::<Vec<_>>();
-				meals.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
 
-				println!("Enter query:");
+				println!("Enter query, like \"soup ingredient:chicken\":");
 				let query = readline();
-				let query = query.to_lowercase();
-				let query = query.trim();
 
-				for meal in meals.iter() {
-					if !meal.name.to_lowercase().trim().contains(query) {
+				for result in meal_plan.search(&query) {
+					let Some(meal) = meal_plan.all_meals.get(&result.id) else {
 						continue;
-					}
+					};
 
 					println!("{}", meal.name);
 					println!("  Image: {}", meal.image);
@@ -149,27 +145,46 @@ async fn main() {
 			"6" => {
 				let url = readline();
 
-				let recipe_json = Command::new("python")
-					.args(["/home/me/Projects/bansheelong2/meals_database/src/bins/scraper.py", &url])
-					.output()
-					.unwrap()
-					.stdout;
-
-				let recipe_json: RecipeJSON =
-					serde_json::from_str(&String::from_utf8(recipe_json).unwrap()).unwrap();
-
-				let normalized_recipe = normalize_recipe_json(recipe_json);
+				let normalized_recipe = match scrape_recipe(&url).await {
+					Ok(normalized_recipe) => normalized_recipe,
+					Err(error) => {
+						println!("{}", error);
+						continue;
+					}
+				};
+
+				let mut meal_info = MealInfo {
+					id: Uuid::new_v4(),
+					..Default::default()
+				};
+				for (index, parsed_ingredient) in normalized_recipe.ingredients.iter().enumerate() {
+					if !parsed_ingredient.confident {
+						println!(
+							"!!! Check ingredient #{}, could not fully parse '{}'",
+							index + 1,
+							parsed_ingredient.original
+						);
+					}
+				}
 
-				let mut meal_info = MealInfo::default();
-				meal_info.id = Uuid::new_v4();
-				meal_info.ingredients = normalized_recipe.ingredients;
+				meal_info.ingredients = normalized_recipe
+					.ingredients
+					.into_iter()
+					.map(ParsedIngredient::into_ingredient)
+					.collect();
 				meal_info.recipe = normalized_recipe.recipe;
 				meal_info.name = normalized_recipe.name;
+				meal_info.cook_minutes = normalized_recipe.cook_minutes;
+				meal_info.course = normalized_recipe.course;
+				meal_info.cuisine = normalized_recipe.cuisine;
+				meal_info.diets = normalized_recipe.diets;
+				meal_info.prep_minutes = normalized_recipe.prep_minutes;
+				meal_info.tags = normalized_recipe.tags;
 
 				println!("Successfully downloaded '{}'", meal_info.name);
 				println!("New Name:");
 				let new_name = readline();
-				if new_name != "" {
+				if !new_name.is_empty() {
 					meal_info.name = new_name
 				}
 
@@ -191,17 +206,415 @@ async fn main() {
 
 				println!("Saving '{}'...", meal_info.name);
 
-				database.get_mut().all_meals.insert(meal_info.id, meal_info);
-				database.save().await;
+				if report(database.save_meal(meal_info).await) {
+					println!("Saved.");
+				}
+			}
+			"7" => {
+				let mut entries = database
+					.get()
+					.ingredient_catalog
+					.values()
+					.cloned()
+					.collect::<Vec<_>>();
+
+				entries.sort_by_key(|entry| entry.name.to_lowercase());
+
+				for (index, entry) in entries.iter().enumerate() {
+					println!(
+						"#{:<5} {} ({}) {}",
+						index + 1,
+						entry.name,
+						entry.aisle,
+						entry.aliases.join(", ")
+					);
+				}
+
+				println!("#{:<5} Add new ingredient", entries.len() + 1);
+
+				let Some(index) = read_number_in_range(1, entries.len() + 1) else {
+					continue;
+				};
+
+				let Some(entry) = entries.get(index - 1).cloned() else {
+					let entry = edit_catalog_entry(CatalogEntry::default());
+					report(database.save_catalog_entry(entry).await);
+					continue;
+				};
+
+				println!("1. Edit");
+				println!("2. Delete");
+
+				match readline().as_str() {
+					"1" => {
+						let old_name = entry.name.clone();
+						let entry = edit_catalog_entry(entry);
+						if entry.name.to_lowercase() != old_name.to_lowercase()
+							&& !report(database.delete_catalog_entry(&old_name).await)
+						{
+							continue;
+						}
+
+						report(database.save_catalog_entry(entry).await);
+					}
+					"2" => {
+						if report(database.delete_catalog_entry(&entry.name).await) {
+							println!("Removed '{}'", entry.name);
+						}
+					}
+					_ => continue,
+				}
+			}
+			"8" => {
+				let mut items = database.get().pantry.values().cloned().collect::<Vec<_>>();
+
+				items.sort_by_key(|item| item.name.to_lowercase());
+
+				for (index, item) in items.iter().enumerate() {
+					match item.expires {
+						Some(expires) => println!(
+							"#{:<5} {}, {} (expires {})",
+							index + 1,
+							item.name,
+							format_amount(&item.amount),
+							expires
+						),
+						None => println!(
+							"#{:<5} {}, {}",
+							index + 1,
+							item.name,
+							format_amount(&item.amount)
+						),
+					}
+				}
+
+				println!("#{:<5} Add new item", items.len() + 1);
+
+				let Some(index) = read_number_in_range(1, items.len() + 1) else {
+					continue;
+				};
+
+				let Some(item) = items.get(index - 1).cloned() else {
+					println!("Enter name:");
+					let name = readline();
+					if name.is_empty() {
+						continue;
+					}
+
+					let Some(item) = edit_pantry_item(PantryItem {
+						name,
+						..Default::default()
+					}) else {
+						continue;
+					};
+
+					report(database.set_pantry_item(item).await);
+					continue;
+				};
+
+				println!("1. Edit");
+				println!("2. Remove");
+
+				match readline().as_str() {
+					"1" => {
+						if let Some(item) = edit_pantry_item(item) {
+							report(database.set_pantry_item(item).await);
+						}
+					}
+					"2" => {
+						if report(database.remove_pantry_item(&item.name).await) {
+							println!("Removed '{}'", item.name);
+						}
+					}
+					_ => continue,
+				}
+			}
+			"9" => {
+				let mut staples = database.get().staples.values().cloned().collect::<Vec<_>>();
+
+				staples.sort_by_key(|staple| staple.name.to_lowercase());
+
+				for (index, staple) in staples.iter().enumerate() {
+					println!(
+						"#{:<5} {}, {} ({})",
+						index + 1,
+						staple.name,
+						format_amount(&staple.amount),
+						staple.frequency
+					);
+				}
+
+				println!("#{:<5} Add new staple", staples.len() + 1);
+
+				let Some(index) = read_number_in_range(1, staples.len() + 1) else {
+					continue;
+				};
+
+				let Some(staple) = staples.get(index - 1).cloned() else {
+					println!("Enter name:");
+					let name = readline();
+					if name.is_empty() {
+						continue;
+					}
+
+					let Some(staple) = edit_staple(Staple {
+						name,
+						..Default::default()
+					}) else {
+						continue;
+					};
+
+					report(database.save_staple(staple).await);
+					continue;
+				};
+
+				println!("1. Edit");
+				println!("2. Remove");
+
+				match readline().as_str() {
+					"1" => {
+						if let Some(staple) = edit_staple(staple) {
+							report(database.save_staple(staple).await);
+						}
+					}
+					"2" => {
+						if report(database.remove_staple(&staple.name).await) {
+							println!("Removed '{}'", staple.name);
+						}
+					}
+					_ => continue,
+				}
+			}
+			"10" => {
+				let items = database.get().extra_items.clone();
+
+				for (index, item) in items.iter().enumerate() {
+					println!(
+						"#{:<5} {}, {}",
+						index + 1,
+						item.name,
+						format_amount(&item.amount)
+					);
+				}
+
+				println!("#{:<5} Add new item", items.len() + 1);
+
+				let Some(index) = read_number_in_range(1, items.len() + 1) else {
+					continue;
+				};
+
+				let Some(item) = items.get(index - 1) else {
+					println!("Enter name:");
+					let name = readline();
+					if name.is_empty() {
+						continue;
+					}
+
+					let Some(units) = read_units() else {
+						continue;
+					};
+
+					println!("Enter amount:");
+					let Some(value) = read_number() else {
+						continue;
+					};
+
+					report(
+						database
+							.add_extra_item(ShoppingListItem::new(name, Amount { units, value }))
+							.await,
+					);
+					continue;
+				};
+
+				println!("Remove '{}'? y/n", item.name);
+				if readline().to_lowercase() == "y"
+					&& report(database.remove_extra_item(&item.name).await)
+				{
+					println!("Removed '{}'", item.name);
+				}
+			}
+			"11" => {
+				let meal_plan = database.get();
+				if meal_plan.shopping_list.is_empty() {
+					println!("There are no shopping lists");
+					continue;
+				}
+
+				for (index, shopping_list) in meal_plan.shopping_list.iter().enumerate() {
+					println!("#{:<5} {} items", index + 1, shopping_list.items.len());
+				}
+
+				let Some(index) = read_number_in_range(1, meal_plan.shopping_list.len()) else {
+					continue;
+				};
+
+				println!("1. Plain text");
+				println!("2. Markdown");
+
+				let shopping_list = &meal_plan.shopping_list[index - 1];
+				let export = match readline().as_str() {
+					"1" => shopping_list_to_text(&meal_plan, shopping_list),
+					"2" => shopping_list_to_markdown(&meal_plan, shopping_list),
+					_ => continue,
+				};
+
+				drop(meal_plan);
+
+				println!("Enter file to save to, or nothing to print it:");
+				let path = readline();
+				if path.is_empty() {
+					println!("{}", export);
+				} else if let Err(error) = std::fs::write(&path, export) {
+					println!("Could not write '{}': {}", path, error);
+				}
+			}
+			"12" => {
+				println!("Enter first date as YYYY-MM-DD, or nothing for the beginning:");
+				let from = readline().parse::<NaiveDate>().ok();
+				println!("Enter last date as YYYY-MM-DD, or nothing for today:");
+				let to = readline().parse::<NaiveDate>().ok();
+
+				let statistics = database.get().statistics(from, to);
+
+				println!("Meals cooked: {}", statistics.total_cooked);
+				println!("Current streak: {} days", statistics.current_streak);
+				println!("Longest streak: {} days", statistics.longest_streak);
+
+				println!("Most cooked:");
+				for meal in statistics.most_cooked(5) {
+					println!("  {:<5} {}", meal.times_cooked, meal.name);
+				}
+
+				println!("Least cooked:");
+				for meal in statistics.least_cooked(5) {
+					match meal.last_cooked {
+						Some(last_cooked) => println!(
+							"  {:<5} {} (last {})",
+							meal.times_cooked, meal.name, last_cooked
+						),
+						None => println!("  {:<5} {} (never)", meal.times_cooked, meal.name),
+					}
+				}
+
+				println!("Ingredients used:");
+				for ingredient in statistics.ingredients.iter() {
+					println!(
+						"  {}, {}",
+						ingredient.name,
+						format_amount(&ingredient.amount)
+					);
+				}
+			}
+			"13" => {
+				let meal_plan = database.get();
+
+				println!("Enter ingredients, separated by commas, or nothing for checked shopping list items:");
+				let ingredients = readline();
+				let ingredients = if ingredients.trim().is_empty() {
+					meal_plan.checked_items()
+				} else {
+					ingredients
+						.split(',')
+						.map(|name| name.trim().to_string())
+						.collect()
+				};
+
+				for meal in meal_plan.cookable_meals(&ingredients) {
+					println!("{:>3.0}% {}", meal.coverage * 100.0, meal.name);
+					if !meal.missing.is_empty() {
+						println!("     Missing: {}", meal.missing.join(", "));
+					}
+				}
+			}
+			"14" => {
+				println!("Enter path to CSV:");
+				let path = readline();
+
+				let file = match std::fs::File::open(path.trim()) {
+					Ok(file) => file,
+					Err(error) => {
+						println!("!!! {}", error);
+						continue;
+					}
+				};
+
+				match parse_nutrition_csv(file) {
+					Ok(entries) => {
+						let count = entries.len();
+						if report(database.import_nutrition(entries).await) {
+							println!("Imported {} ingredients", count);
+						}
+					}
+					Err(error) => println!("!!! {}", error),
+				}
+			}
+			"15" => {
+				println!("Enter path to recipes:");
+				let path = readline();
+
+				let Some(format) = read_recipe_format() else {
+					continue;
+				};
+
+				let text = match std::fs::read_to_string(path.trim()) {
+					Ok(text) => text,
+					Err(error) => {
+						println!("!!! {}", error);
+						continue;
+					}
+				};
+
+				match import_recipes(&text, format) {
+					Ok(meals) => {
+						let count = meals.len();
+						if report(database.import_meals(meals).await) {
+							println!("Imported {} recipes", count);
+						}
+					}
+					Err(error) => println!("!!! {}", error),
+				}
+			}
+			"16" => {
+				let Some(format) = read_recipe_format() else {
+					continue;
+				};
+
+				println!(
+					"Enter path to write to, or nothing for recipes.{}:",
+					format.extension()
+				);
+				let path = readline();
+				let path = if path.trim().is_empty() {
+					format!("recipes.{}", format.extension())
+				} else {
+					path.trim().to_string()
+				};
 
-				println!("Saved.");
+				let meal_plan = database.get();
+				let meals = meal_plan.filter_meals(&MealFilter::default());
+				let count = meals.len();
+				match std::fs::write(&path, export_recipes(meals, format)) {
+					Ok(()) => println!("Exported {} recipes to {}", count, path),
+					Err(error) => println!("!!! {}", error),
+				}
 			}
-			"7" => std::process::exit(0),
+			"17" => std::process::exit(0),
 			_ => continue,
 		}
 	}
 }
 
+fn report(result: Result<(), DatabaseError>) -> bool {
+	match result {
+		Ok(()) => true,
+		Err(error) => {
+			println!("!!! {}", error);
+			false
+		}
+	}
+}
+
 fn edit_name(meal: &mut MealInfo) {
 	println!("Enter name:");
 	meal.name = readline();
@@ -279,12 +692,73 @@ fn edit_steps(meal: &mut MealInfo) {
 	step.description = readline();
 }
 
+/// Prompts for the course, cuisine, tags, times and diets. Entering nothing keeps the current value.
+fn edit_tags(meal: &mut MealInfo) {
+	println!("Enter course, currently '{}':", meal.course);
+	let course = readline();
+	if !course.is_empty() {
+		meal.course = course;
+	}
+
+	println!("Enter cuisine, currently '{}':", meal.cuisine);
+	let cuisine = readline();
+	if !cuisine.is_empty() {
+		meal.cuisine = cuisine;
+	}
+
+	println!(
+		"Enter comma separated tags, currently '{}':",
+		meal.tags.join(", ")
+	);
+	let tags = readline();
+	if !tags.is_empty() {
+		meal.tags = tags
+			.split(',')
+			.map(str::trim)
+			.filter(|tag| !tag.is_empty())
+			.map(String::from)
+			.collect();
+	}
+
+	println!("Enter prep time in minutes:");
+	if let Some(prep_minutes) = read_number() {
+		meal.prep_minutes = Some(prep_minutes);
+	}
+
+	println!("Enter cook time in minutes:");
+	if let Some(cook_minutes) = read_number() {
+		meal.cook_minutes = Some(cook_minutes);
+	}
+
+	for (index, diet) in Diet::ALL.iter().enumerate() {
+		println!("{}. {}", index + 1, diet);
+	}
+
+	println!(
+		"Enter comma separated diets, currently '{}':",
+		meal.diets
+			.iter()
+			.map(Diet::to_string)
+			.collect::<Vec<_>>()
+			.join(", ")
+	);
+	let diets = readline();
+	if !diets.is_empty() {
+		meal.diets = diets
+			.split(',')
+			.filter_map(|index| index.trim().parse::<usize>().ok())
+			.filter_map(|index| Diet::ALL.get(index.wrapping_sub(1)).copied())
+			.collect();
+	}
+}
+
 fn edit_meal(meal: &mut MealInfo) {
 	println!("1. Edit name");
 	println!("2. Edit serving size");
 	println!("3. Edit image url");
 	println!("4. Edit ingredients");
 	println!("5. Edit steps");
+	println!("6. Edit tags and diets");
 
 	loop {
 		let option = readline();
@@ -294,6 +768,7 @@ fn edit_meal(meal: &mut MealInfo) {
 			"3" => edit_image(meal),
 			"4" => edit_ingredients(meal),
 			"5" => edit_steps(meal),
+			"6" => edit_tags(meal),
 			_ => continue,
 		}
 
@@ -301,6 +776,81 @@ fn edit_meal(meal: &mut MealInfo) {
 	}
 }
 
+/// Prompts for each field of a catalog entry. Entering nothing keeps the current value.
+fn edit_catalog_entry(mut entry: CatalogEntry) -> CatalogEntry {
+	loop {
+		println!("Enter name:");
+		let name = readline();
+		if !name.is_empty() {
+			entry.name = name;
+		}
+
+		if !entry.name.is_empty() {
+			break;
+		}
+	}
+
+	println!("Enter other names, separated by commas:");
+	let aliases = readline();
+	if !aliases.is_empty() {
+		entry.aliases = aliases
+			.split(',')
+			.map(|alias| alias.trim().to_string())
+			.filter(|alias| !alias.is_empty())
+			.collect();
+	}
+
+	if let Some(aisle) = read_aisle() {
+		entry.aisle = aisle;
+	}
+
+	println!("Always buy in the same units? y/N");
+	if readline().to_lowercase() == "y" {
+		entry.default_units = read_units();
+	} else {
+		entry.default_units = None;
+	}
+
+	entry
+}
+
+fn edit_pantry_item(mut item: PantryItem) -> Option<PantryItem> {
+	let units = read_units()?;
+
+	println!("Enter amount:");
+	let value = read_number()?;
+
+	item.amount = Amount { units, value };
+
+	loop {
+		println!("Enter expiry date as YYYY-MM-DD, or nothing if it keeps:");
+		let expires = readline();
+		if expires.is_empty() {
+			item.expires = None;
+			break;
+		}
+
+		if let Ok(expires) = expires.parse::<NaiveDate>() {
+			item.expires = Some(expires);
+			break;
+		}
+	}
+
+	Some(item)
+}
+
+fn edit_staple(mut staple: Staple) -> Option<Staple> {
+	let units = read_units()?;
+
+	println!("Enter amount:");
+	let value = read_number()?;
+
+	staple.amount = Amount { units, value };
+	staple.frequency = read_frequency()?;
+
+	Some(staple)
+}
+
 fn enter_recipe() -> MealInfo {
 	println!("Enter name:");
 	let name = readline();
@@ -314,11 +864,7 @@ fn enter_recipe() -> MealInfo {
 	let image = readline();
 
 	let mut ingredients = vec![];
-	loop {
-		let Some(units) = read_units() else {
-			break;
-		};
-
+	while let Some(units) = read_units() {
 		println!("Enter amount:");
 		let Some(value) = read_number() else {
 			break;
@@ -326,7 +872,7 @@ fn enter_recipe() -> MealInfo {
 
 		println!("Enter name:");
 		let name = readline();
-		if name.len() == 0 {
+		if name.is_empty() {
 			break;
 		}
 
@@ -340,21 +886,26 @@ fn enter_recipe() -> MealInfo {
 	loop {
 		println!("Enter step:");
 		let description = readline();
-		if description.len() == 0 {
+		if description.is_empty() {
 			break;
 		}
 
 		recipe.push(RecipeStep { description });
 	}
 
-	return MealInfo {
+	let mut meal_info = MealInfo {
 		id: Uuid::new_v4(),
 		image,
 		ingredients,
 		name,
 		recipe,
 		serving_size,
+		..Default::default()
 	};
+
+	edit_tags(&mut meal_info);
+
+	meal_info
 }
 
 fn read_number<T>() -> Option<T>
@@ -363,7 +914,7 @@ where
 {
 	loop {
 		let value = readline();
-		if value.len() == 0 {
+		if value.is_empty() {
 			return None;
 		}
 
@@ -381,7 +932,7 @@ where
 {
 	loop {
 		let value = readline();
-		if value.len() == 0 {
+		if value.is_empty() {
 			return None;
 		}
 
@@ -404,6 +955,15 @@ fn read_units() -> Option<Units> {
 	println!("3. Ounces");
 	println!("4. Tablespoons");
 	println!("5. Teaspoons");
+	println!("6. Milliliters");
+	println!("7. Grams");
+	println!("8. Kilograms");
+	println!("9. Pounds");
+	println!("10. Liters");
+	println!("11. Fluid ounces");
+	println!("12. Pints");
+	println!("13. Quarts");
+	println!("14. Pinches");
 
 	loop {
 		let ingredient = readline();
@@ -413,12 +973,51 @@ fn read_units() -> Option<Units> {
 			"3" => return Some(Units::Ounce),
 			"4" => return Some(Units::Tablespoon),
 			"5" => return Some(Units::Teaspoon),
+			"6" => return Some(Units::Milliliters),
+			"7" => return Some(Units::Grams),
+			"8" => return Some(Units::Kilograms),
+			"9" => return Some(Units::Pounds),
+			"10" => return Some(Units::Liters),
+			"11" => return Some(Units::FluidOunces),
+			"12" => return Some(Units::Pints),
+			"13" => return Some(Units::Quarts),
+			"14" => return Some(Units::Pinches),
 			"" => return None,
 			_ => continue,
 		}
 	}
 }
 
+fn read_aisle() -> Option<Aisle> {
+	println!("Enter aisle:");
+	for (index, aisle) in Aisle::ALL.iter().enumerate() {
+		println!("{}. {}", index + 1, aisle);
+	}
+
+	let index: usize = read_number_in_range(1, Aisle::ALL.len())?;
+	Some(Aisle::ALL[index - 1])
+}
+
+fn read_frequency() -> Option<Frequency> {
+	println!("Enter frequency:");
+	for (index, frequency) in Frequency::ALL.iter().enumerate() {
+		println!("{}. {}", index + 1, frequency);
+	}
+
+	let index: usize = read_number_in_range(1, Frequency::ALL.len())?;
+	Some(Frequency::ALL[index - 1])
+}
+
+fn read_recipe_format() -> Option<RecipeFormat> {
+	println!("Enter format:");
+	for (index, format) in RecipeFormat::ALL.iter().enumerate() {
+		println!("{}. {}", index + 1, format);
+	}
+
+	let index: usize = read_number_in_range(1, RecipeFormat::ALL.len())?;
+	Some(RecipeFormat::ALL[index - 1])
+}
+
 fn readline() -> String {
 	print!("> ");
 	std::io::stdout().flush().unwrap();
@@ -0,0 +1,157 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+use crate::{MealPlan, MealPlanError, ShoppingListItem, Units};
+
+/// Where an ingredient is found in the grocery store. Declared in the order the store is walked, so
+/// sorting a shopping list by aisle gives a route through it.
+#[derive(
+	Clone, Copy, Debug, Default, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize,
+)]
+pub enum Aisle {
+	Produce,
+	Bakery,
+	Meat,
+	Seafood,
+	Dairy,
+	Frozen,
+	Pantry,
+	Baking,
+	Canned,
+	Spices,
+	Beverages,
+	#[default]
+	Other,
+}
+
+impl Aisle {
+	pub const ALL: [Aisle; 12] = [
+		Aisle::Produce,
+		Aisle::Bakery,
+		Aisle::Meat,
+		Aisle::Seafood,
+		Aisle::Dairy,
+		Aisle::Frozen,
+		Aisle::Pantry,
+		Aisle::Baking,
+		Aisle::Canned,
+		Aisle::Spices,
+		Aisle::Beverages,
+		Aisle::Other,
+	];
+}
+
+impl TryFrom<&str> for Aisle {
+	type Error = ();
+
+	fn try_from(value: &str) -> Result<Self, Self::Error> {
+		Aisle::ALL
+			.into_iter()
+			.find(|aisle| aisle.to_string().eq_ignore_ascii_case(value.trim()))
+			.ok_or(())
+	}
+}
+
+impl Display for Aisle {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Aisle::Produce => f.write_str("Produce"),
+			Aisle::Bakery => f.write_str("Bakery"),
+			Aisle::Meat => f.write_str("Meat"),
+			Aisle::Seafood => f.write_str("Seafood"),
+			Aisle::Dairy => f.write_str("Dairy"),
+			Aisle::Frozen => f.write_str("Frozen"),
+			Aisle::Pantry => f.write_str("Pantry"),
+			Aisle::Baking => f.write_str("Baking"),
+			Aisle::Canned => f.write_str("Canned"),
+			Aisle::Spices => f.write_str("Spices"),
+			Aisle::Beverages => f.write_str("Beverages"),
+			Aisle::Other => f.write_str("Other"),
+		}
+	}
+}
+
+/// An ingredient as the shopping list knows it. Recipe ingredients matching the name or any alias are
+/// bought as this ingredient.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct CatalogEntry {
+	pub aisle: Aisle,
+	#[serde(default)]
+	pub aliases: Vec<String>,
+	/// The unit the ingredient is bought in, if it shouldn't be picked automatically.
+	#[serde(default)]
+	pub default_units: Option<Units>,
+	pub name: String,
+}
+
+/// Lowercases and collapses whitespace so "Garlic  Clove" and "garlic clove" are the same key.
+fn catalog_key(name: &str) -> String {
+	name.split_whitespace()
+		.collect::<Vec<_>>()
+		.join(" ")
+		.to_lowercase()
+}
+
+impl MealPlan {
+	pub fn catalog_entry(&self, name: &str) -> Option<&CatalogEntry> {
+		let key = catalog_key(name);
+		if let Some(entry) = self.ingredient_catalog.get(&key) {
+			return Some(entry);
+		}
+
+		self.ingredient_catalog
+			.values()
+			.find(|entry| entry.aliases.iter().any(|alias| catalog_key(alias) == key))
+	}
+
+	/// The name an ingredient is bought under. Ingredients missing from the catalog keep their own
+	/// name.
+	pub fn canonical_name(&self, name: &str) -> String {
+		match self.catalog_entry(name) {
+			Some(entry) => entry.name.clone(),
+			None => name.trim().into(),
+		}
+	}
+
+	pub fn aisle(&self, name: &str) -> Aisle {
+		self.catalog_entry(name)
+			.map(|entry| entry.aisle)
+			.unwrap_or_default()
+	}
+
+	pub fn save_catalog_entry(&mut self, entry: CatalogEntry) {
+		self.ingredient_catalog
+			.insert(catalog_key(&entry.name), entry);
+	}
+
+	pub fn delete_catalog_entry(&mut self, name: &str) -> Result<CatalogEntry, MealPlanError> {
+		self.ingredient_catalog
+			.remove(&catalog_key(name))
+			.ok_or_else(|| MealPlanError::CatalogEntryNotFound { name: name.into() })
+	}
+
+	/// Groups shopping list items by aisle, in the order the aisles are walked.
+	pub fn group_by_aisle<'a>(
+		&self,
+		items: &'a [ShoppingListItem],
+	) -> Vec<(Aisle, Vec<&'a ShoppingListItem>)> {
+		let mut groups: Vec<(Aisle, Vec<&'a ShoppingListItem>)> = vec![];
+		for item in items.iter() {
+			let aisle = self.aisle(&item.name);
+			match groups
+				.iter_mut()
+				.find(|(group_aisle, _)| *group_aisle == aisle)
+			{
+				Some((_, group)) => group.push(item),
+				None => groups.push((aisle, vec![item])),
+			}
+		}
+
+		groups.sort_by_key(|(aisle, _)| *aisle);
+		for (_, group) in groups.iter_mut() {
+			group.sort_by_key(|item| item.name());
+		}
+
+		groups
+	}
+}
@@ -0,0 +1,248 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+	CatalogEntry, MealInfo, MealPlan, MealPlanError, MealPlanSnapshot, MealStub, NutritionEntry,
+	PantryItem, ShoppingListInfo, ShoppingListItem, Staple,
+};
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub enum MealPlanChange {
+	CatalogEntryDeleted {
+		name: String,
+	},
+	CatalogEntrySaved {
+		entry: CatalogEntry,
+	},
+	DensitySet {
+		grams_per_milliliter: Option<f32>,
+		name: String,
+	},
+	ExtraItemAdded {
+		item: ShoppingListItem,
+	},
+	ExtraItemRemoved {
+		name: String,
+	},
+	LeftoversSet {
+		date: NaiveDate,
+		id: Uuid,
+		leftovers: bool,
+	},
+	MealCompleted {
+		date: NaiveDate,
+		id: Uuid,
+	},
+	MealDeleted {
+		id: Uuid,
+	},
+	MealMoved {
+		date: NaiveDate,
+		id: Uuid,
+		new_date: NaiveDate,
+	},
+	MealPlanned {
+		meal_stub: MealStub,
+	},
+	MealSaved {
+		meal_info: MealInfo,
+	},
+	MealUnplanned {
+		date: NaiveDate,
+		id: Uuid,
+	},
+	MealsImported {
+		meals: Vec<MealInfo>,
+	},
+	MealsShifted {
+		days: i64,
+	},
+	NutritionImported {
+		entries: Vec<NutritionEntry>,
+	},
+	PantryItemRemoved {
+		name: String,
+	},
+	PantryItemSet {
+		item: PantryItem,
+	},
+	ServingsSet {
+		date: NaiveDate,
+		id: Uuid,
+		servings: Option<usize>,
+	},
+	ShoppingListAdded {
+		shopping_list: ShoppingListInfo,
+	},
+	ShoppingListItemAdded {
+		item: ShoppingListItem,
+		shopping_list_id: Uuid,
+	},
+	ShoppingListItemEdited {
+		item: ShoppingListItem,
+		shopping_list_id: Uuid,
+	},
+	ShoppingListItemRemoved {
+		id: Uuid,
+		shopping_list_id: Uuid,
+	},
+	ShoppingListItemSet {
+		have: bool,
+		id: Uuid,
+		shopping_list_id: Uuid,
+	},
+	ShoppingListPruned {
+		shopping_list_id: Uuid,
+	},
+	ShoppingListRenamed {
+		name: String,
+		shopping_list_id: Uuid,
+	},
+	ShoppingListsMerged {
+		from_id: Uuid,
+		into_id: Uuid,
+	},
+	SnapshotRestored {
+		snapshot: MealPlanSnapshot,
+	},
+	StapleRemoved {
+		name: String,
+	},
+	StapleSaved {
+		staple: Staple,
+	},
+}
+
+impl MealPlanChange {
+	/// Recipes in the library that this change can add, modify or remove.
+	pub fn meal_ids(&self) -> Vec<Uuid> {
+		match self {
+			MealPlanChange::MealDeleted { id } => vec![*id],
+			MealPlanChange::MealSaved { meal_info } => vec![meal_info.id],
+			MealPlanChange::MealsImported { meals } => {
+				meals.iter().map(|meal_info| meal_info.id).collect()
+			}
+			MealPlanChange::SnapshotRestored { snapshot } => {
+				snapshot.meals.keys().cloned().collect()
+			}
+			_ => vec![],
+		}
+	}
+
+	/// Whether this change can modify the nutrition table, which is too big to journal on every change.
+	pub fn touches_nutrition(&self) -> bool {
+		match self {
+			MealPlanChange::NutritionImported { .. } => true,
+			MealPlanChange::SnapshotRestored { snapshot } => snapshot.nutrition.is_some(),
+			_ => false,
+		}
+	}
+}
+
+impl MealPlan {
+	pub fn apply(&mut self, change: &MealPlanChange) -> Result<(), MealPlanError> {
+		match change {
+			MealPlanChange::CatalogEntryDeleted { name } => {
+				self.delete_catalog_entry(name).map(|_| ())
+			}
+			MealPlanChange::CatalogEntrySaved { entry } => {
+				self.save_catalog_entry(entry.clone());
+				Ok(())
+			}
+			MealPlanChange::DensitySet {
+				grams_per_milliliter,
+				name,
+			} => {
+				self.set_density(name, *grams_per_milliliter);
+				Ok(())
+			}
+			MealPlanChange::ExtraItemAdded { item } => {
+				self.add_extra_item(item.clone());
+				Ok(())
+			}
+			MealPlanChange::ExtraItemRemoved { name } => self.remove_extra_item(name).map(|_| ()),
+			MealPlanChange::LeftoversSet {
+				date,
+				id,
+				leftovers,
+			} => self.set_leftovers(*date, *id, *leftovers),
+			MealPlanChange::MealCompleted { date, id } => self.complete_meal(*date, *id),
+			MealPlanChange::MealDeleted { id } => self.remove_meal(*id).map(|_| ()),
+			MealPlanChange::MealMoved { date, id, new_date } => {
+				self.move_meal(*date, *id, *new_date)
+			}
+			MealPlanChange::MealPlanned { meal_stub } => self.plan_meal(*meal_stub),
+			MealPlanChange::MealSaved { meal_info } => {
+				self.upsert_meal(meal_info.clone());
+				Ok(())
+			}
+			MealPlanChange::MealUnplanned { date, id } => self.unplan_meal(*date, *id).map(|_| ()),
+			MealPlanChange::MealsImported { meals } => {
+				for meal_info in meals.iter() {
+					self.upsert_meal(meal_info.clone());
+				}
+
+				Ok(())
+			}
+			MealPlanChange::MealsShifted { days } => {
+				self.shift_meals(*days);
+				Ok(())
+			}
+			MealPlanChange::NutritionImported { entries } => {
+				self.import_nutrition(entries.clone());
+				Ok(())
+			}
+			MealPlanChange::PantryItemRemoved { name } => self.remove_pantry_item(name).map(|_| ()),
+			MealPlanChange::PantryItemSet { item } => {
+				self.set_pantry_item(item.clone());
+				Ok(())
+			}
+			MealPlanChange::ServingsSet { date, id, servings } => {
+				self.set_servings(*date, *id, *servings)
+			}
+			MealPlanChange::ShoppingListAdded { shopping_list } => {
+				self.add_shopping_list(shopping_list.clone());
+				Ok(())
+			}
+			MealPlanChange::ShoppingListItemAdded {
+				item,
+				shopping_list_id,
+			} => self.add_shopping_list_item(*shopping_list_id, item.clone()),
+			MealPlanChange::ShoppingListItemEdited {
+				item,
+				shopping_list_id,
+			} => self.edit_shopping_list_item(*shopping_list_id, item.clone()),
+			MealPlanChange::ShoppingListItemRemoved {
+				id,
+				shopping_list_id,
+			} => self
+				.remove_shopping_list_item(*shopping_list_id, *id)
+				.map(|_| ()),
+			MealPlanChange::ShoppingListItemSet {
+				have,
+				id,
+				shopping_list_id,
+			} => self.set_shopping_list_item(*shopping_list_id, *id, *have),
+			MealPlanChange::ShoppingListPruned { shopping_list_id } => {
+				self.prune_shopping_list(*shopping_list_id)
+			}
+			MealPlanChange::ShoppingListRenamed {
+				name,
+				shopping_list_id,
+			} => self.rename_shopping_list(*shopping_list_id, name),
+			MealPlanChange::ShoppingListsMerged { from_id, into_id } => {
+				self.merge_shopping_lists(*from_id, *into_id)
+			}
+			MealPlanChange::SnapshotRestored { snapshot } => {
+				self.restore(snapshot);
+				Ok(())
+			}
+			MealPlanChange::StapleRemoved { name } => self.remove_staple(name).map(|_| ()),
+			MealPlanChange::StapleSaved { staple } => {
+				self.save_staple(staple.clone());
+				Ok(())
+			}
+		}
+	}
+}
@@ -1,45 +1,350 @@
-use serde::{Deserialize, Serialize};
-use std::{
-	io::{BufReader, BufWriter},
-	ops::Deref,
-	sync::{RwLock, RwLockReadGuard, RwLockWriteGuard},
+use async_trait::async_trait;
+use chrono::{Local, NaiveDate};
+use std::sync::{Arc, RwLockReadGuard};
+use uuid::Uuid;
+
+use crate::{
+	CatalogEntry, DatabaseError, JsonDatabase, MealInfo, MealPlan, MealPlanChange, MealPlanMessage,
+	MealStub, MemoryDatabase, NutritionEntry, PantryItem, RestDatabase, ShoppingListItem,
+	SqliteDatabase, Staple,
 };
 
-pub struct Database<T>
-where
-	T: Serialize + for<'a> Deserialize<'a> + Default,
-{
-	data: RwLock<T>,
-	path: String,
-}
+/// Storage for a `MealPlan`. Every write goes through `apply`, which bumps the revision by one, so
+/// the local stores and the REST client can be swapped for one another.
+#[async_trait]
+pub trait Database: Send + Sync {
+	fn get(&self) -> RwLockReadGuard<'_, MealPlan>;
+
+	async fn load(&self) -> Result<(), DatabaseError>;
+
+	async fn apply(&self, change: MealPlanChange) -> Result<(), DatabaseError>;
+
+	async fn replace(&self, meal_plan: MealPlan) -> Result<(), DatabaseError>;
+
+	/// Waits for a change made by someone else. Stores nobody else can write to never return.
+	async fn next_message(&self) -> MealPlanMessage {
+		std::future::pending().await
+	}
+
+	/// Whether writes are reaching the store. Only the REST client can lose its connection, in which
+	/// case writes are queued until it comes back.
+	fn is_connected(&self) -> bool {
+		true
+	}
+
+	/// Writes made while disconnected that haven't reached the store yet.
+	fn queued_changes(&self) -> usize {
+		0
+	}
+
+	/// Drops the oldest queued write, e.g. one that conflicts with a change someone else made while
+	/// disconnected, and sends the rest.
+	async fn discard_queued_change(&self) -> Result<(), DatabaseError> {
+		Err(DatabaseError::Unsupported("queued changes"))
+	}
+
+	/// Sends the oldest queued write on top of the store's current meal plan, overwriting whatever it
+	/// conflicted with, and sends the rest.
+	async fn overwrite_queued_change(&self) -> Result<(), DatabaseError> {
+		Err(DatabaseError::Unsupported("queued changes"))
+	}
+
+	/// Reverts the most recent change recorded in the server's journal.
+	async fn undo(&self) -> Result<(), DatabaseError> {
+		Err(DatabaseError::Unsupported("undo"))
+	}
+
+	async fn redo(&self) -> Result<(), DatabaseError> {
+		Err(DatabaseError::Unsupported("redo"))
+	}
+
+	async fn set_density(
+		&self,
+		name: &str,
+		grams_per_milliliter: Option<f32>,
+	) -> Result<(), DatabaseError> {
+		self.apply(MealPlanChange::DensitySet {
+			grams_per_milliliter,
+			name: name.into(),
+		})
+		.await
+	}
 
-impl<T> Database<T>
-where
-	T: Serialize + for<'a> Deserialize<'a> + Default,
-{
-	pub fn new(path: &str) -> Self {
-		Self {
-			data: RwLock::new(T::default()),
-			path: path.into(),
-		}
+	async fn save_catalog_entry(&self, entry: CatalogEntry) -> Result<(), DatabaseError> {
+		self.apply(MealPlanChange::CatalogEntrySaved { entry })
+			.await
 	}
 
-	pub fn get(&self) -> RwLockReadGuard<T> {
-		self.data.read().unwrap()
+	async fn delete_catalog_entry(&self, name: &str) -> Result<(), DatabaseError> {
+		self.apply(MealPlanChange::CatalogEntryDeleted { name: name.into() })
+			.await
 	}
 
-	pub fn get_mut(&self) -> RwLockWriteGuard<T> {
-		self.data.write().unwrap()
+	async fn set_pantry_item(&self, item: PantryItem) -> Result<(), DatabaseError> {
+		self.apply(MealPlanChange::PantryItemSet { item }).await
 	}
 
-	pub fn save(&self) {
-		let data = self.data.read().unwrap();
-		let file = BufWriter::new(std::fs::File::create(&self.path).unwrap());
-		serde_json::to_writer(file, data.deref()).unwrap();
+	async fn remove_pantry_item(&self, name: &str) -> Result<(), DatabaseError> {
+		self.apply(MealPlanChange::PantryItemRemoved { name: name.into() })
+			.await
 	}
 
-	pub fn load(&mut self) {
-		let file = BufReader::new(std::fs::File::open(&self.path).unwrap());
-		self.data = RwLock::new(serde_json::from_reader(file).unwrap());
+	async fn save_staple(&self, staple: Staple) -> Result<(), DatabaseError> {
+		self.apply(MealPlanChange::StapleSaved { staple }).await
+	}
+
+	async fn remove_staple(&self, name: &str) -> Result<(), DatabaseError> {
+		self.apply(MealPlanChange::StapleRemoved { name: name.into() })
+			.await
+	}
+
+	async fn add_extra_item(&self, item: ShoppingListItem) -> Result<(), DatabaseError> {
+		self.apply(MealPlanChange::ExtraItemAdded { item }).await
+	}
+
+	async fn remove_extra_item(&self, name: &str) -> Result<(), DatabaseError> {
+		self.apply(MealPlanChange::ExtraItemRemoved { name: name.into() })
+			.await
+	}
+
+	/// Adds nutrition entries, like the ones read by `parse_nutrition_csv`.
+	async fn import_nutrition(&self, entries: Vec<NutritionEntry>) -> Result<(), DatabaseError> {
+		self.apply(MealPlanChange::NutritionImported { entries })
+			.await
+	}
+
+	async fn plan_meal(&self, meal_stub: MealStub) -> Result<(), DatabaseError> {
+		self.apply(MealPlanChange::MealPlanned { meal_stub }).await
+	}
+
+	async fn unplan_meal(&self, date: NaiveDate, id: Uuid) -> Result<(), DatabaseError> {
+		self.apply(MealPlanChange::MealUnplanned { date, id }).await
+	}
+
+	async fn move_meal(
+		&self,
+		date: NaiveDate,
+		id: Uuid,
+		new_date: NaiveDate,
+	) -> Result<(), DatabaseError> {
+		self.apply(MealPlanChange::MealMoved { date, id, new_date })
+			.await
+	}
+
+	async fn shift_meals(&self, days: i64) -> Result<(), DatabaseError> {
+		self.apply(MealPlanChange::MealsShifted { days }).await
+	}
+
+	async fn set_leftovers(
+		&self,
+		date: NaiveDate,
+		id: Uuid,
+		leftovers: bool,
+	) -> Result<(), DatabaseError> {
+		self.apply(MealPlanChange::LeftoversSet {
+			date,
+			id,
+			leftovers,
+		})
+		.await
+	}
+
+	async fn set_servings(
+		&self,
+		date: NaiveDate,
+		id: Uuid,
+		servings: Option<usize>,
+	) -> Result<(), DatabaseError> {
+		self.apply(MealPlanChange::ServingsSet { date, id, servings })
+			.await
+	}
+
+	async fn complete_meal(&self, date: NaiveDate, id: Uuid) -> Result<(), DatabaseError> {
+		self.apply(MealPlanChange::MealCompleted { date, id }).await
+	}
+
+	async fn set_shopping_list_item(
+		&self,
+		shopping_list_id: Uuid,
+		id: Uuid,
+		have: bool,
+	) -> Result<(), DatabaseError> {
+		self.apply(MealPlanChange::ShoppingListItemSet {
+			have,
+			id,
+			shopping_list_id,
+		})
+		.await
+	}
+
+	async fn add_shopping_list_item(
+		&self,
+		shopping_list_id: Uuid,
+		item: ShoppingListItem,
+	) -> Result<(), DatabaseError> {
+		self.apply(MealPlanChange::ShoppingListItemAdded {
+			item,
+			shopping_list_id,
+		})
+		.await
+	}
+
+	async fn edit_shopping_list_item(
+		&self,
+		shopping_list_id: Uuid,
+		item: ShoppingListItem,
+	) -> Result<(), DatabaseError> {
+		self.apply(MealPlanChange::ShoppingListItemEdited {
+			item,
+			shopping_list_id,
+		})
+		.await
+	}
+
+	async fn remove_shopping_list_item(
+		&self,
+		shopping_list_id: Uuid,
+		id: Uuid,
+	) -> Result<(), DatabaseError> {
+		self.apply(MealPlanChange::ShoppingListItemRemoved {
+			id,
+			shopping_list_id,
+		})
+		.await
+	}
+
+	async fn rename_shopping_list(
+		&self,
+		shopping_list_id: Uuid,
+		name: &str,
+	) -> Result<(), DatabaseError> {
+		self.apply(MealPlanChange::ShoppingListRenamed {
+			name: name.into(),
+			shopping_list_id,
+		})
+		.await
+	}
+
+	async fn merge_shopping_lists(
+		&self,
+		from_id: Uuid,
+		into_id: Uuid,
+	) -> Result<(), DatabaseError> {
+		self.apply(MealPlanChange::ShoppingListsMerged { from_id, into_id })
+			.await
+	}
+
+	async fn generate_shopping_list(&self) -> Result<(), DatabaseError> {
+		let shopping_list = self.get().generate_shopping_list(Local::now().date_naive());
+		let Some(shopping_list) = shopping_list else {
+			return Ok(());
+		};
+
+		self.apply(MealPlanChange::ShoppingListAdded { shopping_list })
+			.await
+	}
+
+	async fn generate_shopping_list_for_meal(
+		&self,
+		date: NaiveDate,
+		id: Uuid,
+	) -> Result<(), DatabaseError> {
+		let today = Local::now().date_naive();
+		let shopping_list = self
+			.get()
+			.generate_shopping_list_for_meal(date, id, today)?;
+		self.apply(MealPlanChange::ShoppingListAdded { shopping_list })
+			.await
+	}
+
+	async fn prune_shopping_list(&self, shopping_list_id: Uuid) -> Result<(), DatabaseError> {
+		self.apply(MealPlanChange::ShoppingListPruned { shopping_list_id })
+			.await
+	}
+
+	async fn save_meal(&self, meal_info: MealInfo) -> Result<(), DatabaseError> {
+		self.apply(MealPlanChange::MealSaved { meal_info }).await
+	}
+
+	/// Saves many meals at once, like the ones read by `import_recipes`.
+	async fn import_meals(&self, meals: Vec<MealInfo>) -> Result<(), DatabaseError> {
+		self.apply(MealPlanChange::MealsImported { meals }).await
+	}
+
+	async fn delete_meal(&self, id: Uuid) -> Result<(), DatabaseError> {
+		self.apply(MealPlanChange::MealDeleted { id }).await
+	}
+}
+
+#[derive(Clone, Debug)]
+pub enum DatabaseConfig {
+	Json { path: String },
+	Memory,
+	Rest { rest_url: String, ws_url: String },
+	Sqlite { path: String },
+}
+
+impl DatabaseConfig {
+	/// Reads the backend from `BANSHEELONG2_MEALS_DATABASE` (`json`, `memory`, `rest` or `sqlite`).
+	/// File backends take their path from `BANSHEELONG2_MEALS_DATABASE_PATH` and the REST backend
+	/// uses `BANSHEELONG2_REST_URL` and `BANSHEELONG2_WS_URL`. Returns `None` if no backend is set.
+	pub fn from_env() -> Result<Option<Self>, DatabaseError> {
+		let Ok(backend) = std::env::var("BANSHEELONG2_MEALS_DATABASE") else {
+			return Ok(None);
+		};
+
+		let path = std::env::var("BANSHEELONG2_MEALS_DATABASE_PATH").ok();
+
+		let config = match backend.as_str() {
+			"json" => DatabaseConfig::Json {
+				path: path.unwrap_or_else(|| "meals-database.json".into()),
+			},
+			"memory" => DatabaseConfig::Memory,
+			"rest" => DatabaseConfig::rest_from_env()?,
+			"sqlite" => DatabaseConfig::Sqlite {
+				path: path.unwrap_or_else(|| "meals-database.sqlite3".into()),
+			},
+			_ => {
+				return Err(DatabaseError::InvalidConfig(format!(
+					"unknown backend '{}'",
+					backend
+				)))
+			}
+		};
+
+		Ok(Some(config))
+	}
+
+	/// The REST backend at `BANSHEELONG2_REST_URL` and `BANSHEELONG2_WS_URL`.
+	pub fn rest_from_env() -> Result<Self, DatabaseError> {
+		let var = |name: &str| {
+			std::env::var(name)
+				.map_err(|_| DatabaseError::InvalidConfig(format!("{} must be set", name)))
+		};
+
+		Ok(DatabaseConfig::Rest {
+			rest_url: var("BANSHEELONG2_REST_URL")?,
+			ws_url: var("BANSHEELONG2_WS_URL")?,
+		})
+	}
+
+	pub async fn open(self) -> Result<Arc<dyn Database>, DatabaseError> {
+		let database: Arc<dyn Database> = match self {
+			DatabaseConfig::Json { path } => Arc::new(JsonDatabase::open(&path)?),
+			DatabaseConfig::Memory => Arc::new(MemoryDatabase::default()),
+			DatabaseConfig::Rest { rest_url, ws_url } => {
+				let database = RestDatabase::new(&rest_url, &ws_url)?;
+				match database.load().await {
+					// the meal plan is loaded, the conflicting write just stays queued
+					Ok(()) | Err(DatabaseError::Conflict) => {}
+					Err(error) => return Err(error),
+				}
+
+				Arc::new(database)
+			}
+			DatabaseConfig::Sqlite { path } => Arc::new(SqliteDatabase::open(&path)?),
+		};
+
+		Ok(database)
 	}
 }
@@ -0,0 +1,193 @@
+use chrono::NaiveDate;
+use std::fmt::Display;
+use uuid::Uuid;
+
+#[derive(Debug)]
+pub enum DatabaseError {
+	Conflict,
+	InvalidConfig(String),
+	Io(std::io::Error),
+	Json(serde_json::Error),
+	MealPlan(MealPlanError),
+	NoValidBackup,
+	Offline,
+	Request(reqwest::Error),
+	Sqlite(rusqlite::Error),
+	Unsupported(&'static str),
+}
+
+impl Display for DatabaseError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			DatabaseError::Conflict => {
+				f.write_str("Meal plan was changed by someone else and has been reloaded")
+			}
+			DatabaseError::InvalidConfig(message) => {
+				write!(f, "Invalid meals database config: {}", message)
+			}
+			DatabaseError::Io(error) => write!(f, "I/O error: {}", error),
+			DatabaseError::Json(error) => write!(f, "JSON error: {}", error),
+			DatabaseError::MealPlan(error) => write!(f, "{}", error),
+			DatabaseError::NoValidBackup => f.write_str("Could not find a valid backup"),
+			DatabaseError::Offline => f.write_str("Not connected to the meals database server"),
+			DatabaseError::Request(error) => write!(f, "Request failed: {}", error),
+			DatabaseError::Sqlite(error) => write!(f, "SQLite error: {}", error),
+			DatabaseError::Unsupported(operation) => {
+				write!(f, "This database does not support {}", operation)
+			}
+		}
+	}
+}
+
+impl std::error::Error for DatabaseError {}
+
+impl From<std::io::Error> for DatabaseError {
+	fn from(error: std::io::Error) -> Self {
+		DatabaseError::Io(error)
+	}
+}
+
+impl From<serde_json::Error> for DatabaseError {
+	fn from(error: serde_json::Error) -> Self {
+		DatabaseError::Json(error)
+	}
+}
+
+impl From<MealPlanError> for DatabaseError {
+	fn from(error: MealPlanError) -> Self {
+		DatabaseError::MealPlan(error)
+	}
+}
+
+impl From<reqwest::Error> for DatabaseError {
+	fn from(error: reqwest::Error) -> Self {
+		DatabaseError::Request(error)
+	}
+}
+
+impl From<rusqlite::Error> for DatabaseError {
+	fn from(error: rusqlite::Error) -> Self {
+		DatabaseError::Sqlite(error)
+	}
+}
+
+#[derive(Debug)]
+pub enum ScraperError {
+	NoRecipe,
+	Request(reqwest::Error),
+}
+
+impl Display for ScraperError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			ScraperError::NoRecipe => f.write_str("Could not find a recipe on the page"),
+			ScraperError::Request(error) => write!(f, "Could not download recipe: {}", error),
+		}
+	}
+}
+
+impl std::error::Error for ScraperError {}
+
+impl From<reqwest::Error> for ScraperError {
+	fn from(error: reqwest::Error) -> Self {
+		ScraperError::Request(error)
+	}
+}
+
+#[derive(Debug)]
+pub enum NutritionError {
+	Csv(csv::Error),
+	InvalidBasis { name: String, per: String },
+}
+
+impl Display for NutritionError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			NutritionError::Csv(error) => write!(f, "CSV error: {}", error),
+			NutritionError::InvalidBasis { name, per } => write!(
+				f,
+				"Nutrition for '{}' is per '{}' instead of 'g' or 'ml'",
+				name, per
+			),
+		}
+	}
+}
+
+impl std::error::Error for NutritionError {}
+
+impl From<csv::Error> for NutritionError {
+	fn from(error: csv::Error) -> Self {
+		NutritionError::Csv(error)
+	}
+}
+
+#[derive(Debug)]
+pub enum RecipeFormatError {
+	Json(serde_json::Error),
+	NoRecipes,
+}
+
+impl Display for RecipeFormatError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			RecipeFormatError::Json(error) => write!(f, "JSON error: {}", error),
+			RecipeFormatError::NoRecipes => f.write_str("Could not find any recipes"),
+		}
+	}
+}
+
+impl std::error::Error for RecipeFormatError {}
+
+impl From<serde_json::Error> for RecipeFormatError {
+	fn from(error: serde_json::Error) -> Self {
+		RecipeFormatError::Json(error)
+	}
+}
+
+#[derive(Clone, Debug)]
+pub enum MealPlanError {
+	CatalogEntryNotFound { name: String },
+	ExtraItemNotFound { name: String },
+	MealNotFound { id: Uuid },
+	PantryItemNotFound { name: String },
+	PlannedMealNotFound { date: NaiveDate, id: Uuid },
+	ShoppingListItemNotFound { id: Uuid, shopping_list_id: Uuid },
+	ShoppingListNotFound { shopping_list_id: Uuid },
+	StapleNotFound { name: String },
+}
+
+impl Display for MealPlanError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			MealPlanError::CatalogEntryNotFound { name } => {
+				write!(f, "Could not find '{}' in the ingredient catalog", name)
+			}
+			MealPlanError::ExtraItemNotFound { name } => {
+				write!(f, "Could not find extra shopping list item '{}'", name)
+			}
+			MealPlanError::MealNotFound { id } => write!(f, "Could not find meal '{}'", id),
+			MealPlanError::PantryItemNotFound { name } => {
+				write!(f, "Could not find '{}' in the pantry", name)
+			}
+			MealPlanError::PlannedMealNotFound { date, id } => {
+				write!(f, "Could not find meal '{}' planned for {}", id, date)
+			}
+			MealPlanError::ShoppingListItemNotFound {
+				id,
+				shopping_list_id,
+			} => write!(
+				f,
+				"Could not find item '{}' in shopping list '{}'",
+				id, shopping_list_id
+			),
+			MealPlanError::ShoppingListNotFound { shopping_list_id } => {
+				write!(f, "Could not find shopping list '{}'", shopping_list_id)
+			}
+			MealPlanError::StapleNotFound { name } => {
+				write!(f, "Could not find staple '{}'", name)
+			}
+		}
+	}
+}
+
+impl std::error::Error for MealPlanError {}
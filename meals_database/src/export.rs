@@ -0,0 +1,42 @@
+use crate::{format_amount, MealPlan, ShoppingListInfo};
+
+/// Renders a shopping list as plain text, one aisle per paragraph. Items already bought are marked
+/// with "(have)".
+pub fn shopping_list_to_text(meal_plan: &MealPlan, shopping_list: &ShoppingListInfo) -> String {
+	let mut paragraphs = vec![];
+	for (aisle, items) in meal_plan.group_by_aisle(&shopping_list.items) {
+		let mut lines = vec![aisle.to_string()];
+		for item in items {
+			let have = if item.have { " (have)" } else { "" };
+			lines.push(format!(
+				"  {}, {}{}",
+				item.name,
+				format_amount(&item.amount),
+				have
+			));
+		}
+
+		paragraphs.push(lines.join("\n"));
+	}
+
+	paragraphs.join("\n\n") + "\n"
+}
+
+/// Renders a shopping list as a Markdown checklist with a heading per aisle.
+pub fn shopping_list_to_markdown(meal_plan: &MealPlan, shopping_list: &ShoppingListInfo) -> String {
+	let mut markdown = String::from("# Shopping list\n");
+	for (aisle, items) in meal_plan.group_by_aisle(&shopping_list.items) {
+		markdown.push_str(&format!("\n## {}\n\n", aisle));
+		for item in items {
+			let checkbox = if item.have { "[x]" } else { "[ ]" };
+			markdown.push_str(&format!(
+				"- {} {}, {}\n",
+				checkbox,
+				item.name,
+				format_amount(&item.amount)
+			));
+		}
+	}
+
+	markdown
+}
@@ -0,0 +1,148 @@
+use crate::{Amount, Units};
+
+/// Fractions that measuring cups and spoons come in, as (value, glyph).
+const KITCHEN_FRACTIONS: &[(f32, &str)] = &[
+	(0.0, ""),
+	(1.0 / 8.0, "⅛"),
+	(1.0 / 4.0, "¼"),
+	(1.0 / 3.0, "⅓"),
+	(1.0 / 2.0, "½"),
+	(2.0 / 3.0, "⅔"),
+	(3.0 / 4.0, "¾"),
+	(1.0, ""),
+];
+
+/// How close an amount has to be to a fraction to be written as that fraction when splitting it up.
+const TOLERANCE: f32 = 0.02;
+
+/// Formats an amount the way a recipe would, e.g. "1⅓ cups" instead of "1.3333334 cup". The amount is
+/// moved into its most readable unit first.
+pub fn format_amount(amount: &Amount) -> String {
+	let amount = amount.promoted();
+	label(
+		&number(amount.value, &amount.units),
+		amount.value,
+		&amount.units,
+	)
+}
+
+/// Like `format_amount`, but amounts that don't land on a fraction a measuring cup or spoon can
+/// make are split into parts, e.g. "1 cup + 2 tbsp" instead of "1⅛ cups".
+pub fn format_amount_compound(amount: &Amount) -> String {
+	let amount = amount.promoted();
+	compound_parts(amount.value, &amount.units).join(" + ")
+}
+
+fn compound_parts(value: f32, units: &Units) -> Vec<String> {
+	let Some(smaller_units) = smaller_units(units) else {
+		return vec![label(&number(value, units), value, units)];
+	};
+
+	let whole = (value + TOLERANCE).floor();
+	let fraction = measurable_fractions(units)
+		.iter()
+		.rev()
+		.find(|fraction| whole + **fraction <= value + TOLERANCE)
+		.copied()
+		.unwrap_or(0.0);
+
+	let measured = whole + fraction;
+	let rest = (value - measured).max(0.0) * smaller_units.conversion_factor(units).unwrap();
+
+	if measured == 0.0 {
+		return compound_parts(rest, &smaller_units);
+	}
+
+	let mut parts = vec![label(&kitchen_fraction(measured), measured, units)];
+	if kitchen_fraction(rest) != "0" {
+		parts.extend(compound_parts(rest, &smaller_units));
+	}
+
+	parts
+}
+
+/// The unit the remainder goes into when an amount is split up.
+fn smaller_units(units: &Units) -> Option<Units> {
+	match units {
+		Units::Cup => Some(Units::Tablespoon),
+		Units::Tablespoon => Some(Units::Teaspoon),
+		Units::Pounds => Some(Units::Ounce),
+		_ => None,
+	}
+}
+
+/// Fractions there are measures for. Tablespoons only come in halves, and a recipe never calls for
+/// an eighth of a pound.
+fn measurable_fractions(units: &Units) -> &'static [f32] {
+	match units {
+		Units::Cup => &[0.0, 1.0 / 4.0, 1.0 / 3.0, 1.0 / 2.0, 2.0 / 3.0, 3.0 / 4.0],
+		Units::Tablespoon => &[0.0, 1.0 / 2.0],
+		Units::Pounds => &[0.0, 1.0 / 4.0, 1.0 / 2.0, 3.0 / 4.0],
+		_ => &[0.0],
+	}
+}
+
+fn number(value: f32, units: &Units) -> String {
+	match units {
+		Units::Grams | Units::Kilograms | Units::Liters | Units::Milliliters => decimal(value),
+		_ => kitchen_fraction(value),
+	}
+}
+
+/// Rounds to the closest kitchen fraction, e.g. 2.3 becomes "2⅓". Amounts between a sixteenth and
+/// an eighth are rounded up to an eighth so they don't disappear.
+pub fn kitchen_fraction(value: f32) -> String {
+	let mut whole = value.floor();
+	let (fraction, glyph) = KITCHEN_FRACTIONS
+		.iter()
+		.min_by(|(left, _), (right, _)| {
+			(value - whole - left)
+				.abs()
+				.total_cmp(&(value - whole - right).abs())
+		})
+		.copied()
+		.unwrap();
+
+	whole += fraction.floor();
+	if whole == 0.0 && glyph.is_empty() {
+		return if value >= 1.0 / 16.0 {
+			"⅛".into()
+		} else {
+			"0".into()
+		};
+	}
+
+	if glyph.is_empty() {
+		format!("{}", whole)
+	} else if whole == 0.0 {
+		glyph.into()
+	} else {
+		format!("{}{}", whole, glyph)
+	}
+}
+
+/// Metric amounts are written as decimals. Whole grams and milliliters are precise enough once
+/// there are a few of them.
+fn decimal(value: f32) -> String {
+	let decimals = if value >= 10.0 { 0 } else { 2 };
+	let decimal = format!("{:.*}", decimals, value);
+
+	if decimal.contains('.') {
+		decimal
+			.trim_end_matches('0')
+			.trim_end_matches('.')
+			.to_string()
+	} else {
+		decimal
+	}
+}
+
+fn label(number: &str, value: f32, units: &Units) -> String {
+	let plural = value > 1.0 + TOLERANCE;
+	match units {
+		Units::Count => number.into(),
+		Units::Cup if plural => format!("{} cups", number),
+		Units::Pinches if plural => format!("{} pinches", number),
+		units => format!("{} {}", number, units),
+	}
+}
@@ -0,0 +1,325 @@
+use crate::{Amount, Ingredient, Units};
+
+/// Phrases that stand in for a quantity, e.g. "salt to taste".
+const QUANTITY_PHRASES: &[&str] = &[
+	"as needed",
+	"for garnish",
+	"for serving",
+	"optional",
+	"to serve",
+	"to taste",
+];
+
+/// Units recipes use that `Units` can't represent. A line using one of these is kept, but flagged.
+const UNSUPPORTED_UNITS: &[&str] = &["gal", "gallon", "gallons"];
+
+/// An ingredient line from a recipe, broken into its parts.
+#[derive(Clone, Debug, Default)]
+pub struct ParsedIngredient {
+	/// `None` if the line doesn't start with a quantity, e.g. "salt to taste".
+	pub amount: Option<Amount>,
+	/// Whether the whole line was understood. Lines that weren't should be checked by hand.
+	pub confident: bool,
+	pub name: String,
+	/// Parenthetical asides and phrases like "to taste".
+	pub note: Option<String>,
+	pub original: String,
+	/// The low end of a range like "2-3 cloves garlic". `amount` holds the high end, since that's how
+	/// much needs to be bought.
+	pub range_minimum: Option<f32>,
+}
+
+impl ParsedIngredient {
+	/// Converts into an `Ingredient`, counting lines without a quantity as one of the item.
+	pub fn into_ingredient(self) -> Ingredient {
+		Ingredient {
+			amount: self.amount.unwrap_or(Amount {
+				units: Units::Count,
+				value: 1.0,
+			}),
+			name: self.name,
+		}
+	}
+}
+
+pub fn parse_ingredient(line: &str) -> ParsedIngredient {
+	let mut parsed = ParsedIngredient {
+		original: line.into(),
+		..Default::default()
+	};
+
+	let (line, mut notes) = take_parentheticals(&prepare(line));
+	let tokens = line.split_whitespace().collect::<Vec<_>>();
+
+	let mut index = 0;
+	let mut understood = true;
+	if let Some((minimum, value, consumed)) = parse_range(&tokens) {
+		index += consumed;
+
+		let units = match parse_units(&tokens[index..]) {
+			Some((units, consumed)) => {
+				index += consumed;
+				units
+			}
+			None => {
+				if tokens.get(index).is_some_and(|token| {
+					UNSUPPORTED_UNITS.contains(&normalize_word(token).as_str())
+				}) {
+					understood = false;
+				}
+
+				Units::Count
+			}
+		};
+
+		parsed.amount = Some(Amount { units, value });
+		parsed.range_minimum = minimum;
+
+		if tokens
+			.get(index)
+			.is_some_and(|token| token.eq_ignore_ascii_case("of"))
+		{
+			index += 1;
+		}
+	}
+
+	let mut name = tokens[index..].join(" ");
+	let mut has_quantity_phrase = false;
+	while let Some(phrase) = QUANTITY_PHRASES.iter().find(|phrase| {
+		name.len()
+			.checked_sub(phrase.len())
+			.and_then(|start| name.get(start..))
+			.is_some_and(|end| end.eq_ignore_ascii_case(phrase))
+	}) {
+		let start = name.len() - phrase.len();
+		notes.push(name[start..].into());
+		name = name[..start].trim_end_matches([' ', ',', ';']).into();
+		has_quantity_phrase = true;
+	}
+
+	parsed.name = name.trim_matches([' ', ',', ';']).into();
+	parsed.note = Some(notes.join("; ")).filter(|note| !note.is_empty());
+	parsed.confident =
+		understood && !parsed.name.is_empty() && (parsed.amount.is_some() || has_quantity_phrase);
+
+	parsed
+}
+
+/// Cleans up a line so numbers and units split into tokens: "1½" becomes "1 ½", "200g" becomes
+/// "200 g" and "2–3" becomes "2 - 3".
+fn prepare(line: &str) -> String {
+	let line = line
+		.trim()
+		.trim_start_matches(['•', '*', '-', '–', '▢'])
+		.replace('\u{2044}', "/")
+		.replace(['–', '—'], "-");
+
+	let mut prepared = String::with_capacity(line.len());
+	let mut previous: Option<char> = None;
+	for character in line.chars() {
+		let previous_is_number = previous.is_some_and(|previous| {
+			previous.is_ascii_digit() || vulgar_fraction(previous).is_some()
+		});
+
+		if previous_is_number
+			&& (character.is_alphabetic() && vulgar_fraction(character).is_none()
+				|| vulgar_fraction(character).is_some()
+				|| character == '-')
+		{
+			prepared.push(' ');
+		}
+
+		if character == '-' && previous_is_number {
+			prepared.push_str("- ");
+			previous = Some(' ');
+			continue;
+		}
+
+		prepared.push(character);
+		previous = Some(character);
+	}
+
+	prepared
+}
+
+/// Pulls every "(...)" out of the line, returning what's left and the contents of the parentheses.
+fn take_parentheticals(line: &str) -> (String, Vec<String>) {
+	let mut rest = String::new();
+	let mut notes = vec![];
+	let mut note = String::new();
+	let mut depth = 0;
+
+	for character in line.chars() {
+		match character {
+			'(' => {
+				if depth > 0 {
+					note.push(character);
+				}
+
+				depth += 1;
+			}
+			')' if depth > 0 => {
+				depth -= 1;
+				if depth == 0 {
+					notes.push(note.trim().to_string());
+					note.clear();
+					rest.push(' ');
+				} else {
+					note.push(character);
+				}
+			}
+			_ if depth > 0 => note.push(character),
+			_ => rest.push(character),
+		}
+	}
+
+	if !note.trim().is_empty() {
+		notes.push(note.trim().to_string());
+	}
+
+	notes.retain(|note| !note.is_empty());
+
+	(rest, notes)
+}
+
+/// Parses a quantity, or a range of them, from the start of `tokens`. Returns the low end of a
+/// range, the quantity (or high end), and how many tokens were used.
+fn parse_range(tokens: &[&str]) -> Option<(Option<f32>, f32, usize)> {
+	let (low, consumed) = parse_quantity(tokens)?;
+
+	let separator = tokens.get(consumed).map(|token| token.to_lowercase());
+	if let Some("-" | "to" | "or") = separator.as_deref() {
+		if let Some((high, high_consumed)) = parse_quantity(&tokens[consumed + 1..]) {
+			let consumed = consumed + 1 + high_consumed;
+
+			// "1-1/2 cups" is a mixed number written with a dash, not a range from 1 down to 1/2
+			if high < low && high < 1.0 {
+				return Some((None, low + high, consumed));
+			}
+
+			return Some((Some(low), high, consumed));
+		}
+	}
+
+	Some((None, low, consumed))
+}
+
+/// Parses a number, fraction or mixed number like "1 1/2" from the start of `tokens`.
+fn parse_quantity(tokens: &[&str]) -> Option<(f32, usize)> {
+	let whole = parse_number(tokens.first()?)?;
+
+	if whole.fract() == 0.0 {
+		if let Some(fraction) = tokens.get(1).filter(|token| is_fraction(token)) {
+			if let Some(fraction) = parse_number(fraction).filter(|fraction| *fraction < 1.0) {
+				return Some((whole + fraction, 2));
+			}
+		}
+	}
+
+	Some((whole, 1))
+}
+
+fn is_fraction(token: &str) -> bool {
+	token.contains('/')
+		|| token
+			.chars()
+			.any(|character| vulgar_fraction(character).is_some())
+}
+
+fn parse_number(token: &str) -> Option<f32> {
+	let mut characters = token.chars();
+	if let (Some(character), None) = (characters.next(), characters.next()) {
+		if let Some(value) = vulgar_fraction(character) {
+			return Some(value);
+		}
+	}
+
+	if let Some((numerator, denominator)) = token.split_once('/') {
+		let numerator = numerator.parse::<f32>().ok()?;
+		let denominator = denominator.parse::<f32>().ok()?;
+		return Some(numerator / denominator).filter(|value| value.is_finite());
+	}
+
+	if !token.starts_with(|character: char| character.is_ascii_digit() || character == '.') {
+		return None;
+	}
+
+	token
+		.parse::<f32>()
+		.ok()
+		.filter(|value| value.is_finite() && *value >= 0.0)
+}
+
+fn vulgar_fraction(character: char) -> Option<f32> {
+	let value = match character {
+		'½' => 1.0 / 2.0,
+		'⅓' => 1.0 / 3.0,
+		'⅔' => 2.0 / 3.0,
+		'¼' => 1.0 / 4.0,
+		'¾' => 3.0 / 4.0,
+		'⅕' => 1.0 / 5.0,
+		'⅖' => 2.0 / 5.0,
+		'⅗' => 3.0 / 5.0,
+		'⅘' => 4.0 / 5.0,
+		'⅙' => 1.0 / 6.0,
+		'⅚' => 5.0 / 6.0,
+		'⅐' => 1.0 / 7.0,
+		'⅛' => 1.0 / 8.0,
+		'⅜' => 3.0 / 8.0,
+		'⅝' => 5.0 / 8.0,
+		'⅞' => 7.0 / 8.0,
+		'⅑' => 1.0 / 9.0,
+		'⅒' => 1.0 / 10.0,
+		_ => return None,
+	};
+
+	Some(value)
+}
+
+fn normalize_word(word: &str) -> String {
+	word.trim_end_matches(['.', ',']).to_lowercase()
+}
+
+/// Reads a unit from the start of `tokens`, accepting plurals and the usual abbreviations. Returns
+/// the unit and how many tokens it took up.
+fn parse_units(tokens: &[&str]) -> Option<(Units, usize)> {
+	let word = tokens.first()?.trim_end_matches(['.', ',']);
+
+	// recipes use "T" for tablespoons and "t" for teaspoons
+	match word {
+		"T" | "Tbs" | "Tbsp" => return Some((Units::Tablespoon, 1)),
+		"t" => return Some((Units::Teaspoon, 1)),
+		_ => {}
+	}
+
+	let next_word = tokens.get(1).map(|word| normalize_word(word));
+	if let ("fl" | "fluid", Some("oz" | "ounce" | "ounces")) =
+		(normalize_word(word).as_str(), next_word.as_deref())
+	{
+		return Some((Units::FluidOunces, 2));
+	}
+
+	let units = match normalize_word(word).as_str() {
+		"c" | "cup" | "cups" => Units::Cup,
+		"clove" | "cloves" | "teaspoon" | "teaspoons" | "tsp" | "tsps" => Units::Teaspoon,
+		"milliliter" | "milliliters" | "millilitre" | "millilitres" | "ml" | "mls" => {
+			Units::Milliliters
+		}
+		"ounce" | "ounces" | "oz" => Units::Ounce,
+		"tablespoon" | "tablespoons" | "tbl" | "tbs" | "tbsp" | "tbsps" | "thumb" | "thumbs" => {
+			Units::Tablespoon
+		}
+		"unit" | "units" => Units::Count,
+		"floz" => Units::FluidOunces,
+		"g" | "gr" | "gram" | "grams" | "gramme" | "grammes" => Units::Grams,
+		"kg" | "kgs" | "kilo" | "kilos" | "kilogram" | "kilograms" => Units::Kilograms,
+		"l" | "liter" | "liters" | "litre" | "litres" => Units::Liters,
+		"pinch" | "pinches" => Units::Pinches,
+		"pint" | "pints" | "pt" | "pts" => Units::Pints,
+		"lb" | "lbs" | "pound" | "pounds" => Units::Pounds,
+		"qt" | "qts" | "quart" | "quarts" => Units::Quarts,
+		_ => return None,
+	};
+
+	Some((units, 1))
+}
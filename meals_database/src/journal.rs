@@ -0,0 +1,303 @@
+use chrono::{DateTime, Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::{
+	collections::HashMap,
+	fs::{File, OpenOptions},
+	io::{BufRead, BufReader, Write},
+	path::PathBuf,
+};
+use uuid::Uuid;
+
+use crate::{
+	CatalogEntry, DatabaseError, MealInfo, MealPlan, MealPlanChange, MealStub, NutritionEntry,
+	PantryItem, ShoppingListInfo, ShoppingListItem, Staple,
+};
+
+const UNDO_LIMIT: usize = 50;
+
+/// The parts of a `MealPlan` a change can touch. The recipe library dwarfs everything else, so only
+/// the recipes in `meals` are recorded, with `None` for a recipe that did not exist. The nutrition
+/// table is only recorded for changes that can modify it. Parts that are
+/// `None` are left alone when restoring, either because the change didn't touch them or because the
+/// entry was journaled before they existed.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct MealPlanSnapshot {
+	#[serde(default)]
+	pub completed_meals: Option<HashMap<NaiveDate, Vec<Uuid>>>,
+	#[serde(default)]
+	pub densities: Option<HashMap<String, f32>>,
+	#[serde(default)]
+	pub extra_items: Option<Vec<ShoppingListItem>>,
+	#[serde(default)]
+	pub ingredient_catalog: Option<HashMap<String, CatalogEntry>>,
+	pub meals: HashMap<Uuid, Option<MealInfo>>,
+	#[serde(default)]
+	pub nutrition: Option<HashMap<String, NutritionEntry>>,
+	#[serde(default)]
+	pub pantry: Option<HashMap<String, PantryItem>>,
+	#[serde(default)]
+	pub planned_meals: Option<HashMap<NaiveDate, Vec<MealStub>>>,
+	#[serde(default)]
+	pub shopping_list: Option<Vec<ShoppingListInfo>>,
+	#[serde(default)]
+	pub staples: Option<HashMap<String, Staple>>,
+}
+
+/// Compares by serialized value, since not every part of a meal plan can be compared directly.
+fn same<T>(before: &T, after: &T) -> bool
+where
+	T: Serialize,
+{
+	match (serde_json::to_value(before), serde_json::to_value(after)) {
+		(Ok(before), Ok(after)) => before == after,
+		_ => false,
+	}
+}
+
+fn drop_unchanged<T>(before: &mut Option<T>, after: &mut Option<T>)
+where
+	T: Serialize,
+{
+	if same(before, after) {
+		*before = None;
+		*after = None;
+	}
+}
+
+impl MealPlanSnapshot {
+	/// Leaves out everything that's the same before and after a change, so a journal entry only holds
+	/// what the change touched.
+	pub fn difference(
+		mut before: MealPlanSnapshot,
+		mut after: MealPlanSnapshot,
+	) -> (MealPlanSnapshot, MealPlanSnapshot) {
+		drop_unchanged(&mut before.completed_meals, &mut after.completed_meals);
+		drop_unchanged(&mut before.densities, &mut after.densities);
+		drop_unchanged(&mut before.extra_items, &mut after.extra_items);
+		drop_unchanged(
+			&mut before.ingredient_catalog,
+			&mut after.ingredient_catalog,
+		);
+		drop_unchanged(&mut before.nutrition, &mut after.nutrition);
+		drop_unchanged(&mut before.pantry, &mut after.pantry);
+		drop_unchanged(&mut before.planned_meals, &mut after.planned_meals);
+		drop_unchanged(&mut before.shopping_list, &mut after.shopping_list);
+		drop_unchanged(&mut before.staples, &mut after.staples);
+
+		let unchanged_meals = before
+			.meals
+			.iter()
+			.filter(|(id, meal_info)| {
+				after
+					.meals
+					.get(id)
+					.is_some_and(|after_meal_info| same(*meal_info, after_meal_info))
+			})
+			.map(|(id, _)| *id)
+			.collect::<Vec<_>>();
+
+		for id in unchanged_meals {
+			before.meals.remove(&id);
+			after.meals.remove(&id);
+		}
+
+		(before, after)
+	}
+}
+
+impl MealPlan {
+	pub fn snapshot(
+		&self,
+		meal_ids: impl IntoIterator<Item = Uuid>,
+		nutrition: bool,
+	) -> MealPlanSnapshot {
+		MealPlanSnapshot {
+			completed_meals: Some(self.completed_meals.clone()),
+			densities: Some(self.densities.clone()),
+			extra_items: Some(self.extra_items.clone()),
+			ingredient_catalog: Some(self.ingredient_catalog.clone()),
+			meals: meal_ids
+				.into_iter()
+				.map(|id| (id, self.all_meals.get(&id).cloned()))
+				.collect(),
+			nutrition: nutrition.then(|| self.nutrition.clone()),
+			pantry: Some(self.pantry.clone()),
+			planned_meals: Some(self.planned_meals.clone()),
+			shopping_list: Some(self.shopping_list.clone()),
+			staples: Some(self.staples.clone()),
+		}
+	}
+
+	pub fn restore(&mut self, snapshot: &MealPlanSnapshot) {
+		if let Some(completed_meals) = &snapshot.completed_meals {
+			self.completed_meals = completed_meals.clone();
+		}
+
+		if let Some(densities) = &snapshot.densities {
+			self.densities = densities.clone();
+		}
+
+		if let Some(extra_items) = &snapshot.extra_items {
+			self.extra_items = extra_items.clone();
+		}
+
+		if let Some(ingredient_catalog) = &snapshot.ingredient_catalog {
+			self.ingredient_catalog = ingredient_catalog.clone();
+		}
+
+		if let Some(nutrition) = &snapshot.nutrition {
+			self.nutrition = nutrition.clone();
+		}
+
+		if let Some(pantry) = &snapshot.pantry {
+			self.pantry = pantry.clone();
+		}
+
+		if let Some(planned_meals) = &snapshot.planned_meals {
+			self.planned_meals = planned_meals.clone();
+		}
+
+		if let Some(shopping_list) = &snapshot.shopping_list {
+			self.shopping_list = shopping_list.clone();
+		}
+
+		if let Some(staples) = &snapshot.staples {
+			self.staples = staples.clone();
+		}
+
+		for (id, meal_info) in snapshot.meals.iter() {
+			match meal_info {
+				Some(meal_info) => {
+					self.all_meals.insert(*id, meal_info.clone());
+				}
+				None => {
+					self.all_meals.remove(id);
+				}
+			}
+		}
+	}
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub enum JournalAction {
+	Change,
+	Redo,
+	Replace,
+	Undo,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct JournalEntry {
+	pub action: JournalAction,
+	pub after: MealPlanSnapshot,
+	pub before: MealPlanSnapshot,
+	pub change: Option<MealPlanChange>,
+	pub revision: u64,
+	pub timestamp: DateTime<Local>,
+	pub user: String,
+}
+
+/// Append-only log of every write to the meal plan, one JSON entry per line. The undo and redo
+/// stacks are rebuilt from the log on startup.
+pub struct Journal {
+	entries: Vec<JournalEntry>,
+	path: PathBuf,
+	redo: Vec<JournalEntry>,
+	undo: Vec<JournalEntry>,
+}
+
+impl Journal {
+	pub fn open(path: &str) -> Result<Self, DatabaseError> {
+		let mut journal = Journal {
+			entries: vec![],
+			path: path.into(),
+			redo: vec![],
+			undo: vec![],
+		};
+
+		let file = match File::open(&journal.path) {
+			Ok(file) => file,
+			Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(journal),
+			Err(error) => return Err(error.into()),
+		};
+
+		for line in BufReader::new(file).lines() {
+			let line = line?;
+			if line.trim().is_empty() {
+				continue;
+			}
+
+			match serde_json::from_str::<JournalEntry>(&line) {
+				Ok(entry) => journal.track(entry),
+				Err(error) => log::error!("Skipping unreadable journal entry: {}", error),
+			}
+		}
+
+		Ok(journal)
+	}
+
+	pub fn entries(&self) -> &[JournalEntry] {
+		&self.entries
+	}
+
+	pub fn can_undo(&self) -> bool {
+		!self.undo.is_empty()
+	}
+
+	pub fn can_redo(&self) -> bool {
+		!self.redo.is_empty()
+	}
+
+	/// The entry an undo would revert.
+	pub fn next_undo(&self) -> Option<&JournalEntry> {
+		self.undo.last()
+	}
+
+	/// The entry a redo would reapply.
+	pub fn next_redo(&self) -> Option<&JournalEntry> {
+		self.redo.last()
+	}
+
+	pub fn record(&mut self, entry: JournalEntry) -> Result<(), DatabaseError> {
+		let mut line = serde_json::to_string(&entry)?;
+		line.push('\n');
+
+		// track before writing so the undo stack matches the meal plan even if the disk write fails
+		self.track(entry);
+
+		let mut file = OpenOptions::new()
+			.create(true)
+			.append(true)
+			.open(&self.path)?;
+
+		file.write_all(line.as_bytes())?;
+		file.sync_data()?;
+
+		Ok(())
+	}
+
+	fn track(&mut self, entry: JournalEntry) {
+		match entry.action {
+			JournalAction::Change | JournalAction::Replace => {
+				self.undo.push(entry.clone());
+				if self.undo.len() > UNDO_LIMIT {
+					self.undo.remove(0);
+				}
+
+				self.redo.clear();
+			}
+			JournalAction::Redo => {
+				if let Some(redone) = self.redo.pop() {
+					self.undo.push(redone);
+				}
+			}
+			JournalAction::Undo => {
+				if let Some(undone) = self.undo.pop() {
+					self.redo.push(undone);
+				}
+			}
+		}
+
+		self.entries.push(entry);
+	}
+}
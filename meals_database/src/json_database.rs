@@ -0,0 +1,251 @@
+use async_trait::async_trait;
+use chrono::{Local, NaiveDateTime, TimeDelta};
+use serde::{Deserialize, Serialize};
+use std::{
+	fs::File,
+	io::{BufReader, BufWriter, ErrorKind, Write},
+	ops::Deref,
+	path::{Path, PathBuf},
+	sync::{RwLock, RwLockReadGuard, RwLockWriteGuard},
+};
+
+use crate::{Database, DatabaseError, MealPlan, MealPlanChange};
+
+const BACKUP_COUNT: usize = 48;
+const BACKUP_INTERVAL: TimeDelta = TimeDelta::hours(1);
+const BACKUP_TIMESTAMP_FORMAT: &str = "%Y%m%d%H%M%S";
+
+pub struct JsonDatabase<T>
+where
+	T: Serialize + for<'a> Deserialize<'a> + Default,
+{
+	data: RwLock<T>,
+	path: PathBuf,
+}
+
+impl<T> JsonDatabase<T>
+where
+	T: Serialize + for<'a> Deserialize<'a> + Default,
+{
+	pub fn new(path: &str) -> Self {
+		Self {
+			data: RwLock::new(T::default()),
+			path: path.into(),
+		}
+	}
+
+	/// Loads the database at `path`, falling back to the newest valid backup if the file is missing
+	/// or corrupt. A missing file with no backups starts out empty.
+	pub fn open(path: &str) -> Result<Self, DatabaseError> {
+		let database = Self::new(path);
+		match database.load() {
+			Ok(()) => {}
+			Err(DatabaseError::Io(error)) if error.kind() == ErrorKind::NotFound => {
+				match database.restore_latest_backup() {
+					Ok(path) => log::warn!("Database missing, restored '{}'", path.display()),
+					Err(_) => log::warn!("Database missing, starting with an empty meal plan"),
+				}
+			}
+			Err(error) => {
+				log::error!("Could not load database: {}", error);
+
+				let path = database.restore_latest_backup()?;
+				log::warn!("Restored database from backup '{}'", path.display());
+			}
+		}
+
+		Ok(database)
+	}
+
+	pub fn get(&self) -> RwLockReadGuard<'_, T> {
+		self.data.read().unwrap()
+	}
+
+	pub fn get_mut(&self) -> RwLockWriteGuard<'_, T> {
+		self.data.write().unwrap()
+	}
+
+	/// Writes to a temporary file next to the database and renames it over the old one, so a crash
+	/// mid-write leaves either the old or the new contents on disk and never a truncated file.
+	pub fn save(&self) -> Result<(), DatabaseError> {
+		let data = self.data.read().unwrap();
+		self.write(data.deref())
+	}
+
+	fn write(&self, data: &T) -> Result<(), DatabaseError> {
+		let temporary_path = write_temporary(&self.path, data)?;
+		self.backup()?;
+
+		rename_into_place(&temporary_path, &self.path)
+	}
+
+	pub fn load(&self) -> Result<(), DatabaseError> {
+		*self.data.write().unwrap() = Self::read(&self.path)?;
+		Ok(())
+	}
+
+	/// Loads the newest backup that still parses, returning its path.
+	pub fn restore_latest_backup(&self) -> Result<PathBuf, DatabaseError> {
+		for path in self.backups()?.into_iter().rev() {
+			match Self::read(&path) {
+				Ok(data) => {
+					*self.data.write().unwrap() = data;
+					return Ok(path);
+				}
+				Err(error) => log::error!("Skipping backup '{}': {}", path.display(), error),
+			}
+		}
+
+		Err(DatabaseError::NoValidBackup)
+	}
+
+	fn read(path: &Path) -> Result<T, DatabaseError> {
+		let file = BufReader::new(File::open(path)?);
+		Ok(serde_json::from_reader(file)?)
+	}
+
+	fn directory(&self) -> PathBuf {
+		directory(&self.path)
+	}
+
+	fn backup_prefix(&self) -> String {
+		format!("{}.", self.path.file_name().unwrap().to_string_lossy())
+	}
+
+	/// Backups sorted from oldest to newest.
+	fn backups(&self) -> Result<Vec<PathBuf>, DatabaseError> {
+		let prefix = self.backup_prefix();
+
+		let mut backups = vec![];
+		for entry in std::fs::read_dir(self.directory())? {
+			let path = entry?.path();
+			let file_name = path.file_name().unwrap().to_string_lossy().to_string();
+			if file_name.starts_with(&prefix) && file_name.ends_with(".bak") {
+				backups.push(path);
+			}
+		}
+
+		backups.sort();
+
+		Ok(backups)
+	}
+
+	fn backup_timestamp(&self, path: &Path) -> Option<NaiveDateTime> {
+		let file_name = path.file_name()?.to_string_lossy().to_string();
+		let timestamp = file_name
+			.strip_prefix(&self.backup_prefix())?
+			.strip_suffix(".bak")?;
+
+		NaiveDateTime::parse_from_str(timestamp, BACKUP_TIMESTAMP_FORMAT).ok()
+	}
+
+	/// Copies the current database file into a timestamped backup if the newest backup is older
+	/// than `BACKUP_INTERVAL`, then deletes all but the newest `BACKUP_COUNT` backups.
+	fn backup(&self) -> Result<(), DatabaseError> {
+		if !self.path.exists() {
+			return Ok(());
+		}
+
+		let now = Local::now().naive_local();
+		let mut backups = self.backups()?;
+
+		let newest = backups.last().and_then(|path| self.backup_timestamp(path));
+
+		if let Some(newest) = newest {
+			if now - newest < BACKUP_INTERVAL {
+				return Ok(());
+			}
+		}
+
+		let backup_path = self.directory().join(format!(
+			"{}{}.bak",
+			self.backup_prefix(),
+			now.format(BACKUP_TIMESTAMP_FORMAT)
+		));
+
+		std::fs::copy(&self.path, &backup_path)?;
+		backups.push(backup_path);
+
+		while backups.len() > BACKUP_COUNT {
+			std::fs::remove_file(backups.remove(0))?;
+		}
+
+		Ok(())
+	}
+}
+
+fn directory(path: &Path) -> PathBuf {
+	match path.parent() {
+		Some(parent) if !parent.as_os_str().is_empty() => parent.into(),
+		_ => PathBuf::from("."),
+	}
+}
+
+fn write_temporary<V>(path: &Path, value: &V) -> Result<PathBuf, DatabaseError>
+where
+	V: Serialize,
+{
+	let mut temporary_path = path.to_path_buf().into_os_string();
+	temporary_path.push(".tmp");
+
+	let mut file = BufWriter::new(File::create(&temporary_path)?);
+	serde_json::to_writer(&mut file, value)?;
+
+	file.flush()?;
+	file.get_ref().sync_all()?;
+
+	Ok(temporary_path.into())
+}
+
+fn rename_into_place(temporary_path: &Path, path: &Path) -> Result<(), DatabaseError> {
+	std::fs::rename(temporary_path, path)?;
+	File::open(directory(path))?.sync_all()?;
+
+	Ok(())
+}
+
+/// Writes to a temporary file next to `path` and renames it over the old one, so a crash mid-write
+/// leaves either the old or the new contents on disk and never a truncated file.
+pub(crate) fn write_json_atomically<V>(path: &Path, value: &V) -> Result<(), DatabaseError>
+where
+	V: Serialize,
+{
+	let temporary_path = write_temporary(path, value)?;
+	rename_into_place(&temporary_path, path)
+}
+
+#[async_trait]
+impl Database for JsonDatabase<MealPlan> {
+	fn get(&self) -> RwLockReadGuard<'_, MealPlan> {
+		JsonDatabase::get(self)
+	}
+
+	async fn load(&self) -> Result<(), DatabaseError> {
+		JsonDatabase::load(self)
+	}
+
+	/// Changes a copy of the meal plan and only swaps it in once it's on disk, so a failed save
+	/// leaves the meal plan and its revision as they were.
+	async fn apply(&self, change: MealPlanChange) -> Result<(), DatabaseError> {
+		let mut meal_plan = self.get_mut();
+
+		let mut new_meal_plan = meal_plan.clone();
+		new_meal_plan.apply(&change)?;
+		new_meal_plan.revision += 1;
+
+		self.write(&new_meal_plan)?;
+		*meal_plan = new_meal_plan;
+
+		Ok(())
+	}
+
+	async fn replace(&self, mut new_meal_plan: MealPlan) -> Result<(), DatabaseError> {
+		let mut meal_plan = self.get_mut();
+		new_meal_plan.revision = meal_plan.revision + 1;
+
+		self.write(&new_meal_plan)?;
+		*meal_plan = new_meal_plan;
+
+		Ok(())
+	}
+}
@@ -1,13 +1,97 @@
+mod catalog;
+mod change;
 mod database;
+mod error;
+mod export;
+mod formatting;
+mod ingredient_parser;
+mod journal;
+mod json_database;
+mod memory_database;
+mod nutrition;
+mod on_hand;
+mod pantry;
+mod recipe_formats;
+mod requests;
 mod rest_database;
 mod scraper;
+mod search;
+mod shopping_list;
+mod sqlite_database;
+mod staples;
+mod statistics;
+mod suggestions;
+mod tags;
 mod types;
 
+pub use catalog::Aisle;
+pub use catalog::CatalogEntry;
+pub use change::MealPlanChange;
 pub use database::Database;
+pub use database::DatabaseConfig;
+pub use error::DatabaseError;
+pub use error::MealPlanError;
+pub use error::NutritionError;
+pub use error::RecipeFormatError;
+pub use error::ScraperError;
+pub use export::shopping_list_to_markdown;
+pub use export::shopping_list_to_text;
+pub use formatting::format_amount;
+pub use formatting::format_amount_compound;
+pub use formatting::kitchen_fraction;
+pub use ingredient_parser::parse_ingredient;
+pub use ingredient_parser::ParsedIngredient;
+pub use journal::Journal;
+pub use journal::JournalAction;
+pub use journal::JournalEntry;
+pub use journal::MealPlanSnapshot;
+pub use json_database::JsonDatabase;
+pub use memory_database::MemoryDatabase;
+pub use nutrition::parse_nutrition_csv;
+pub use nutrition::DayNutrition;
+pub use nutrition::MealNutrition;
+pub use nutrition::Nutrients;
+pub use nutrition::NutritionEntry;
+pub use on_hand::CookableMeal;
+pub use pantry::PantryItem;
+pub use recipe_formats::export_recipes;
+pub use recipe_formats::import_recipes;
+pub use recipe_formats::RecipeFormat;
+pub use requests::CatalogEntryRequest;
+pub use requests::ChangeResponse;
+pub use requests::DeleteMealRequest;
+pub use requests::DensityRequest;
+pub use requests::EditShoppingListItemRequest;
+pub use requests::ExtraItemRequest;
+pub use requests::LeftoversRequest;
+pub use requests::MergeShoppingListsRequest;
+pub use requests::MoveMealRequest;
+pub use requests::PantryItemRequest;
+pub use requests::PlannedMealRequest;
+pub use requests::RemoveShoppingListItemRequest;
+pub use requests::RenameShoppingListRequest;
+pub use requests::ServingsRequest;
+pub use requests::ShiftMealsRequest;
+pub use requests::ShoppingListItemRequest;
+pub use requests::ShoppingListRequest;
+pub use requests::StapleRequest;
 pub use rest_database::RestDatabase;
+pub use scraper::extract_recipe_json;
 pub use scraper::normalize_recipe_json;
+pub use scraper::parse_recipe_html;
+pub use scraper::scrape_recipe;
 pub use scraper::NormalizedRecipe;
 pub use scraper::RecipeJSON;
+pub use search::SearchIndex;
+pub use search::SearchResult;
+pub use sqlite_database::SqliteDatabase;
+pub use staples::Frequency;
+pub use staples::Staple;
+pub use statistics::MealStatistics;
+pub use statistics::Statistics;
+pub use suggestions::SuggestionOptions;
+pub use tags::Diet;
+pub use tags::MealFilter;
 pub use types::Amount;
 pub use types::Ingredient;
 pub use types::MealInfo;
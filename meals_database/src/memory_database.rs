@@ -0,0 +1,45 @@
+use async_trait::async_trait;
+use std::sync::{RwLock, RwLockReadGuard};
+
+use crate::{Database, DatabaseError, MealPlan, MealPlanChange};
+
+#[derive(Default)]
+pub struct MemoryDatabase {
+	data: RwLock<MealPlan>,
+}
+
+impl MemoryDatabase {
+	pub fn new(meal_plan: MealPlan) -> Self {
+		Self {
+			data: RwLock::new(meal_plan),
+		}
+	}
+}
+
+#[async_trait]
+impl Database for MemoryDatabase {
+	fn get(&self) -> RwLockReadGuard<'_, MealPlan> {
+		self.data.read().unwrap()
+	}
+
+	async fn load(&self) -> Result<(), DatabaseError> {
+		Ok(())
+	}
+
+	async fn apply(&self, change: MealPlanChange) -> Result<(), DatabaseError> {
+		let mut meal_plan = self.data.write().unwrap();
+		meal_plan.apply(&change)?;
+		meal_plan.revision += 1;
+
+		Ok(())
+	}
+
+	async fn replace(&self, new_meal_plan: MealPlan) -> Result<(), DatabaseError> {
+		let mut meal_plan = self.data.write().unwrap();
+		let revision = meal_plan.revision + 1;
+		*meal_plan = new_meal_plan;
+		meal_plan.revision = revision;
+
+		Ok(())
+	}
+}
@@ -0,0 +1,217 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::io::Read;
+use uuid::Uuid;
+
+use crate::{Ingredient, MealInfo, MealPlan, NutritionError, Units};
+
+/// Calories in kcal, sodium in milligrams and everything else in grams.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct Nutrients {
+	pub calories: f32,
+	pub carbs: f32,
+	pub fat: f32,
+	pub fiber: f32,
+	pub protein: f32,
+	pub sodium: f32,
+}
+
+impl Nutrients {
+	pub fn add(&self, other: &Nutrients) -> Nutrients {
+		Nutrients {
+			calories: self.calories + other.calories,
+			carbs: self.carbs + other.carbs,
+			fat: self.fat + other.fat,
+			fiber: self.fiber + other.fiber,
+			protein: self.protein + other.protein,
+			sodium: self.sodium + other.sodium,
+		}
+	}
+
+	pub fn scale(&self, scale_factor: f32) -> Nutrients {
+		Nutrients {
+			calories: self.calories * scale_factor,
+			carbs: self.carbs * scale_factor,
+			fat: self.fat * scale_factor,
+			fiber: self.fiber * scale_factor,
+			protein: self.protein * scale_factor,
+			sodium: self.sodium * scale_factor,
+		}
+	}
+}
+
+/// The nutrients in 100 grams of an ingredient, or 100 milliliters if it's a liquid.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct NutritionEntry {
+	/// Grams, or milliliters for liquids, in one of the ingredient. Lets counted ingredients like
+	/// "2 eggs" be looked up.
+	#[serde(default)]
+	pub each: Option<f32>,
+	#[serde(default)]
+	pub liquid: bool,
+	pub name: String,
+	pub nutrients: Nutrients,
+}
+
+/// A row of a nutrition CSV. `per` is "g" or "ml", and grams when left empty.
+#[derive(Deserialize)]
+struct NutritionRow {
+	calories: f32,
+	carbs: f32,
+	#[serde(default)]
+	each: Option<f32>,
+	fat: f32,
+	fiber: f32,
+	name: String,
+	#[serde(default)]
+	per: Option<String>,
+	protein: f32,
+	sodium: f32,
+}
+
+/// Reads a CSV with a header row naming the `name`, `calories`, `protein`, `carbs`, `fat`, `fiber`
+/// and `sodium` columns, in any order. The optional `per` and `each` columns mark liquids and give
+/// the size of one of a counted ingredient.
+pub fn parse_nutrition_csv(reader: impl Read) -> Result<Vec<NutritionEntry>, NutritionError> {
+	let mut reader = csv::ReaderBuilder::new()
+		.trim(csv::Trim::All)
+		.from_reader(reader);
+
+	let mut entries = vec![];
+	for row in reader.deserialize::<NutritionRow>() {
+		let row = row?;
+
+		let liquid = match row.per.as_deref().map(str::to_lowercase).as_deref() {
+			None | Some("") | Some("g") | Some("100g") | Some("100 g") => false,
+			Some("ml") | Some("100ml") | Some("100 ml") => true,
+			Some(_) => {
+				return Err(NutritionError::InvalidBasis {
+					name: row.name,
+					per: row.per.unwrap_or_default(),
+				})
+			}
+		};
+
+		entries.push(NutritionEntry {
+			each: row.each,
+			liquid,
+			name: row.name,
+			nutrients: Nutrients {
+				calories: row.calories,
+				carbs: row.carbs,
+				fat: row.fat,
+				fiber: row.fiber,
+				protein: row.protein,
+				sodium: row.sodium,
+			},
+		});
+	}
+
+	Ok(entries)
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct MealNutrition {
+	pub id: Uuid,
+	/// Ingredients left out of the totals, because they have no nutrition entry or their amount
+	/// can't be converted to one.
+	pub missing: Vec<String>,
+	pub name: String,
+	pub per_serving: Nutrients,
+}
+
+/// One serving of every meal planned on a day.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct DayNutrition {
+	pub date: NaiveDate,
+	pub meals: Vec<MealNutrition>,
+	pub total: Nutrients,
+}
+
+impl MealPlan {
+	pub fn nutrition_entry(&self, name: &str) -> Option<&NutritionEntry> {
+		self.nutrition
+			.get(&self.canonical_name(name).to_lowercase())
+	}
+
+	/// Adds the entries under their catalog names, replacing any entries already there.
+	pub fn import_nutrition(&mut self, entries: Vec<NutritionEntry>) {
+		for mut entry in entries {
+			entry.name = self.canonical_name(&entry.name);
+			self.nutrition.insert(entry.name.to_lowercase(), entry);
+		}
+	}
+
+	/// `None` if the ingredient has no entry or its amount can't be put into grams or milliliters.
+	pub fn ingredient_nutrients(&self, ingredient: &Ingredient) -> Option<Nutrients> {
+		let entry = self.nutrition_entry(&ingredient.name)?;
+
+		let size = if ingredient.amount.units == Units::Count {
+			entry.each? * ingredient.amount.value
+		} else {
+			let base_units = if entry.liquid {
+				Units::Milliliters
+			} else {
+				Units::Grams
+			};
+
+			base_units.conversion_factor_with_density(
+				&ingredient.amount.units,
+				self.density(&ingredient.name),
+			)? * ingredient.amount.value
+		};
+
+		Some(entry.nutrients.scale(size / 100.0))
+	}
+
+	pub fn meal_nutrition(&self, meal_info: &MealInfo) -> MealNutrition {
+		let mut total = Nutrients::default();
+		let mut missing = vec![];
+		for ingredient in meal_info.ingredients.iter() {
+			match self.ingredient_nutrients(ingredient) {
+				Some(nutrients) => total = total.add(&nutrients),
+				None => missing.push(ingredient.name.clone()),
+			}
+		}
+
+		MealNutrition {
+			id: meal_info.id,
+			missing,
+			name: meal_info.name.clone(),
+			per_serving: total.scale(1.0 / meal_info.serving_size.max(1) as f32),
+		}
+	}
+
+	pub fn day_nutrition(&self, date: NaiveDate) -> DayNutrition {
+		let meals = self
+			.planned_meals
+			.get(&date)
+			.into_iter()
+			.flatten()
+			.filter_map(|meal_stub| self.all_meals.get(&meal_stub.id))
+			.map(|meal_info| self.meal_nutrition(meal_info))
+			.collect::<Vec<_>>();
+
+		let total = meals.iter().fold(Nutrients::default(), |total, meal| {
+			total.add(&meal.per_serving)
+		});
+
+		DayNutrition { date, meals, total }
+	}
+
+	/// Every day from `from` to `to` that has meals planned.
+	pub fn nutrition_report(&self, from: NaiveDate, to: NaiveDate) -> Vec<DayNutrition> {
+		let mut dates = self
+			.planned_meals
+			.iter()
+			.filter(|(date, meal_stubs)| **date >= from && **date <= to && !meal_stubs.is_empty())
+			.map(|(date, _)| *date)
+			.collect::<Vec<_>>();
+		dates.sort();
+
+		dates
+			.into_iter()
+			.map(|date| self.day_nutrition(date))
+			.collect()
+	}
+}
@@ -0,0 +1,97 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::MealPlan;
+
+/// How much of a recipe can be made from the ingredients on hand.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct CookableMeal {
+	/// The share of the recipe's ingredients on hand, from 0 to 1.
+	pub coverage: f32,
+	pub id: Uuid,
+	/// The recipe's ingredients that aren't on hand, in recipe order.
+	pub missing: Vec<String>,
+	pub name: String,
+}
+
+/// Pads the words of a name with spaces, so whole words can be found with `contains`.
+fn padded_words(name: &str) -> String {
+	format!(
+		" {} ",
+		name.to_lowercase()
+			.split(|character: char| !character.is_alphanumeric())
+			.filter(|word| !word.is_empty())
+			.collect::<Vec<_>>()
+			.join(" ")
+	)
+}
+
+impl MealPlan {
+	/// The names of every item checked off on the shopping lists.
+	pub fn checked_items(&self) -> Vec<String> {
+		let mut names: Vec<String> = vec![];
+		for item in self
+			.shopping_list
+			.iter()
+			.flat_map(|shopping_list| shopping_list.items.iter())
+			.filter(|item| item.have)
+		{
+			if !names
+				.iter()
+				.any(|name| name.eq_ignore_ascii_case(&item.name))
+			{
+				names.push(item.name.clone());
+			}
+		}
+
+		names
+	}
+
+	/// Ranks the meals by how many of their ingredients are in `ingredients`, most covered first.
+	/// Names are compared by catalog name, and having "chicken" covers "chicken thighs". Meals that
+	/// use none of the ingredients are left out.
+	pub fn cookable_meals(&self, ingredients: &[String]) -> Vec<CookableMeal> {
+		let on_hand = ingredients
+			.iter()
+			.map(|name| padded_words(&self.canonical_name(name)))
+			.filter(|words| !words.trim().is_empty())
+			.collect::<Vec<_>>();
+
+		let mut meals = self
+			.all_meals
+			.values()
+			.filter(|meal_info| !meal_info.ingredients.is_empty())
+			.filter_map(|meal_info| {
+				let missing = meal_info
+					.ingredients
+					.iter()
+					.map(|ingredient| self.canonical_name(&ingredient.name))
+					.filter(|name| {
+						let words = padded_words(name);
+						!on_hand.iter().any(|have| words.contains(have.as_str()))
+					})
+					.collect::<Vec<_>>();
+
+				if missing.len() == meal_info.ingredients.len() {
+					return None;
+				}
+
+				Some(CookableMeal {
+					coverage: 1.0 - missing.len() as f32 / meal_info.ingredients.len() as f32,
+					id: meal_info.id,
+					missing,
+					name: meal_info.name.clone(),
+				})
+			})
+			.collect::<Vec<_>>();
+
+		meals.sort_by(|a, b| {
+			b.coverage
+				.total_cmp(&a.coverage)
+				.then(a.missing.len().cmp(&b.missing.len()))
+				.then(a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+		});
+
+		meals
+	}
+}
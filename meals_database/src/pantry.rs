@@ -0,0 +1,120 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::{Amount, MealPlan, MealPlanError};
+
+/// Amounts smaller than this are treated as used up.
+const EMPTY: f32 = 0.001;
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct PantryItem {
+	pub amount: Amount,
+	#[serde(default)]
+	pub expires: Option<NaiveDate>,
+	pub name: String,
+}
+
+impl PantryItem {
+	pub fn is_expired(&self, today: NaiveDate) -> bool {
+		self.expires.is_some_and(|expires| expires < today)
+	}
+}
+
+impl MealPlan {
+	fn pantry_key(&self, name: &str) -> String {
+		self.canonical_name(name).to_lowercase()
+	}
+
+	pub fn pantry_item(&self, name: &str) -> Option<&PantryItem> {
+		self.pantry.get(&self.pantry_key(name))
+	}
+
+	/// Adds the item to the pantry under its catalog name, replacing whatever was there.
+	pub fn set_pantry_item(&mut self, mut item: PantryItem) {
+		item.name = self.canonical_name(&item.name);
+
+		let key = self.pantry_key(&item.name);
+		self.pantry.insert(key, item);
+	}
+
+	pub fn remove_pantry_item(&mut self, name: &str) -> Result<PantryItem, MealPlanError> {
+		self.pantry
+			.remove(&self.pantry_key(name))
+			.ok_or_else(|| MealPlanError::PantryItemNotFound { name: name.into() })
+	}
+
+	/// Puts a bought amount into the pantry.
+	pub fn restock_pantry(&mut self, name: &str, amount: &Amount) {
+		let key = self.pantry_key(name);
+		let density = self.density(name);
+
+		let Some(item) = self.pantry.get_mut(&key) else {
+			self.set_pantry_item(PantryItem {
+				amount: amount.clone(),
+				expires: None,
+				name: name.into(),
+			});
+
+			return;
+		};
+
+		match item.amount.add(amount, density) {
+			Some(total) => item.amount = total,
+			None => log::warn!(
+				"Cannot add {} {} to {} {} of '{}' in the pantry",
+				amount.value,
+				amount.units,
+				item.amount.value,
+				item.amount.units,
+				name
+			),
+		}
+	}
+
+	/// Takes a used amount out of the pantry, removing the item once it runs out. Ingredients that
+	/// aren't in the pantry are ignored.
+	pub fn use_pantry(&mut self, name: &str, amount: &Amount) {
+		let key = self.pantry_key(name);
+		let density = self.density(name);
+
+		let Some(item) = self.pantry.get_mut(&key) else {
+			return;
+		};
+
+		let Some(rest) = item.amount.subtract(amount, density) else {
+			log::warn!(
+				"Cannot take {} {} from {} {} of '{}' in the pantry",
+				amount.value,
+				amount.units,
+				item.amount.value,
+				item.amount.units,
+				name
+			);
+
+			return;
+		};
+
+		if rest.value <= EMPTY {
+			self.pantry.remove(&key);
+		} else {
+			item.amount = rest;
+		}
+	}
+
+	/// How much of an amount still has to be bought after using what's in the pantry. Expired items
+	/// as of `today` don't count. Returns `None` if the pantry has all of it.
+	pub fn still_needed(&self, name: &str, amount: &Amount, today: NaiveDate) -> Option<Amount> {
+		let Some(item) = self
+			.pantry_item(name)
+			.filter(|item| !item.is_expired(today))
+		else {
+			return Some(amount.clone());
+		};
+
+		let Some(missing) = amount.subtract(&item.amount, self.density(name)) else {
+			return Some(amount.clone());
+		};
+
+		Some(missing).filter(|missing| missing.value > EMPTY)
+	}
+}
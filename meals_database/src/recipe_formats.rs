@@ -0,0 +1,522 @@
+use serde_json::{json, Map, Value};
+use std::fmt::Display;
+use uuid::Uuid;
+
+use crate::{
+	normalize_recipe_json,
+	scraper::{json_ld, minutes},
+	Diet, MealInfo, RecipeFormatError, RecipeJSON, Units,
+};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RecipeFormat {
+	/// schema.org `Recipe`s, which keep the most of a recipe.
+	JsonLd,
+	Markdown,
+	/// Paprika's recipe JSON. Mealie's JSON can be imported too.
+	Paprika,
+}
+
+impl RecipeFormat {
+	pub const ALL: [RecipeFormat; 3] = [
+		RecipeFormat::JsonLd,
+		RecipeFormat::Markdown,
+		RecipeFormat::Paprika,
+	];
+
+	pub fn extension(&self) -> &'static str {
+		match self {
+			RecipeFormat::JsonLd => "jsonld",
+			RecipeFormat::Markdown => "md",
+			RecipeFormat::Paprika => "json",
+		}
+	}
+}
+
+impl TryFrom<&str> for RecipeFormat {
+	type Error = ();
+
+	fn try_from(value: &str) -> Result<Self, Self::Error> {
+		match value.trim().to_lowercase().as_str() {
+			"json-ld" | "jsonld" | "schema.org" => Ok(RecipeFormat::JsonLd),
+			"markdown" | "md" => Ok(RecipeFormat::Markdown),
+			"paprika" | "mealie" => Ok(RecipeFormat::Paprika),
+			_ => Err(()),
+		}
+	}
+}
+
+impl Display for RecipeFormat {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			RecipeFormat::JsonLd => f.write_str("json-ld"),
+			RecipeFormat::Markdown => f.write_str("markdown"),
+			RecipeFormat::Paprika => f.write_str("paprika"),
+		}
+	}
+}
+
+/// Amounts are written exactly as they're stored rather than rounded for reading, so importing an
+/// export gives back the same recipe.
+fn ingredient_lines(meal_info: &MealInfo) -> Vec<String> {
+	meal_info
+		.ingredients
+		.iter()
+		.map(|ingredient| match ingredient.amount.units {
+			Units::Count => format!("{} {}", ingredient.amount.value, ingredient.name),
+			_ => format!(
+				"{} {} {}",
+				ingredient.amount.value, ingredient.amount.units, ingredient.name
+			),
+		})
+		.collect()
+}
+
+fn step_lines(meal_info: &MealInfo) -> Vec<String> {
+	meal_info
+		.recipe
+		.iter()
+		.map(|step| step.description.replace('\n', " "))
+		.collect()
+}
+
+/// Writes the recipes out in `format`. JSON formats give a single object for a single recipe and an
+/// array otherwise.
+pub fn export_recipes<'a>(
+	meals: impl IntoIterator<Item = &'a MealInfo>,
+	format: RecipeFormat,
+) -> String {
+	let to_value: fn(&MealInfo) -> Value = match format {
+		RecipeFormat::JsonLd => to_json_ld,
+		RecipeFormat::Markdown => {
+			return meals
+				.into_iter()
+				.map(to_markdown)
+				.collect::<Vec<_>>()
+				.join("\n")
+		}
+		RecipeFormat::Paprika => to_paprika,
+	};
+
+	let values = meals.into_iter().map(to_value).collect::<Vec<_>>();
+	let value = match <[Value; 1]>::try_from(values) {
+		Ok([value]) => value,
+		Err(values) => Value::Array(values),
+	};
+
+	serde_json::to_string_pretty(&value).unwrap() + "\n"
+}
+
+fn to_json_ld(meal_info: &MealInfo) -> Value {
+	let mut recipe = json!({
+		"@context": "https://schema.org",
+		"@type": "Recipe",
+		"identifier": meal_info.id,
+		"name": meal_info.name,
+		"recipeIngredient": ingredient_lines(meal_info),
+		"recipeInstructions": step_lines(meal_info)
+			.into_iter()
+			.map(|text| json!({ "@type": "HowToStep", "text": text }))
+			.collect::<Vec<_>>(),
+		"recipeYield": meal_info.serving_size.to_string(),
+	});
+
+	let object = recipe.as_object_mut().unwrap();
+	let mut insert = |key: &str, value: Value| {
+		object.insert(key.into(), value);
+	};
+
+	if !meal_info.image.is_empty() {
+		insert("image", json!(meal_info.image));
+	}
+
+	if !meal_info.course.is_empty() {
+		insert("recipeCategory", json!(meal_info.course));
+	}
+
+	if !meal_info.cuisine.is_empty() {
+		insert("recipeCuisine", json!(meal_info.cuisine));
+	}
+
+	if !meal_info.tags.is_empty() {
+		insert("keywords", json!(meal_info.tags.join(", ")));
+	}
+
+	if !meal_info.diets.is_empty() {
+		let diets = meal_info.diets.iter().map(Diet::schema).collect::<Vec<_>>();
+		insert("suitableForDiet", json!(diets));
+	}
+
+	if let Some(prep_minutes) = meal_info.prep_minutes {
+		insert("prepTime", json!(format!("PT{}M", prep_minutes)));
+	}
+
+	if let Some(cook_minutes) = meal_info.cook_minutes {
+		insert("cookTime", json!(format!("PT{}M", cook_minutes)));
+	}
+
+	if let Some(total_minutes) = meal_info.total_minutes() {
+		insert("totalTime", json!(format!("PT{}M", total_minutes)));
+	}
+
+	recipe
+}
+
+/// Paprika has no courses, cuisines or diets, and calls tags categories.
+fn to_paprika(meal_info: &MealInfo) -> Value {
+	let time = |minutes: Option<u32>| {
+		minutes
+			.map(|minutes| format!("{} min", minutes))
+			.unwrap_or_default()
+	};
+
+	json!({
+		"categories": meal_info.tags,
+		"cook_time": time(meal_info.cook_minutes),
+		"directions": step_lines(meal_info).join("\n"),
+		"image_url": meal_info.image,
+		"ingredients": ingredient_lines(meal_info).join("\n"),
+		"name": meal_info.name,
+		"prep_time": time(meal_info.prep_minutes),
+		"servings": meal_info.serving_size.to_string(),
+		"total_time": time(meal_info.total_minutes()),
+		"uid": meal_info.id,
+	})
+}
+
+fn to_markdown(meal_info: &MealInfo) -> String {
+	let mut markdown = format!("# {}\n\n", meal_info.name);
+
+	if !meal_info.image.is_empty() {
+		markdown.push_str(&format!("![{}]({})\n\n", meal_info.name, meal_info.image));
+	}
+
+	let diets = meal_info
+		.diets
+		.iter()
+		.map(Diet::to_string)
+		.collect::<Vec<_>>();
+
+	let details = [
+		("Serves", meal_info.serving_size.to_string()),
+		("Course", meal_info.course.clone()),
+		("Cuisine", meal_info.cuisine.clone()),
+		(
+			"Prep",
+			meal_info
+				.prep_minutes
+				.map(|minutes| format!("{} min", minutes))
+				.unwrap_or_default(),
+		),
+		(
+			"Cook",
+			meal_info
+				.cook_minutes
+				.map(|minutes| format!("{} min", minutes))
+				.unwrap_or_default(),
+		),
+		("Diets", diets.join(", ")),
+		("Tags", meal_info.tags.join(", ")),
+	];
+
+	for (label, value) in details {
+		if !value.is_empty() {
+			markdown.push_str(&format!("- {}: {}\n", label, value));
+		}
+	}
+
+	markdown.push_str("\n## Ingredients\n\n");
+	for line in ingredient_lines(meal_info) {
+		markdown.push_str(&format!("- {}\n", line));
+	}
+
+	markdown.push_str("\n## Steps\n\n");
+	for (index, line) in step_lines(meal_info).into_iter().enumerate() {
+		markdown.push_str(&format!("{}. {}\n", index + 1, line));
+	}
+
+	markdown
+}
+
+/// Reads every recipe in `text`. Ingredients are parsed like scraped ones, and every recipe gets a
+/// new id so importing never overwrites a saved recipe.
+pub fn import_recipes(
+	text: &str,
+	format: RecipeFormat,
+) -> Result<Vec<MealInfo>, RecipeFormatError> {
+	let recipes = match format {
+		RecipeFormat::JsonLd => json_ld::recipes(&serde_json::from_str(text)?),
+		RecipeFormat::Markdown => from_markdown(text),
+		RecipeFormat::Paprika => from_paprika(&serde_json::from_str(text)?),
+	};
+
+	if recipes.is_empty() {
+		return Err(RecipeFormatError::NoRecipes);
+	}
+
+	Ok(recipes.into_iter().map(into_meal_info).collect())
+}
+
+/// The first number in a yield like "Serves 4" or "4 servings".
+fn servings(yields: &str) -> Option<usize> {
+	yields
+		.split(|character: char| !character.is_ascii_digit())
+		.find(|number| !number.is_empty())
+		.and_then(|number| number.parse().ok())
+		.filter(|servings| *servings > 0)
+}
+
+fn into_meal_info(recipe_json: RecipeJSON) -> MealInfo {
+	let serving_size = servings(&recipe_json.yields).unwrap_or(1);
+	let recipe = normalize_recipe_json(recipe_json);
+
+	MealInfo {
+		cook_minutes: recipe.cook_minutes,
+		course: recipe.course,
+		cuisine: recipe.cuisine,
+		diets: recipe.diets,
+		id: Uuid::new_v4(),
+		image: recipe.image,
+		ingredients: recipe
+			.ingredients
+			.into_iter()
+			.map(|ingredient| ingredient.into_ingredient())
+			.collect(),
+		name: recipe.name,
+		prep_minutes: recipe.prep_minutes,
+		recipe: recipe.recipe,
+		serving_size,
+		tags: recipe.tags,
+	}
+}
+
+/// Reads durations like "1 hr 30 mins" or "PT1H30M" as minutes. A bare number is in minutes.
+fn duration(text: &str) -> Option<u32> {
+	if text.trim().starts_with('P') {
+		return minutes(text);
+	}
+
+	// split "1hr30min" into "1 hr 30 min"
+	let mut spaced = String::new();
+	for character in text.to_lowercase().chars() {
+		let is_number = character.is_ascii_digit() || character == '.';
+		let was_number = spaced
+			.chars()
+			.last()
+			.is_some_and(|last| last.is_ascii_digit() || last == '.');
+		if is_number != was_number {
+			spaced.push(' ');
+		}
+
+		spaced.push(character);
+	}
+
+	let mut total = 0.0;
+	let mut number: Option<f32> = None;
+	for word in spaced.split_whitespace() {
+		if let Ok(value) = word.parse::<f32>() {
+			if let Some(number) = number {
+				total += number;
+			}
+
+			number = Some(value);
+			continue;
+		}
+
+		let Some(value) = number.take() else {
+			continue;
+		};
+
+		total += match word.chars().next() {
+			Some('d') => value * 24.0 * 60.0,
+			Some('h') => value * 60.0,
+			_ => value,
+		};
+	}
+
+	total += number.unwrap_or(0.0);
+
+	Some(total as u32).filter(|minutes| *minutes > 0)
+}
+
+fn string(value: Option<&Value>) -> String {
+	match value {
+		Some(Value::String(string)) => string.trim().into(),
+		Some(Value::Number(number)) => number.to_string(),
+		_ => String::new(),
+	}
+}
+
+/// Lists of strings, or of objects like Mealie's tags that hold the text under `key`.
+fn strings(value: Option<&Value>, key: &str) -> Vec<String> {
+	match value {
+		Some(Value::Array(values)) => values
+			.iter()
+			.map(|value| match value {
+				Value::Object(object) => string(object.get(key)),
+				value => string(Some(value)),
+			})
+			.filter(|string| !string.is_empty())
+			.collect(),
+		Some(Value::String(string)) => string
+			.lines()
+			.map(str::trim)
+			.filter(|line| !line.is_empty())
+			.map(String::from)
+			.collect(),
+		_ => vec![],
+	}
+}
+
+/// The first of `keys` the object has.
+fn field<'a>(object: &'a Map<String, Value>, keys: &[&str]) -> Option<&'a Value> {
+	keys.iter().find_map(|key| object.get(*key))
+}
+
+/// Reads Paprika recipes, and Mealie's recipes which use schema.org-like names for most fields.
+fn from_paprika(value: &Value) -> Vec<RecipeJSON> {
+	let objects = match value {
+		Value::Array(values) => values.iter().filter_map(Value::as_object).collect(),
+		Value::Object(object) => match object.get("recipes") {
+			Some(Value::Array(values)) => values.iter().filter_map(Value::as_object).collect(),
+			_ => vec![object],
+		},
+		_ => vec![],
+	};
+
+	objects
+		.into_iter()
+		.map(|object| {
+			// Mealie ingredients are objects, with the original line in `display` or `note`
+			let ingredients = match object.get("recipeIngredient") {
+				Some(Value::Array(values)) => values
+					.iter()
+					.map(|value| match value {
+						Value::Object(ingredient) => {
+							string(field(ingredient, &["display", "originalText", "note"]))
+						}
+						value => string(Some(value)),
+					})
+					.filter(|line| !line.is_empty())
+					.collect(),
+				_ => strings(object.get("ingredients"), "name"),
+			};
+
+			let image = string(field(object, &["image_url", "image"]));
+
+			RecipeJSON {
+				category: strings(object.get("recipeCategory"), "name").join(", "),
+				cook_time: duration(&string(field(object, &["cook_time", "cookTime"]))),
+				image: if image.starts_with("http") {
+					image
+				} else {
+					String::new()
+				},
+				ingredients,
+				instructions_list: strings(
+					field(object, &["directions", "recipeInstructions"]),
+					"text",
+				),
+				keywords: strings(field(object, &["categories", "tags"]), "name"),
+				prep_time: duration(&string(field(object, &["prep_time", "prepTime"]))),
+				title: string(object.get("name")),
+				yields: string(field(
+					object,
+					&["servings", "recipeYield", "recipeServings"],
+				)),
+				..Default::default()
+			}
+		})
+		.collect()
+}
+
+/// Reads recipes in the layout `to_markdown` writes. Every `# ` heading starts a new recipe.
+fn from_markdown(text: &str) -> Vec<RecipeJSON> {
+	let mut recipes: Vec<RecipeJSON> = vec![];
+	let mut section = "";
+	for line in text.lines().map(str::trim) {
+		if let Some(name) = line.strip_prefix("# ") {
+			recipes.push(RecipeJSON {
+				title: name.trim().into(),
+				..Default::default()
+			});
+			section = "";
+			continue;
+		}
+
+		let Some(recipe) = recipes.last_mut() else {
+			continue;
+		};
+
+		if let Some(heading) = line.strip_prefix("## ") {
+			section = match heading.trim().to_lowercase().as_str() {
+				"ingredients" => "ingredients",
+				"steps" | "instructions" | "directions" | "method" => "steps",
+				_ => "",
+			};
+			continue;
+		}
+
+		if line.is_empty() {
+			continue;
+		}
+
+		// "- item", "* item" or "1. item"
+		let item = line
+			.strip_prefix("- ")
+			.or_else(|| line.strip_prefix("* "))
+			.or_else(|| {
+				let (number, rest) = line.split_once(". ")?;
+				number.parse::<usize>().ok().map(|_| rest)
+			});
+
+		match section {
+			"ingredients" => recipe.ingredients.extend(item.map(String::from)),
+			"steps" => match item {
+				Some(step) => recipe.instructions_list.push(step.into()),
+				// a step that wraps onto more lines
+				None => match recipe.instructions_list.last_mut() {
+					Some(step) => {
+						step.push(' ');
+						step.push_str(line);
+					}
+					None => recipe.instructions_list.push(line.into()),
+				},
+			},
+			_ => {
+				if let Some(image) = line
+					.strip_prefix("![")
+					.and_then(|rest| rest.split_once("]("))
+					.and_then(|(_, rest)| rest.strip_suffix(')'))
+				{
+					recipe.image = image.into();
+					continue;
+				}
+
+				let Some((label, value)) = item.and_then(|item| item.split_once(':')) else {
+					continue;
+				};
+
+				let value = value.trim();
+				match label.trim().to_lowercase().as_str() {
+					"serves" | "servings" | "yield" => recipe.yields = value.into(),
+					"course" => recipe.category = value.into(),
+					"cuisine" => recipe.cuisine = value.into(),
+					"prep" => recipe.prep_time = duration(value),
+					"cook" => recipe.cook_time = duration(value),
+					"diets" => {
+						recipe.dietary_restrictions = value
+							.split(',')
+							.filter_map(|diet| Diet::try_from(diet).ok())
+							.map(|diet| diet.schema().into())
+							.collect()
+					}
+					"tags" => recipe.keywords = vec![value.into()],
+					_ => {}
+				}
+			}
+		}
+	}
+
+	recipes
+}
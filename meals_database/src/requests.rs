@@ -0,0 +1,110 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{MealPlanChange, ShoppingListItem};
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct CatalogEntryRequest {
+	pub name: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct DensityRequest {
+	pub grams_per_milliliter: Option<f32>,
+	pub name: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ExtraItemRequest {
+	pub name: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PlannedMealRequest {
+	pub date: NaiveDate,
+	pub id: Uuid,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct MoveMealRequest {
+	pub date: NaiveDate,
+	pub id: Uuid,
+	pub new_date: NaiveDate,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ShiftMealsRequest {
+	pub days: i64,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct LeftoversRequest {
+	pub date: NaiveDate,
+	pub id: Uuid,
+	pub leftovers: bool,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PantryItemRequest {
+	pub name: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ServingsRequest {
+	pub date: NaiveDate,
+	pub id: Uuid,
+	pub servings: Option<usize>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ShoppingListRequest {
+	pub shopping_list_id: Uuid,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ShoppingListItemRequest {
+	pub have: bool,
+	pub id: Uuid,
+	pub shopping_list_id: Uuid,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct EditShoppingListItemRequest {
+	pub item: ShoppingListItem,
+	pub shopping_list_id: Uuid,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct RemoveShoppingListItemRequest {
+	pub id: Uuid,
+	pub shopping_list_id: Uuid,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct RenameShoppingListRequest {
+	pub name: String,
+	pub shopping_list_id: Uuid,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct MergeShoppingListsRequest {
+	pub from_id: Uuid,
+	pub into_id: Uuid,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct StapleRequest {
+	pub name: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct DeleteMealRequest {
+	pub id: Uuid,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ChangeResponse {
+	pub change: Option<MealPlanChange>,
+	pub revision: u64,
+}
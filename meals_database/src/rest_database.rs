@@ -1,95 +1,626 @@
+use async_trait::async_trait;
 use async_tungstenite::{
 	tokio::{connect_async, TokioAdapter},
 	tungstenite::Message,
 	WebSocketStream,
 };
-use futures::{executor::block_on, StreamExt};
-use reqwest::header;
+use chrono::{Local, NaiveDate};
+use futures::StreamExt;
+use reqwest::{header, StatusCode};
 use serde::{Deserialize, Serialize};
-use serde_json::json;
-use std::time::Duration;
-use tokio::{
-	net::TcpStream,
+use serde_json::{json, Value};
+use std::{
+	fmt::Display,
+	fs::File,
+	io::BufReader,
+	path::PathBuf,
 	sync::{
-		mpsc::{self, Receiver, Sender},
+		atomic::{AtomicBool, Ordering},
 		RwLock, RwLockReadGuard, RwLockWriteGuard,
 	},
-	time::sleep,
+	time::Duration,
+};
+use tokio::{
+	net::TcpStream,
+	sync::Mutex,
+	time::{sleep, timeout},
+};
+use uuid::Uuid;
+
+use crate::{
+	json_database::write_json_atomically, CatalogEntryRequest, ChangeResponse, Database,
+	DatabaseError, DeleteMealRequest, DensityRequest, EditShoppingListItemRequest,
+	ExtraItemRequest, LeftoversRequest, MealPlan, MealPlanChange, MealPlanMessage,
+	MergeShoppingListsRequest, MoveMealRequest, PantryItemRequest, PlannedMealRequest,
+	RemoveShoppingListItemRequest, RenameShoppingListRequest, ServingsRequest, ShiftMealsRequest,
+	ShoppingListItemRequest, ShoppingListRequest, StapleRequest,
 };
 
-use crate::MealPlanMessage;
-
-pub struct RestDatabase<T>
-where
-	T: Serialize + for<'a> Deserialize<'a> + Default,
-{
-	data: RwLock<T>,
-	get_all_url: String,
-	replace_url: String,
-	sender: Sender<MealPlanMessage>,
-	ws_socket: RwLock<WebSocketStream<TokioAdapter<TcpStream>>>,
+const RECONNECT_INTERVAL: Duration = Duration::from_secs(5);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A write made while the server was unreachable, along with the revision it was made against.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+struct QueuedChange {
+	base_revision: u64,
+	change: MealPlanChange,
+}
+
+/// Mirrors the server's meal plan and keeps it up to date through the websocket. The last known
+/// meal plan is cached on disk so the client can start while the server is down, and writes made
+/// while disconnected are queued on disk and replayed once the server is reachable again.
+pub struct RestDatabase {
+	cache_path: PathBuf,
+	client: reqwest::Client,
+	connected: AtomicBool,
+	data: RwLock<MealPlan>,
+	data_directory: PathBuf,
+	queue: std::sync::Mutex<Vec<QueuedChange>>,
+	queue_path: PathBuf,
+	replaying: Mutex<()>,
+	rest_url: String,
+	ws_socket: Mutex<Option<WebSocketStream<TokioAdapter<TcpStream>>>>,
 	ws_url: String,
 }
 
-impl<T> RestDatabase<T>
-where
-	T: Serialize + for<'a> Deserialize<'a> + Default,
-{
-	pub async fn new(
-		get_all_url: &str,
-		replace_url: &str,
-		ws_url: &str,
-	) -> (Self, Receiver<MealPlanMessage>) {
-		let ws_socket = Self::try_connect(ws_url).await;
-		let (sender, receiver) = mpsc::channel(100);
-
-		(
-			Self {
-				data: RwLock::new(T::default()),
-				get_all_url: get_all_url.into(),
-				replace_url: replace_url.into(),
-				sender,
-				ws_socket: RwLock::new(ws_socket),
-				ws_url: ws_url.into(),
-			},
-			receiver,
-		)
+impl RestDatabase {
+	pub fn new(rest_url: &str, ws_url: &str) -> Result<Self, DatabaseError> {
+		#[allow(deprecated)]
+		let Some(home_directory) = std::env::home_dir() else {
+			return Err(DatabaseError::InvalidConfig(
+				"could not find the home directory to keep the meal plan in".into(),
+			));
+		};
+
+		Ok(Self::with_data_directory(
+			rest_url,
+			ws_url,
+			home_directory.join(".local/share/bansheelong2"),
+		))
+	}
+
+	/// Keeps the cache, write queue and auth token in `data_directory` instead of the user's data
+	/// directory.
+	pub fn with_data_directory(rest_url: &str, ws_url: &str, data_directory: PathBuf) -> Self {
+		if let Err(error) = std::fs::create_dir_all(&data_directory) {
+			log::error!(
+				"Could not create '{}': {}",
+				data_directory.to_string_lossy(),
+				error
+			);
+		}
+
+		let queue_path = data_directory.join("meals-queue.json");
+		let queue = match File::open(&queue_path) {
+			Ok(file) => serde_json::from_reader(BufReader::new(file)).unwrap_or_else(|error| {
+				log::error!("Discarding unreadable write queue: {}", error);
+				vec![]
+			}),
+			Err(_) => vec![],
+		};
+
+		Self {
+			cache_path: data_directory.join("meals-cache.json"),
+			client: reqwest::Client::builder()
+				.timeout(REQUEST_TIMEOUT)
+				.build()
+				.unwrap(),
+			connected: AtomicBool::new(true),
+			data: RwLock::new(MealPlan::default()),
+			data_directory,
+			queue: std::sync::Mutex::new(queue),
+			queue_path,
+			replaying: Mutex::new(()),
+			rest_url: rest_url.trim_end_matches('/').into(),
+			ws_socket: Mutex::new(None),
+			ws_url: ws_url.into(),
+		}
 	}
 
-	pub fn get(&self) -> RwLockReadGuard<T> {
-		block_on(self.data.read())
+	fn get_mut(&self) -> RwLockWriteGuard<'_, MealPlan> {
+		self.data.write().unwrap()
 	}
 
-	pub fn get_mut(&self) -> RwLockWriteGuard<T> {
-		block_on(self.data.write())
+	async fn try_connect(ws_url: &str) -> Option<WebSocketStream<TokioAdapter<TcpStream>>> {
+		match timeout(REQUEST_TIMEOUT, connect_async(ws_url)).await {
+			Ok(Ok((ws_socket, _))) => {
+				log::info!("Connected to '{}'", ws_url);
+				Some(ws_socket)
+			}
+			Ok(Err(error)) => {
+				log::error!("Failed to connect to '{}': {:?}", ws_url, error);
+				None
+			}
+			Err(_) => {
+				log::error!("Timed out connecting to '{}'", ws_url);
+				None
+			}
+		}
 	}
 
-	async fn try_connect(ws_url: &str) -> WebSocketStream<TokioAdapter<TcpStream>> {
+	/// Marks the server as unreachable. Returns true if it was reachable until now.
+	fn disconnect(&self, reason: impl Display) -> bool {
+		let was_connected = self.connected.swap(false, Ordering::SeqCst);
+		if was_connected {
+			log::warn!(
+				"Lost connection to '{}', queueing changes until it returns: {}",
+				self.rest_url,
+				reason
+			);
+		}
+
+		was_connected
+	}
+
+	/// Catches up with the server after being disconnected and sends everything that was queued. A
+	/// queued change that conflicts with someone else's still counts as reconnected, it stays queued
+	/// until it's discarded or overwritten.
+	async fn reconnect(&self) -> Result<(), DatabaseError> {
+		self.refresh().await?;
+		let result = self.replay().await;
+		if matches!(result, Ok(()) | Err(DatabaseError::Conflict))
+			&& !self.connected.swap(true, Ordering::SeqCst)
+		{
+			log::info!("Reconnected to '{}'", self.rest_url);
+		}
+
+		result
+	}
+
+	fn is_unreachable(error: &DatabaseError) -> bool {
+		match error {
+			DatabaseError::Request(error) => error.is_connect() || error.is_timeout(),
+			_ => false,
+		}
+	}
+
+	/// Fetches the server's meal plan and applies the queued changes on top, so the local copy shows
+	/// writes that haven't been sent yet. The revision stays the server's so replayed writes match.
+	async fn refresh(&self) -> Result<(), DatabaseError> {
+		let mut meal_plan = self
+			.client
+			.get(format!("{}/meals/all", self.rest_url))
+			.header(header::ACCEPT, "application/json")
+			.header(header::AUTHORIZATION, self.authorization()?)
+			.send()
+			.await?
+			.error_for_status()?
+			.json::<MealPlan>()
+			.await?;
+
+		for queued_change in self.queue.lock().unwrap().iter() {
+			if let Err(error) = meal_plan.apply(&queued_change.change) {
+				log::warn!("Queued change no longer applies: {}", error);
+			}
+		}
+
+		*self.get_mut() = meal_plan;
+		self.save_cache();
+
+		Ok(())
+	}
+
+	/// Sends queued changes to the server in the order they were made, each against the revision it
+	/// was made at. Stops at the first change the server doesn't accept and leaves it queued, e.g.
+	/// with `DatabaseError::Conflict` if someone else changed the meal plan in the meantime.
+	async fn replay(&self) -> Result<(), DatabaseError> {
+		let _replaying = self.replaying.lock().await;
+
 		loop {
-			match connect_async(ws_url).await {
-				Ok((ws_socket, _)) => {
-					log::info!("Connected to '{}'", ws_url);
-					return ws_socket;
+			let Some(queued_change) = self.queue.lock().unwrap().first().cloned() else {
+				return Ok(());
+			};
+
+			let (path, body) = Self::endpoint(queued_change.change.clone());
+			let response = self
+				.post(path, &body, queued_change.base_revision)
+				.await
+				.inspect_err(|error| {
+					log::warn!(
+						"Could not replay queued change {:?}: {}",
+						queued_change.change,
+						error
+					)
+				})?
+				.json::<ChangeResponse>()
+				.await?;
+
+			{
+				let mut queue = self.queue.lock().unwrap();
+				queue.remove(0);
+
+				// the changes queued after this one were made on top of it
+				for later_change in queue.iter_mut().filter(|later_change| {
+					later_change.base_revision == queued_change.base_revision
+				}) {
+					later_change.base_revision = response.revision;
+				}
+			}
+
+			self.save_queue();
+			self.refresh().await?;
+		}
+	}
+
+	/// Applies a write locally and queues it for when the server is reachable again.
+	fn enqueue(&self, change: MealPlanChange) -> Result<(), DatabaseError> {
+		let base_revision = {
+			let mut meal_plan = self.get_mut();
+			meal_plan.apply(&change)?;
+			meal_plan.revision
+		};
+
+		self.queue.lock().unwrap().push(QueuedChange {
+			base_revision,
+			change,
+		});
+
+		self.save_queue();
+		self.save_cache();
+
+		Ok(())
+	}
+
+	fn load_cache(&self) -> Result<(), DatabaseError> {
+		match File::open(&self.cache_path) {
+			Ok(file) => *self.get_mut() = serde_json::from_reader(BufReader::new(file))?,
+			Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+				log::warn!("No cached meal plan to start from, starting empty");
+			}
+			Err(error) => return Err(error.into()),
+		}
+
+		Ok(())
+	}
+
+	fn save_cache(&self) {
+		let meal_plan = self.get();
+		if let Err(error) = write_json_atomically(&self.cache_path, &*meal_plan) {
+			log::error!("Could not cache meal plan: {}", error);
+		}
+	}
+
+	fn save_queue(&self) {
+		let queue = self.queue.lock().unwrap();
+		if let Err(error) = write_json_atomically(&self.queue_path, &*queue) {
+			log::error!("Could not save write queue: {}", error);
+		}
+	}
+
+	/// Sends a write that was made against `revision`. The server rejects it if the meal plan has
+	/// changed since.
+	async fn post<B>(
+		&self,
+		path: &str,
+		body: &B,
+		revision: u64,
+	) -> Result<reqwest::Response, DatabaseError>
+	where
+		B: Serialize,
+	{
+		let response = self
+			.client
+			.post(format!("{}{}", self.rest_url, path))
+			.json(body)
+			.header(header::ACCEPT, "application/json")
+			.header(header::AUTHORIZATION, self.authorization()?)
+			.header(header::IF_MATCH, format!("\"{}\"", revision))
+			.send()
+			.await?;
+
+		if response.status() == StatusCode::CONFLICT {
+			log::warn!("Write to '{}' was rejected as stale, reloading", path);
+			self.refresh().await?;
+			return Err(DatabaseError::Conflict);
+		}
+
+		Ok(response.error_for_status()?)
+	}
+
+	async fn post_change<B>(&self, path: &str, body: &B) -> Result<(), DatabaseError>
+	where
+		B: Serialize,
+	{
+		let revision = self.get().revision;
+		let response = self
+			.post(path, body, revision)
+			.await?
+			.json::<ChangeResponse>()
+			.await?;
+
+		if let Some(change) = response.change {
+			if !self.apply_change(response.revision, &change) {
+				self.refresh().await?;
+			}
+		}
+
+		Ok(())
+	}
+
+	/// Applies a change broadcast by (or returned from) the server. Changes arrive through both the
+	/// websocket and write responses, so anything at or below the current revision has already been
+	/// applied. Returns false if a revision was skipped and the caller should reload everything.
+	fn apply_change(&self, revision: u64, change: &MealPlanChange) -> bool {
+		// the local copy already has the queued changes applied, so it has to be rebuilt instead
+		if !self.queue.lock().unwrap().is_empty() {
+			return false;
+		}
+
+		let mut meal_plan = self.get_mut();
+
+		let current_revision = meal_plan.revision;
+		if revision <= current_revision {
+			return true;
+		} else if revision != current_revision + 1 {
+			log::warn!(
+				"Missed changes between revision {} and {}",
+				current_revision,
+				revision
+			);
+			return false;
+		}
+
+		if let Err(error) = meal_plan.apply(change) {
+			log::error!("Could not apply change to local meal plan: {}", error);
+			return false;
+		}
+
+		meal_plan.revision = revision;
+		drop(meal_plan);
+
+		self.save_cache();
+
+		true
+	}
+
+	fn endpoint(change: MealPlanChange) -> (&'static str, Value) {
+		match change {
+			MealPlanChange::CatalogEntryDeleted { name } => {
+				("/meals/catalog/delete", json!(CatalogEntryRequest { name }))
+			}
+			MealPlanChange::CatalogEntrySaved { entry } => ("/meals/catalog", json!(entry)),
+			MealPlanChange::DensitySet {
+				grams_per_milliliter,
+				name,
+			} => (
+				"/meals/density",
+				json!(DensityRequest {
+					grams_per_milliliter,
+					name,
+				}),
+			),
+			MealPlanChange::ExtraItemAdded { item } => ("/meals/extra-items", json!(item)),
+			MealPlanChange::ExtraItemRemoved { name } => (
+				"/meals/extra-items/remove",
+				json!(ExtraItemRequest { name }),
+			),
+			MealPlanChange::LeftoversSet {
+				date,
+				id,
+				leftovers,
+			} => (
+				"/meals/leftovers",
+				json!(LeftoversRequest {
+					date,
+					id,
+					leftovers,
+				}),
+			),
+			MealPlanChange::MealCompleted { date, id } => {
+				("/meals/complete", json!(PlannedMealRequest { date, id }))
+			}
+			MealPlanChange::MealDeleted { id } => {
+				("/meals/recipe/delete", json!(DeleteMealRequest { id }))
+			}
+			MealPlanChange::MealMoved { date, id, new_date } => {
+				("/meals/move", json!(MoveMealRequest { date, id, new_date }))
+			}
+			MealPlanChange::MealPlanned { meal_stub } => ("/meals/plan", json!(meal_stub)),
+			MealPlanChange::MealSaved { meal_info } => ("/meals/recipe", json!(meal_info)),
+			MealPlanChange::MealUnplanned { date, id } => {
+				("/meals/unplan", json!(PlannedMealRequest { date, id }))
+			}
+			MealPlanChange::MealsImported { meals } => ("/meals/recipes", json!(meals)),
+			MealPlanChange::MealsShifted { days } => {
+				("/meals/shift", json!(ShiftMealsRequest { days }))
+			}
+			MealPlanChange::NutritionImported { entries } => ("/meals/nutrition", json!(entries)),
+			MealPlanChange::PantryItemRemoved { name } => {
+				("/meals/pantry/remove", json!(PantryItemRequest { name }))
+			}
+			MealPlanChange::PantryItemSet { item } => ("/meals/pantry", json!(item)),
+			MealPlanChange::ServingsSet { date, id, servings } => (
+				"/meals/servings",
+				json!(ServingsRequest { date, id, servings }),
+			),
+			MealPlanChange::ShoppingListAdded { shopping_list } => {
+				("/meals/shopping-list/add", json!(shopping_list))
+			}
+			MealPlanChange::ShoppingListItemAdded {
+				item,
+				shopping_list_id,
+			} => (
+				"/meals/shopping-list/item/add",
+				json!(EditShoppingListItemRequest {
+					item,
+					shopping_list_id,
+				}),
+			),
+			MealPlanChange::ShoppingListItemEdited {
+				item,
+				shopping_list_id,
+			} => (
+				"/meals/shopping-list/item/edit",
+				json!(EditShoppingListItemRequest {
+					item,
+					shopping_list_id,
+				}),
+			),
+			MealPlanChange::ShoppingListItemRemoved {
+				id,
+				shopping_list_id,
+			} => (
+				"/meals/shopping-list/item/remove",
+				json!(RemoveShoppingListItemRequest {
+					id,
+					shopping_list_id,
+				}),
+			),
+			MealPlanChange::ShoppingListItemSet {
+				have,
+				id,
+				shopping_list_id,
+			} => (
+				"/meals/shopping-list/item",
+				json!(ShoppingListItemRequest {
+					have,
+					id,
+					shopping_list_id,
+				}),
+			),
+			MealPlanChange::ShoppingListPruned { shopping_list_id } => (
+				"/meals/shopping-list/prune",
+				json!(ShoppingListRequest { shopping_list_id }),
+			),
+			MealPlanChange::ShoppingListRenamed {
+				name,
+				shopping_list_id,
+			} => (
+				"/meals/shopping-list/rename",
+				json!(RenameShoppingListRequest {
+					name,
+					shopping_list_id,
+				}),
+			),
+			MealPlanChange::ShoppingListsMerged { from_id, into_id } => (
+				"/meals/shopping-list/merge",
+				json!(MergeShoppingListsRequest { from_id, into_id }),
+			),
+			MealPlanChange::SnapshotRestored { snapshot } => ("/meals/restore", json!(snapshot)),
+			MealPlanChange::StapleRemoved { name } => {
+				("/meals/staples/remove", json!(StapleRequest { name }))
+			}
+			MealPlanChange::StapleSaved { staple } => ("/meals/staples", json!(staple)),
+		}
+	}
+
+	fn authorization(&self) -> Result<String, DatabaseError> {
+		let path = self.data_directory.join("auth-token");
+		let token = std::fs::read_to_string(&path).map_err(|error| {
+			DatabaseError::InvalidConfig(format!(
+				"could not read auth token '{}': {}",
+				path.to_string_lossy(),
+				error
+			))
+		})?;
+
+		Ok(format!("Bearer {}", token.trim()))
+	}
+}
+
+#[async_trait]
+impl Database for RestDatabase {
+	fn get(&self) -> RwLockReadGuard<'_, MealPlan> {
+		self.data.read().unwrap()
+	}
+
+	async fn load(&self) -> Result<(), DatabaseError> {
+		if self.is_connected() {
+			match self.refresh().await {
+				Ok(()) => return self.replay().await,
+				Err(error) if Self::is_unreachable(&error) => {
+					self.disconnect(&error);
+				}
+				Err(error) => return Err(error),
+			}
+		}
+
+		self.load_cache()
+	}
+
+	async fn apply(&self, change: MealPlanChange) -> Result<(), DatabaseError> {
+		if self.is_connected() {
+			match self.replay().await {
+				Ok(()) => {
+					let (path, body) = Self::endpoint(change.clone());
+					match self.post_change(path, &body).await {
+						Err(error) if Self::is_unreachable(&error) => {
+							self.disconnect(&error);
+						}
+						result => return result,
+					}
+				}
+				Err(error) if Self::is_unreachable(&error) => {
+					self.disconnect(&error);
 				}
 				Err(error) => {
-					log::error!("Failed to connect to '{}': {:?}", ws_url, error);
-					sleep(Duration::from_secs(5)).await;
+					// the change waits behind the queued one the server didn't take
+					self.enqueue(change)?;
+					return Err(error);
 				}
 			}
 		}
+
+		self.enqueue(change)
+	}
+
+	async fn replace(&self, meal_plan: MealPlan) -> Result<(), DatabaseError> {
+		if !self.is_connected() {
+			return Err(DatabaseError::Offline);
+		}
+
+		let revision = self.get().revision;
+		self.post("/meals/replace", &meal_plan, revision).await?;
+		self.refresh().await
 	}
 
-	pub async fn recv_loop(&self) {
+	async fn next_message(&self) -> MealPlanMessage {
+		let mut ws_socket = self.ws_socket.lock().await;
+
 		loop {
-			let mut ws_socket = self.ws_socket.write().await;
+			let Some(socket) = ws_socket.as_mut() else {
+				let Some(socket) = Self::try_connect(&self.ws_url).await else {
+					sleep(RECONNECT_INTERVAL).await;
+					continue;
+				};
 
-			let result = match ws_socket.next().await {
-				Some(message) => message,
-				None => {
+				*ws_socket = Some(socket);
+
+				// anything could have happened while the socket was down
+				match self.reconnect().await {
+					Ok(()) | Err(DatabaseError::Conflict) => return MealPlanMessage::Update,
+					Err(error) => {
+						log::error!("Could not resynchronize with server: {}", error);
+						*ws_socket = None;
+						sleep(RECONNECT_INTERVAL).await;
+						continue;
+					}
+				}
+			};
+
+			let result = match timeout(RECONNECT_INTERVAL, socket.next()).await {
+				Ok(Some(result)) => result,
+				Ok(None) => {
 					log::error!("Stream became empty");
 
-					sleep(Duration::from_secs(5)).await;
-					*ws_socket = Self::try_connect(&self.ws_url).await;
+					*ws_socket = None;
+					if self.disconnect("websocket stream ended") {
+						return MealPlanMessage::Update;
+					}
+
+					continue;
+				}
+				Err(_) => {
+					// writes can fail while the socket stays up, so retry them on a timer
+					if !self.is_connected() {
+						match self.reconnect().await {
+							Ok(()) | Err(DatabaseError::Conflict) => {
+								return MealPlanMessage::Update
+							}
+							Err(error) => log::error!("Still disconnected: {}", error),
+						}
+					}
+
 					continue;
 				}
 			};
@@ -99,12 +630,15 @@ where
 				Err(error) => {
 					log::error!("Encountered error during read: {:?}", error);
 
-					if let Err(error) = ws_socket.close(None).await {
+					if let Err(error) = socket.close(None).await {
 						log::error!("Failed to close socket: {:?}", error);
 					}
 
-					sleep(Duration::from_secs(5)).await;
-					*ws_socket = Self::try_connect(&self.ws_url).await;
+					*ws_socket = None;
+					if self.disconnect(error) {
+						return MealPlanMessage::Update;
+					}
+
 					continue;
 				}
 			};
@@ -116,9 +650,13 @@ where
 						continue;
 					};
 
-					if let Err(error) = self.sender.send(meal_plan_message).await {
-						log::error!("Could not send message through channel: {:?}", error);
+					if let MealPlanMessage::Changed { change, revision } = &meal_plan_message {
+						if !self.apply_change(*revision, change) {
+							return MealPlanMessage::Update;
+						}
 					}
+
+					return meal_plan_message;
 				}
 				Message::Binary(_) => log::error!("Unexpected binary message"),
 				Message::Ping(_) => log::error!("Unexpected ping message"),
@@ -126,8 +664,11 @@ where
 				Message::Close(close_frame) => {
 					log::error!("Unexpected close: {:?}", close_frame);
 
-					sleep(Duration::from_secs(5)).await;
-					*ws_socket = Self::try_connect(&self.ws_url).await;
+					*ws_socket = None;
+					if self.disconnect("websocket closed") {
+						return MealPlanMessage::Update;
+					}
+
 					continue;
 				}
 				Message::Frame(_) => log::error!("Unexpected frame"),
@@ -135,62 +676,111 @@ where
 		}
 	}
 
-	pub async fn save(&self) {
-		#[allow(deprecated)]
-		let home_directory = std::env::home_dir().unwrap();
-
-		let data = self.get();
-		let client = reqwest::Client::new();
-		client
-			.post(&self.replace_url)
-			.body(json!(&*data).to_string())
-			.header(header::CONTENT_TYPE, "application/json")
-			.header(header::ACCEPT, "application/json")
-			.header(
-				header::AUTHORIZATION,
-				format!(
-					"Bearer {}",
-					std::fs::read_to_string(format!(
-						"{}/.local/share/bansheelong2/auth-token",
-						home_directory.to_string_lossy()
-					))
-					.unwrap()
-					.trim()
-				),
-			)
-			.send()
-			.await
-			.unwrap();
+	fn is_connected(&self) -> bool {
+		self.connected.load(Ordering::SeqCst)
 	}
 
-	pub async fn load(&self) {
-		#[allow(deprecated)]
-		let home_directory = std::env::home_dir().unwrap();
+	fn queued_changes(&self) -> usize {
+		self.queue.lock().unwrap().len()
+	}
 
-		let client = reqwest::Client::new();
-		let response = client
-			.get(&self.get_all_url)
-			.header(header::ACCEPT, "application/json")
-			.header(
-				header::AUTHORIZATION,
-				format!(
-					"Bearer {}",
-					std::fs::read_to_string(format!(
-						"{}/.local/share/bansheelong2/auth-token",
-						home_directory.to_string_lossy()
-					))
-					.unwrap()
-					.trim()
-				),
-			)
-			.send()
-			.await
-			.unwrap()
-			.json::<T>()
+	async fn discard_queued_change(&self) -> Result<(), DatabaseError> {
+		if !self.is_connected() {
+			return Err(DatabaseError::Offline);
+		}
+
+		{
+			let mut queue = self.queue.lock().unwrap();
+			if queue.is_empty() {
+				return Ok(());
+			}
+
+			let queued_change = queue.remove(0);
+			log::info!("Discarding queued change {:?}", queued_change.change);
+		}
+
+		self.save_queue();
+		self.refresh().await?;
+		self.replay().await
+	}
+
+	async fn overwrite_queued_change(&self) -> Result<(), DatabaseError> {
+		if !self.is_connected() {
+			return Err(DatabaseError::Offline);
+		}
+
+		self.refresh().await?;
+		let revision = self.get().revision;
+
+		{
+			let mut queue = self.queue.lock().unwrap();
+			let Some(base_revision) = queue
+				.first()
+				.map(|queued_change| queued_change.base_revision)
+			else {
+				return Ok(());
+			};
+
+			// the changes made alongside the conflicting one go on top of the server's meal plan too
+			for queued_change in queue
+				.iter_mut()
+				.filter(|queued_change| queued_change.base_revision == base_revision)
+			{
+				queued_change.base_revision = revision;
+			}
+		}
+
+		self.save_queue();
+		self.replay().await
+	}
+
+	async fn undo(&self) -> Result<(), DatabaseError> {
+		if !self.is_connected() {
+			return Err(DatabaseError::Offline);
+		}
+
+		self.post_change("/meals/undo", &json!({})).await
+	}
+
+	async fn redo(&self) -> Result<(), DatabaseError> {
+		if !self.is_connected() {
+			return Err(DatabaseError::Offline);
+		}
+
+		self.post_change("/meals/redo", &json!({})).await
+	}
+
+	async fn generate_shopping_list(&self) -> Result<(), DatabaseError> {
+		if !self.is_connected() {
+			let shopping_list = self.get().generate_shopping_list(Local::now().date_naive());
+			let Some(shopping_list) = shopping_list else {
+				return Ok(());
+			};
+
+			return self.enqueue(MealPlanChange::ShoppingListAdded { shopping_list });
+		}
+
+		self.post_change("/meals/shopping-list/generate", &json!({}))
 			.await
-			.unwrap();
+	}
 
-		let mut data = self.get_mut();
-		*data = response;
+	async fn generate_shopping_list_for_meal(
+		&self,
+		date: NaiveDate,
+		id: Uuid,
+	) -> Result<(), DatabaseError> {
+		if !self.is_connected() {
+			let today = Local::now().date_naive();
+			let shopping_list = self
+				.get()
+				.generate_shopping_list_for_meal(date, id, today)?;
+			return self.enqueue(MealPlanChange::ShoppingListAdded { shopping_list });
+		}
+
+		self.post_change(
+			"/meals/shopping-list/generate-for-meal",
+			&PlannedMealRequest { date, id },
+		)
+		.await
 	}
 }
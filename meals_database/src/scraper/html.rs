@@ -0,0 +1,34 @@
+use scraper::{ElementRef, Html, Node};
+
+/// The readable text inside an element, with whitespace collapsed. Scripts and styles are skipped
+/// and line breaks read as spaces.
+pub(crate) fn text(element: ElementRef) -> String {
+	let mut text = String::new();
+	push_text(element, &mut text);
+
+	text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn push_text(element: ElementRef, text: &mut String) {
+	for child in element.children() {
+		match child.value() {
+			Node::Text(contents) => text.push_str(contents),
+			Node::Element(child_element) => match child_element.name() {
+				"br" => text.push(' '),
+				"script" | "style" => {}
+				_ => push_text(ElementRef::wrap(child).unwrap(), text),
+			},
+			_ => {}
+		}
+	}
+}
+
+/// The text inside the element exactly as it appears in the page, for reading scripts.
+pub(crate) fn raw_text(element: ElementRef) -> String {
+	element.text().collect()
+}
+
+/// Reads a snippet of markup as text, decoding entities and dropping any tags.
+pub(crate) fn clean(fragment: &str) -> String {
+	text(Html::parse_fragment(fragment).root_element())
+}
@@ -0,0 +1,182 @@
+use scraper::{Html, Selector};
+use serde_json::{Map, Value};
+
+use super::{host, html, minutes, RecipeJSON};
+
+/// Finds the first schema.org `Recipe` in the page's `application/ld+json` scripts, looking inside
+/// arrays and `@graph`s.
+pub(crate) fn extract(document: &Html, url: &str) -> Option<RecipeJSON> {
+	let scripts = Selector::parse("script[type]").unwrap();
+
+	document
+		.select(&scripts)
+		.filter(|script| {
+			script
+				.attr("type")
+				.is_some_and(|kind| kind.to_ascii_lowercase().contains("ld+json"))
+		})
+		.filter_map(|script| parse_json(&html::raw_text(script)))
+		.find_map(|value| find_recipe(&value).map(|recipe| to_recipe_json(recipe, url)))
+}
+
+/// Every schema.org `Recipe` in a JSON-LD document, for importing exported recipes in bulk.
+pub(crate) fn recipes(value: &Value) -> Vec<RecipeJSON> {
+	let mut recipes = vec![];
+	find_recipes(value, &mut recipes);
+
+	recipes
+		.into_iter()
+		.map(|recipe| to_recipe_json(recipe, ""))
+		.collect()
+}
+
+fn find_recipes<'a>(value: &'a Value, recipes: &mut Vec<&'a Map<String, Value>>) {
+	match value {
+		Value::Array(values) => {
+			for value in values {
+				find_recipes(value, recipes);
+			}
+		}
+		Value::Object(object) if is_type(object, "Recipe") => recipes.push(object),
+		Value::Object(object) => {
+			for value in object.values() {
+				find_recipes(value, recipes);
+			}
+		}
+		_ => {}
+	}
+}
+
+fn parse_json(text: &str) -> Option<Value> {
+	let text = text
+		.trim()
+		.trim_start_matches("<!--")
+		.trim_end_matches("-->")
+		.trim();
+
+	match serde_json::from_str(text) {
+		Ok(value) => Some(value),
+		Err(error) => {
+			log::warn!("Skipping unreadable JSON-LD: {}", error);
+			None
+		}
+	}
+}
+
+fn find_recipe(value: &Value) -> Option<&Map<String, Value>> {
+	match value {
+		Value::Array(values) => values.iter().find_map(find_recipe),
+		Value::Object(object) if is_type(object, "Recipe") => Some(object),
+		Value::Object(object) => object.values().find_map(find_recipe),
+		_ => None,
+	}
+}
+
+fn is_type(object: &Map<String, Value>, name: &str) -> bool {
+	let matches = |kind: &Value| {
+		kind.as_str()
+			.is_some_and(|kind| kind == name || kind.ends_with(&format!("/{}", name)))
+	};
+
+	match object.get("@type") {
+		Some(Value::Array(kinds)) => kinds.iter().any(matches),
+		Some(kind) => matches(kind),
+		None => false,
+	}
+}
+
+fn to_recipe_json(recipe: &Map<String, Value>, url: &str) -> RecipeJSON {
+	let field = |name: &str| recipe.get(name).and_then(text).unwrap_or_default();
+	let joined = |name: &str| recipe.get(name).map(list).unwrap_or_default().join(", ");
+
+	let canonical_url = recipe
+		.get("url")
+		.and_then(Value::as_str)
+		.unwrap_or(url)
+		.to_string();
+
+	RecipeJSON {
+		author: joined("author"),
+		host: host(&canonical_url),
+		canonical_url,
+		category: joined("recipeCategory"),
+		cook_time: minutes(&field("cookTime")),
+		cuisine: joined("recipeCuisine"),
+		description: field("description"),
+		dietary_restrictions: recipe.get("suitableForDiet").map(list).unwrap_or_default(),
+		image: recipe.get("image").and_then(image).unwrap_or_default(),
+		ingredients: recipe
+			.get("recipeIngredient")
+			.or_else(|| recipe.get("ingredients"))
+			.map(list)
+			.unwrap_or_default(),
+		instructions_list: recipe
+			.get("recipeInstructions")
+			.map(instructions)
+			.unwrap_or_default(),
+		keywords: recipe.get("keywords").map(list).unwrap_or_default(),
+		prep_time: minutes(&field("prepTime")),
+		title: field("name"),
+		yields: field("recipeYield"),
+	}
+}
+
+fn text(value: &Value) -> Option<String> {
+	let cleaned = match value {
+		// site authors put HTML and entities into JSON-LD strings more often than not
+		Value::String(string) => html::clean(string),
+		Value::Number(number) => number.to_string(),
+		Value::Array(values) => return values.iter().find_map(text),
+		Value::Object(object) => {
+			return ["name", "text", "@value"]
+				.iter()
+				.find_map(|key| object.get(*key).and_then(text))
+		}
+		_ => return None,
+	};
+
+	Some(cleaned).filter(|cleaned| !cleaned.is_empty())
+}
+
+fn list(value: &Value) -> Vec<String> {
+	match value {
+		Value::Array(values) => values.iter().filter_map(text).collect(),
+		value => text(value).into_iter().collect(),
+	}
+}
+
+fn image(value: &Value) -> Option<String> {
+	match value {
+		Value::String(url) => Some(html::clean(url)),
+		Value::Array(values) => values.iter().find_map(image),
+		Value::Object(object) => object
+			.get("url")
+			.or_else(|| object.get("contentUrl"))
+			.and_then(image),
+		_ => None,
+	}
+}
+
+/// Flattens the many shapes `recipeInstructions` comes in: a block of text, a list of strings, or
+/// `HowToStep`s, optionally grouped into `HowToSection`s.
+fn instructions(value: &Value) -> Vec<String> {
+	match value {
+		Value::String(string) => string
+			.lines()
+			.map(html::clean)
+			.filter(|step| !step.is_empty())
+			.collect(),
+		Value::Array(values) => values.iter().flat_map(instructions).collect(),
+		Value::Object(object) if is_type(object, "HowToSection") => object
+			.get("itemListElement")
+			.map(instructions)
+			.unwrap_or_default(),
+		Value::Object(object) => object
+			.get("text")
+			.or_else(|| object.get("name"))
+			.and_then(text)
+			.into_iter()
+			.collect(),
+		_ => vec![],
+	}
+}
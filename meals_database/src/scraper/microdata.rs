@@ -0,0 +1,144 @@
+use scraper::{ElementRef, Html, Selector};
+use std::collections::HashMap;
+
+use super::{host, html, minutes, RecipeJSON};
+
+/// Reads a schema.org `Recipe` marked up with `itemscope`/`itemtype` attributes, which older sites
+/// use instead of JSON-LD.
+pub(crate) fn extract(document: &Html, url: &str) -> Option<RecipeJSON> {
+	let items = Selector::parse("[itemscope][itemtype]").unwrap();
+	let recipe = document.select(&items).find(|element| {
+		element
+			.attr("itemtype")
+			.is_some_and(|kind| kind.trim_end_matches('/').ends_with("schema.org/Recipe"))
+	})?;
+
+	let mut properties = HashMap::new();
+	collect_properties(recipe, &mut properties);
+
+	let first = |name: &str| {
+		properties
+			.get(name)
+			.and_then(|elements| elements.iter().map(|element| value(*element)).next())
+			.unwrap_or_default()
+	};
+
+	let all = |name: &str| {
+		properties
+			.get(name)
+			.map(|elements| {
+				elements
+					.iter()
+					.map(|element| value(*element))
+					.filter(|value| !value.is_empty())
+					.collect::<Vec<_>>()
+			})
+			.unwrap_or_default()
+	};
+
+	let canonical_url = Some(first("url"))
+		.filter(|canonical_url| !canonical_url.is_empty())
+		.unwrap_or_else(|| url.into());
+
+	let mut ingredients = all("recipeIngredient");
+	if ingredients.is_empty() {
+		ingredients = all("ingredients");
+	}
+
+	Some(RecipeJSON {
+		author: all("author").join(", "),
+		host: host(&canonical_url),
+		canonical_url,
+		category: all("recipeCategory").join(", "),
+		cook_time: minutes(&first("cookTime")),
+		cuisine: all("recipeCuisine").join(", "),
+		description: first("description"),
+		dietary_restrictions: all("suitableForDiet"),
+		image: first("image"),
+		ingredients,
+		instructions_list: properties
+			.get("recipeInstructions")
+			.map(|elements| {
+				elements
+					.iter()
+					.flat_map(|element| steps(*element))
+					.collect()
+			})
+			.unwrap_or_default(),
+		keywords: all("keywords"),
+		prep_time: minutes(&first("prepTime")),
+		title: first("name"),
+		yields: first("recipeYield"),
+	})
+}
+
+/// Collects the properties belonging to `item`. Properties inside a nested item belong to that item,
+/// so the search stops at any element that starts one.
+fn collect_properties<'a>(
+	item: ElementRef<'a>,
+	properties: &mut HashMap<&'a str, Vec<ElementRef<'a>>>,
+) {
+	for child in item.child_elements() {
+		if let Some(names) = child.attr("itemprop") {
+			for name in names.split_whitespace() {
+				properties.entry(name).or_default().push(child);
+			}
+		}
+
+		if child.attr("itemscope").is_none() {
+			collect_properties(child, properties);
+		}
+	}
+}
+
+fn value(element: ElementRef) -> String {
+	let attribute = match element.value().name() {
+		"meta" => "content",
+		"audio" | "embed" | "iframe" | "img" | "source" | "video" => "src",
+		"a" | "area" | "link" => "href",
+		"time" => "datetime",
+		"data" | "meter" => "value",
+		_ => "content",
+	};
+
+	match element.attr(attribute) {
+		Some(value) => value.trim().into(),
+		None => html::text(element),
+	}
+}
+
+/// Splits an instructions property into steps. It is either a single `HowToStep`, an element per
+/// step, or one element holding a list or paragraphs.
+fn steps(element: ElementRef) -> Vec<String> {
+	if element.attr("itemscope").is_some() {
+		let mut properties = HashMap::new();
+		collect_properties(element, &mut properties);
+
+		let text = properties
+			.get("text")
+			.and_then(|elements| elements.first())
+			.map(|text| value(*text))
+			.unwrap_or_else(|| html::text(element));
+
+		return vec![text];
+	}
+
+	for name in ["li", "p"] {
+		let steps = element
+			.descendent_elements()
+			.skip(1)
+			.filter(|descendant| descendant.value().name() == name)
+			.map(html::text)
+			.filter(|step| !step.is_empty())
+			.collect::<Vec<_>>();
+
+		if !steps.is_empty() {
+			return steps;
+		}
+	}
+
+	vec![value(element)]
+		.into_iter()
+		.filter(|step| !step.is_empty())
+		.collect()
+}
@@ -0,0 +1,174 @@
+use reqwest::header;
+use scraper::Html;
+use serde::{Deserialize, Serialize};
+
+use crate::{parse_ingredient, Diet, ParsedIngredient, RecipeStep, ScraperError};
+
+mod html;
+pub(crate) mod json_ld;
+mod microdata;
+
+/// Some recipe sites turn away requests that don't look like they came from a browser.
+const USER_AGENT: &str =
+	"Mozilla/5.0 (X11; Linux x86_64; rv:128.0) Gecko/20100101 Firefox/128.0 bansheelong2";
+
+/// A recipe as it appears on the page, before its ingredients are parsed.
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct RecipeJSON {
+	pub author: String,
+	pub canonical_url: String,
+	pub category: String,
+	/// In minutes.
+	#[serde(default)]
+	pub cook_time: Option<u32>,
+	pub cuisine: String,
+	pub description: String,
+	/// schema.org `RestrictedDiet`s, like "https://schema.org/VeganDiet".
+	#[serde(default)]
+	pub dietary_restrictions: Vec<String>,
+	pub host: String,
+	pub image: String,
+	pub ingredients: Vec<String>,
+	pub instructions_list: Vec<String>,
+	#[serde(default)]
+	pub keywords: Vec<String>,
+	/// In minutes.
+	#[serde(default)]
+	pub prep_time: Option<u32>,
+	pub title: String,
+	pub yields: String,
+}
+
+/// Downloads a recipe page and reads the recipe out of its schema.org metadata.
+pub async fn scrape_recipe(url: &str) -> Result<NormalizedRecipe, ScraperError> {
+	let html = reqwest::Client::new()
+		.get(url)
+		.header(header::USER_AGENT, USER_AGENT)
+		.send()
+		.await?
+		.error_for_status()?
+		.text()
+		.await?;
+
+	parse_recipe_html(&html, url)
+}
+
+pub fn parse_recipe_html(html: &str, url: &str) -> Result<NormalizedRecipe, ScraperError> {
+	Ok(normalize_recipe_json(extract_recipe_json(html, url)?))
+}
+
+/// Reads the first schema.org `Recipe` out of a page, preferring JSON-LD and falling back to
+/// microdata. `url` is where the page came from and is used if the recipe doesn't name its own.
+pub fn extract_recipe_json(html: &str, url: &str) -> Result<RecipeJSON, ScraperError> {
+	let document = Html::parse_document(html);
+
+	json_ld::extract(&document, url)
+		.or_else(|| microdata::extract(&document, url))
+		.ok_or(ScraperError::NoRecipe)
+}
+
+fn host(url: &str) -> String {
+	let url = url.split_once("://").map_or(url, |(_, rest)| rest);
+	let host = url.split(['/', '?', '#']).next().unwrap_or_default();
+
+	host.trim_start_matches("www.").into()
+}
+
+/// Reads an ISO 8601 duration like "PT1H30M" as minutes. Seconds are dropped.
+pub(crate) fn minutes(duration: &str) -> Option<u32> {
+	let duration = duration.trim().strip_prefix('P')?;
+
+	let mut minutes = 0;
+	let mut number = String::new();
+	let mut in_time = false;
+	for character in duration.chars() {
+		match character {
+			'T' => in_time = true,
+			'0'..='9' | '.' => number.push(character),
+			unit => {
+				let value = number.parse::<f32>().ok()?;
+				number.clear();
+
+				minutes += match (unit, in_time) {
+					('D', false) => value * 24.0 * 60.0,
+					('H', true) => value * 60.0,
+					('M', true) => value,
+					('S', true) => 0.0,
+					_ => return None,
+				} as u32;
+			}
+		}
+	}
+
+	Some(minutes).filter(|minutes| *minutes > 0)
+}
+
+pub fn cleanup_recipe_step(step: &str) -> String {
+	step.replace("\u{2022} ", "").trim().into()
+}
+
+pub fn normalize_recipe_json(json: RecipeJSON) -> NormalizedRecipe {
+	let mut ingredients = vec![];
+	for ingredient in json.ingredients.iter() {
+		let parsed_ingredient = parse_ingredient(ingredient);
+		if !parsed_ingredient.confident {
+			log::warn!("Could not fully parse ingredient '{}'", ingredient);
+		}
+
+		ingredients.push(parsed_ingredient);
+	}
+
+	let mut recipe = vec![];
+	for step in json.instructions_list.iter() {
+		recipe.push(RecipeStep {
+			description: cleanup_recipe_step(step),
+		});
+	}
+
+	let mut diets = json
+		.dietary_restrictions
+		.iter()
+		.filter_map(|diet| Diet::from_schema(diet))
+		.collect::<Vec<_>>();
+	diets.sort();
+	diets.dedup();
+
+	let mut tags: Vec<String> = vec![];
+	for keyword in json
+		.keywords
+		.iter()
+		.flat_map(|keywords| keywords.split(','))
+	{
+		let keyword = keyword.trim();
+		if !keyword.is_empty() && !tags.iter().any(|tag| tag.eq_ignore_ascii_case(keyword)) {
+			tags.push(keyword.into());
+		}
+	}
+
+	NormalizedRecipe {
+		cook_minutes: json.cook_time,
+		course: json.category,
+		cuisine: json.cuisine,
+		diets,
+		image: json.image,
+		ingredients,
+		name: json.title,
+		prep_minutes: json.prep_time,
+		recipe,
+		tags,
+	}
+}
+
+#[derive(Debug)]
+pub struct NormalizedRecipe {
+	pub cook_minutes: Option<u32>,
+	pub course: String,
+	pub cuisine: String,
+	pub diets: Vec<Diet>,
+	pub image: String,
+	pub ingredients: Vec<ParsedIngredient>,
+	pub name: String,
+	pub prep_minutes: Option<u32>,
+	pub recipe: Vec<RecipeStep>,
+	pub tags: Vec<String>,
+}
@@ -0,0 +1,214 @@
+use fuzzy_matcher::{skim::SkimMatcherV2, FuzzyMatcher};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::MealPlan;
+
+/// Which part of a recipe a search term is matched against. A term like "ingredient:chicken" only
+/// looks at ingredients, a plain term looks everywhere.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Field {
+	Ingredient,
+	Name,
+	Step,
+	Tag,
+}
+
+impl Field {
+	fn parse(name: &str) -> Option<Field> {
+		match name.to_lowercase().as_str() {
+			"ingredient" | "ingredients" => Some(Field::Ingredient),
+			"name" => Some(Field::Name),
+			"step" | "steps" => Some(Field::Step),
+			"tag" | "tags" => Some(Field::Tag),
+			_ => None,
+		}
+	}
+
+	/// Matches in names count the most, matches in steps the least.
+	fn weight(&self) -> i64 {
+		match self {
+			Field::Ingredient => 2,
+			Field::Name => 4,
+			Field::Step => 1,
+			Field::Tag => 3,
+		}
+	}
+}
+
+#[derive(Debug)]
+struct Term {
+	/// `None` searches every field.
+	field: Option<Field>,
+	text: String,
+}
+
+/// Splits a query into terms. Quotes keep spaces in a term, like `ingredient:"chicken thighs"`.
+/// Unknown fields, like the "12" in "12:30", are searched for as plain text.
+fn parse_query(query: &str) -> Vec<Term> {
+	let mut words = vec![];
+	let mut word = String::new();
+	let mut quoted = false;
+	for character in query.chars() {
+		match character {
+			'"' => quoted = !quoted,
+			character if character.is_whitespace() && !quoted => {
+				words.push(std::mem::take(&mut word));
+			}
+			character => word.push(character),
+		}
+	}
+	words.push(word);
+
+	words
+		.into_iter()
+		.filter(|word| !word.trim().is_empty())
+		.map(|word| {
+			let field = word
+				.split_once(':')
+				.filter(|(_, text)| !text.trim().is_empty())
+				.and_then(|(field, text)| Some((Field::parse(field)?, text)));
+
+			match field {
+				Some((field, text)) => Term {
+					field: Some(field),
+					text: text.trim().to_lowercase(),
+				},
+				None => Term {
+					field: None,
+					text: word.trim().to_lowercase(),
+				},
+			}
+		})
+		.collect()
+}
+
+/// A meal's searchable text. Everything but `name` is lowercased.
+struct IndexEntry {
+	id: Uuid,
+	ingredients: Vec<String>,
+	lowercase_name: String,
+	name: String,
+	/// Steps are matched word by word, since fuzzy matching a whole paragraph matches almost
+	/// anything.
+	step_words: Vec<String>,
+	tags: Vec<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SearchResult {
+	pub id: Uuid,
+	pub name: String,
+	/// Higher is a better match.
+	pub score: i64,
+}
+
+pub struct SearchIndex {
+	entries: Vec<IndexEntry>,
+	matcher: SkimMatcherV2,
+}
+
+impl SearchIndex {
+	fn best_match(&self, texts: &[String], term: &str) -> Option<i64> {
+		texts
+			.iter()
+			.filter_map(|text| self.matcher.fuzzy_match(text, term))
+			.max()
+	}
+
+	fn score_term(&self, entry: &IndexEntry, term: &Term) -> Option<i64> {
+		let fields = match term.field {
+			Some(field) => vec![field],
+			None => vec![Field::Name, Field::Tag, Field::Ingredient, Field::Step],
+		};
+
+		fields
+			.into_iter()
+			.filter_map(|field| {
+				let score = match field {
+					Field::Ingredient => self.best_match(&entry.ingredients, &term.text),
+					Field::Name => self.matcher.fuzzy_match(&entry.lowercase_name, &term.text),
+					Field::Step => self.best_match(&entry.step_words, &term.text),
+					Field::Tag => self.best_match(&entry.tags, &term.text),
+				};
+
+				score.map(|score| score * field.weight())
+			})
+			.max()
+	}
+
+	/// Meals matching every term in `query`, best matches first. An empty query matches every meal,
+	/// sorted by name.
+	pub fn search(&self, query: &str) -> Vec<SearchResult> {
+		let terms = parse_query(query);
+
+		let mut results = self
+			.entries
+			.iter()
+			.filter_map(|entry| {
+				let mut score = 0;
+				for term in terms.iter() {
+					score += self.score_term(entry, term)?;
+				}
+
+				Some(SearchResult {
+					id: entry.id,
+					name: entry.name.clone(),
+					score,
+				})
+			})
+			.collect::<Vec<_>>();
+
+		results.sort_by(|a, b| {
+			b.score
+				.cmp(&a.score)
+				.then(a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+		});
+
+		results
+	}
+}
+
+impl MealPlan {
+	pub fn search_index(&self) -> SearchIndex {
+		let entries = self
+			.all_meals
+			.values()
+			.map(|meal_info| IndexEntry {
+				id: meal_info.id,
+				ingredients: meal_info
+					.ingredients
+					.iter()
+					.map(|ingredient| self.canonical_name(&ingredient.name).to_lowercase())
+					.collect(),
+				lowercase_name: meal_info.name.to_lowercase(),
+				name: meal_info.name.clone(),
+				step_words: meal_info
+					.recipe
+					.iter()
+					.flat_map(|step| step.description.split_whitespace())
+					.map(|word| {
+						word.trim_matches(|character: char| !character.is_alphanumeric())
+							.to_lowercase()
+					})
+					.filter(|word| !word.is_empty())
+					.collect(),
+				tags: meal_info
+					.tags
+					.iter()
+					.map(|tag| tag.to_lowercase())
+					.collect(),
+			})
+			.collect();
+
+		SearchIndex {
+			entries,
+			matcher: SkimMatcherV2::default(),
+		}
+	}
+
+	/// Builds a throwaway index. Keep the index from `search_index` around to search it repeatedly.
+	pub fn search(&self, query: &str) -> Vec<SearchResult> {
+		self.search_index().search(query)
+	}
+}
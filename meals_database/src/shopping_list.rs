@@ -0,0 +1,147 @@
+use uuid::Uuid;
+
+use crate::{MealPlan, MealPlanError, ShoppingListInfo, ShoppingListItem};
+
+impl MealPlan {
+	pub fn shopping_list_by_id(
+		&self,
+		shopping_list_id: Uuid,
+	) -> Result<&ShoppingListInfo, MealPlanError> {
+		self.shopping_list
+			.iter()
+			.find(|shopping_list| shopping_list.id == shopping_list_id)
+			.ok_or(MealPlanError::ShoppingListNotFound { shopping_list_id })
+	}
+
+	pub(crate) fn shopping_list_by_id_mut(
+		&mut self,
+		shopping_list_id: Uuid,
+	) -> Result<&mut ShoppingListInfo, MealPlanError> {
+		self.shopping_list
+			.iter_mut()
+			.find(|shopping_list| shopping_list.id == shopping_list_id)
+			.ok_or(MealPlanError::ShoppingListNotFound { shopping_list_id })
+	}
+
+	pub(crate) fn shopping_list_item_mut(
+		&mut self,
+		shopping_list_id: Uuid,
+		id: Uuid,
+	) -> Result<&mut ShoppingListItem, MealPlanError> {
+		self.shopping_list_by_id_mut(shopping_list_id)?
+			.items
+			.iter_mut()
+			.find(|item| item.id == id)
+			.ok_or(MealPlanError::ShoppingListItemNotFound {
+				id,
+				shopping_list_id,
+			})
+	}
+
+	/// Adds an item to a shopping list. An item of the same name that's still needed is topped up
+	/// instead, if the amounts can be added together.
+	pub fn add_shopping_list_item(
+		&mut self,
+		shopping_list_id: Uuid,
+		mut item: ShoppingListItem,
+	) -> Result<(), MealPlanError> {
+		item.name = self.canonical_name(&item.name);
+
+		let density = self.density(&item.name);
+		let shopping_list = self.shopping_list_by_id_mut(shopping_list_id)?;
+		let total = shopping_list
+			.items
+			.iter_mut()
+			.filter(|existing| !existing.have && existing.name() == item.name())
+			.find_map(|existing| {
+				let total = existing.amount.add(&item.amount, density)?;
+				Some((existing, total))
+			});
+
+		match total {
+			Some((existing, total)) => existing.amount = total,
+			None => shopping_list.items.push(item),
+		}
+
+		self.sort_shopping_list(shopping_list_id)
+	}
+
+	/// Changes an item's name and amount. Whether it was bought is left alone.
+	pub fn edit_shopping_list_item(
+		&mut self,
+		shopping_list_id: Uuid,
+		item: ShoppingListItem,
+	) -> Result<(), MealPlanError> {
+		let name = self.canonical_name(&item.name);
+		let existing = self.shopping_list_item_mut(shopping_list_id, item.id)?;
+		existing.amount = item.amount;
+		existing.name = name;
+
+		self.sort_shopping_list(shopping_list_id)
+	}
+
+	pub fn remove_shopping_list_item(
+		&mut self,
+		shopping_list_id: Uuid,
+		id: Uuid,
+	) -> Result<ShoppingListItem, MealPlanError> {
+		let shopping_list = self.shopping_list_by_id_mut(shopping_list_id)?;
+		let Some(index) = shopping_list.items.iter().position(|item| item.id == id) else {
+			return Err(MealPlanError::ShoppingListItemNotFound {
+				id,
+				shopping_list_id,
+			});
+		};
+
+		Ok(shopping_list.items.remove(index))
+	}
+
+	pub fn rename_shopping_list(
+		&mut self,
+		shopping_list_id: Uuid,
+		name: &str,
+	) -> Result<(), MealPlanError> {
+		self.shopping_list_by_id_mut(shopping_list_id)?.name = name.trim().into();
+		Ok(())
+	}
+
+	/// Moves everything on one shopping list onto another and removes the emptied list.
+	pub fn merge_shopping_lists(
+		&mut self,
+		from_id: Uuid,
+		into_id: Uuid,
+	) -> Result<(), MealPlanError> {
+		self.shopping_list_by_id(into_id)?;
+		if from_id == into_id {
+			return Ok(());
+		}
+
+		let from = self.shopping_list_by_id(from_id)?.clone();
+		for item in from.items {
+			if item.have {
+				self.shopping_list_by_id_mut(into_id)?.items.push(item);
+			} else {
+				self.add_shopping_list_item(into_id, item)?;
+			}
+		}
+
+		let into = self.shopping_list_by_id_mut(into_id)?;
+		into.for_meals.extend(from.for_meals);
+		if into.name.is_empty() {
+			into.name = from.name;
+		}
+
+		self.shopping_list
+			.retain(|shopping_list| shopping_list.id != from_id);
+
+		self.sort_shopping_list(into_id)
+	}
+
+	fn sort_shopping_list(&mut self, shopping_list_id: Uuid) -> Result<(), MealPlanError> {
+		let mut items = std::mem::take(&mut self.shopping_list_by_id_mut(shopping_list_id)?.items);
+		items.sort_by_key(|item| (self.aisle(&item.name), item.name()));
+		self.shopping_list_by_id_mut(shopping_list_id)?.items = items;
+
+		Ok(())
+	}
+}
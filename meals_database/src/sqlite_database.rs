@@ -0,0 +1,157 @@
+use async_trait::async_trait;
+use rusqlite::{params, Connection, OptionalExtension, Transaction};
+use serde_json::{json, Value};
+use std::sync::{Mutex, RwLock, RwLockReadGuard};
+
+use crate::{Database, DatabaseError, MealInfo, MealPlan, MealPlanChange};
+
+const SCHEMA: &str = "
+	CREATE TABLE IF NOT EXISTS meals (
+		id TEXT PRIMARY KEY NOT NULL,
+		data TEXT NOT NULL
+	);
+
+	CREATE TABLE IF NOT EXISTS meal_plan (
+		id INTEGER PRIMARY KEY NOT NULL CHECK (id = 0),
+		data TEXT NOT NULL
+	);
+";
+
+/// Stores every recipe as its own row in `meals` and the rest of the meal plan as a single JSON
+/// document in `meal_plan`, so saving a recipe doesn't rewrite the whole recipe library.
+pub struct SqliteDatabase {
+	connection: Mutex<Connection>,
+	data: RwLock<MealPlan>,
+}
+
+impl SqliteDatabase {
+	pub fn open(path: &str) -> Result<Self, DatabaseError> {
+		let connection = Connection::open(path)?;
+		connection.execute_batch(SCHEMA)?;
+
+		let database = Self {
+			connection: Mutex::new(connection),
+			data: RwLock::new(MealPlan::default()),
+		};
+
+		database.read()?;
+
+		Ok(database)
+	}
+
+	fn read(&self) -> Result<(), DatabaseError> {
+		let connection = self.connection.lock().unwrap();
+
+		let data = connection
+			.query_row("SELECT data FROM meal_plan WHERE id = 0", [], |row| {
+				row.get::<_, String>(0)
+			})
+			.optional()?;
+
+		let mut meal_plan = match data {
+			Some(data) => {
+				let mut data: Value = serde_json::from_str(&data)?;
+				data["all_meals"] = json!({});
+				serde_json::from_value::<MealPlan>(data)?
+			}
+			None => MealPlan::default(),
+		};
+
+		let mut statement = connection.prepare("SELECT data FROM meals")?;
+		for data in statement.query_map([], |row| row.get::<_, String>(0))? {
+			let meal_info: MealInfo = serde_json::from_str(&data?)?;
+			meal_plan.all_meals.insert(meal_info.id, meal_info);
+		}
+
+		*self.data.write().unwrap() = meal_plan;
+
+		Ok(())
+	}
+
+	fn write_meal(transaction: &Transaction, meal_info: &MealInfo) -> Result<(), DatabaseError> {
+		transaction.execute(
+			"INSERT OR REPLACE INTO meals (id, data) VALUES (?1, ?2)",
+			params![meal_info.id.to_string(), serde_json::to_string(meal_info)?],
+		)?;
+
+		Ok(())
+	}
+
+	fn write_meal_plan(
+		transaction: &Transaction,
+		meal_plan: &MealPlan,
+	) -> Result<(), DatabaseError> {
+		let mut data = serde_json::to_value(meal_plan)?;
+		if let Value::Object(data) = &mut data {
+			data.remove("all_meals");
+		}
+
+		transaction.execute(
+			"INSERT OR REPLACE INTO meal_plan (id, data) VALUES (0, ?1)",
+			params![data.to_string()],
+		)?;
+
+		Ok(())
+	}
+}
+
+#[async_trait]
+impl Database for SqliteDatabase {
+	fn get(&self) -> RwLockReadGuard<'_, MealPlan> {
+		self.data.read().unwrap()
+	}
+
+	async fn load(&self) -> Result<(), DatabaseError> {
+		self.read()
+	}
+
+	/// Changes a copy of the meal plan and only swaps it in once the transaction has committed, so a
+	/// failed write leaves the meal plan and its revision as they were.
+	async fn apply(&self, change: MealPlanChange) -> Result<(), DatabaseError> {
+		let mut meal_plan = self.data.write().unwrap();
+
+		let mut new_meal_plan = meal_plan.clone();
+		new_meal_plan.apply(&change)?;
+		new_meal_plan.revision += 1;
+
+		let mut connection = self.connection.lock().unwrap();
+		let transaction = connection.transaction()?;
+
+		for id in change.meal_ids() {
+			match new_meal_plan.all_meals.get(&id) {
+				Some(meal_info) => Self::write_meal(&transaction, meal_info)?,
+				None => {
+					transaction
+						.execute("DELETE FROM meals WHERE id = ?1", params![id.to_string()])?;
+				}
+			}
+		}
+
+		Self::write_meal_plan(&transaction, &new_meal_plan)?;
+		transaction.commit()?;
+
+		*meal_plan = new_meal_plan;
+
+		Ok(())
+	}
+
+	async fn replace(&self, mut new_meal_plan: MealPlan) -> Result<(), DatabaseError> {
+		let mut meal_plan = self.data.write().unwrap();
+		new_meal_plan.revision = meal_plan.revision + 1;
+
+		let mut connection = self.connection.lock().unwrap();
+		let transaction = connection.transaction()?;
+
+		transaction.execute("DELETE FROM meals", [])?;
+		for meal_info in new_meal_plan.all_meals.values() {
+			Self::write_meal(&transaction, meal_info)?;
+		}
+
+		Self::write_meal_plan(&transaction, &new_meal_plan)?;
+		transaction.commit()?;
+
+		*meal_plan = new_meal_plan;
+
+		Ok(())
+	}
+}
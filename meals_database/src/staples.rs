@@ -0,0 +1,185 @@
+use chrono::{Days, Months, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+use crate::{Amount, MealPlan, MealPlanError, ShoppingListInfo, ShoppingListItem};
+
+/// How often a staple goes on the shopping list.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum Frequency {
+	EveryTrip,
+	#[default]
+	Weekly,
+	EveryOtherWeek,
+	Monthly,
+}
+
+impl Frequency {
+	pub const ALL: [Frequency; 4] = [
+		Frequency::EveryTrip,
+		Frequency::Weekly,
+		Frequency::EveryOtherWeek,
+		Frequency::Monthly,
+	];
+
+	/// The first day the staple is needed again after it was last put on a shopping list.
+	pub fn next_due(&self, last_added: NaiveDate) -> NaiveDate {
+		match self {
+			Frequency::EveryTrip => last_added,
+			Frequency::Weekly => last_added + Days::new(7),
+			Frequency::EveryOtherWeek => last_added + Days::new(14),
+			Frequency::Monthly => last_added + Months::new(1),
+		}
+	}
+}
+
+impl TryFrom<&str> for Frequency {
+	type Error = ();
+
+	fn try_from(value: &str) -> Result<Self, Self::Error> {
+		Frequency::ALL
+			.into_iter()
+			.find(|frequency| frequency.to_string().eq_ignore_ascii_case(value.trim()))
+			.ok_or(())
+	}
+}
+
+impl Display for Frequency {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Frequency::EveryTrip => f.write_str("Every trip"),
+			Frequency::Weekly => f.write_str("Weekly"),
+			Frequency::EveryOtherWeek => f.write_str("Every other week"),
+			Frequency::Monthly => f.write_str("Monthly"),
+		}
+	}
+}
+
+/// Something bought on a schedule rather than for a recipe, like milk or paper towels.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct Staple {
+	pub amount: Amount,
+	pub frequency: Frequency,
+	/// The day the staple was last put on a generated shopping list.
+	#[serde(default)]
+	pub last_added: Option<NaiveDate>,
+	pub name: String,
+}
+
+impl Staple {
+	pub fn is_due(&self, today: NaiveDate) -> bool {
+		match self.last_added {
+			Some(last_added) => self.frequency.next_due(last_added) <= today,
+			None => true,
+		}
+	}
+}
+
+impl MealPlan {
+	fn staple_key(&self, name: &str) -> String {
+		self.canonical_name(name).to_lowercase()
+	}
+
+	pub fn staple(&self, name: &str) -> Option<&Staple> {
+		self.staples.get(&self.staple_key(name))
+	}
+
+	/// Adds or replaces a staple under its catalog name. Saving an existing staple keeps track of when
+	/// it was last bought.
+	pub fn save_staple(&mut self, mut staple: Staple) {
+		staple.name = self.canonical_name(&staple.name);
+
+		let key = self.staple_key(&staple.name);
+		if staple.last_added.is_none() {
+			staple.last_added = self.staples.get(&key).and_then(|staple| staple.last_added);
+		}
+
+		self.staples.insert(key, staple);
+	}
+
+	pub fn remove_staple(&mut self, name: &str) -> Result<Staple, MealPlanError> {
+		self.staples
+			.remove(&self.staple_key(name))
+			.ok_or_else(|| MealPlanError::StapleNotFound { name: name.into() })
+	}
+
+	pub fn due_staples(&self, today: NaiveDate) -> Vec<&Staple> {
+		self.staples
+			.values()
+			.filter(|staple| staple.is_due(today))
+			.collect()
+	}
+
+	/// Adds a free-form item to the next generated shopping list, on top of any of the same name that's
+	/// already waiting.
+	pub fn add_extra_item(&mut self, mut item: ShoppingListItem) {
+		item.name = self.canonical_name(&item.name);
+		item.have = false;
+
+		let density = self.density(&item.name);
+		let Some(existing) = self
+			.extra_items
+			.iter_mut()
+			.find(|existing| existing.name() == item.name())
+		else {
+			self.extra_items.push(item);
+			return;
+		};
+
+		match existing.amount.add(&item.amount, density) {
+			Some(amount) => existing.amount = amount,
+			None => log::warn!(
+				"Cannot add {} {} to {} {} of '{}'",
+				item.amount.value,
+				item.amount.units,
+				existing.amount.value,
+				existing.amount.units,
+				item.name
+			),
+		}
+	}
+
+	pub fn remove_extra_item(&mut self, name: &str) -> Result<ShoppingListItem, MealPlanError> {
+		let name = self.canonical_name(name).to_lowercase();
+		let Some(index) = self.extra_items.iter().position(|item| item.name() == name) else {
+			return Err(MealPlanError::ExtraItemNotFound { name });
+		};
+
+		Ok(self.extra_items.remove(index))
+	}
+
+	/// Adds a shopping list. A list generated from the whole meal plan already has the due staples
+	/// and its extra items on it, so the staples are marked as added and those extra items are taken
+	/// off. Extra items added since the list was made, possibly by someone else, stay for the next one.
+	pub fn add_shopping_list(&mut self, shopping_list: ShoppingListInfo) {
+		if let Some(created) = shopping_list.created {
+			for staple in self.staples.values_mut() {
+				if staple.is_due(created) {
+					staple.last_added = Some(created);
+				}
+			}
+
+			for taken in shopping_list.extra_items.iter() {
+				self.take_extra_item(taken);
+			}
+		}
+
+		self.shopping_list.push(shopping_list);
+	}
+
+	/// Takes what a shopping list took in off an extra item. Anything added to it since stays.
+	fn take_extra_item(&mut self, taken: &ShoppingListItem) {
+		let density = self.density(&taken.name);
+		let Some(index) = self.extra_items.iter().position(|item| item.id == taken.id) else {
+			return;
+		};
+
+		let item = &mut self.extra_items[index];
+		match item.amount.subtract(&taken.amount, density) {
+			Some(amount) if amount.value > f32::EPSILON => item.amount = amount,
+			_ => {
+				self.extra_items.remove(index);
+			}
+		}
+	}
+}
@@ -0,0 +1,156 @@
+use chrono::{Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use uuid::Uuid;
+
+use crate::{Ingredient, MealPlan, ShoppingListItem};
+
+/// How often a recipe was cooked.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct MealStatistics {
+	pub id: Uuid,
+	pub last_cooked: Option<NaiveDate>,
+	pub name: String,
+	pub times_cooked: usize,
+}
+
+/// What was cooked between two dates, built from the completed meals.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct Statistics {
+	/// Days in a row something was cooked, up to the end of the range. Today doesn't break the
+	/// streak if nothing has been cooked yet.
+	pub current_streak: usize,
+	pub from: Option<NaiveDate>,
+	/// Everything the cooked meals used at their recipe's serving size, sorted by name.
+	pub ingredients: Vec<Ingredient>,
+	pub longest_streak: usize,
+	/// Every recipe, most cooked first. Recipes that weren't cooked are at the end.
+	pub meals: Vec<MealStatistics>,
+	pub to: Option<NaiveDate>,
+	pub total_cooked: usize,
+}
+
+impl Statistics {
+	pub fn most_cooked(&self, count: usize) -> impl Iterator<Item = &MealStatistics> {
+		self.meals
+			.iter()
+			.filter(|meal| meal.times_cooked > 0)
+			.take(count)
+	}
+
+	/// Includes recipes that were never cooked.
+	pub fn least_cooked(&self, count: usize) -> impl Iterator<Item = &MealStatistics> {
+		self.meals.iter().rev().take(count)
+	}
+}
+
+impl MealPlan {
+	/// Statistics for the completed meals between `from` and `to`, both inclusive. Leaving either
+	/// out doesn't bound the range on that side.
+	pub fn statistics(&self, from: Option<NaiveDate>, to: Option<NaiveDate>) -> Statistics {
+		let mut meals = self
+			.all_meals
+			.values()
+			.map(|meal_info| {
+				(
+					meal_info.id,
+					MealStatistics {
+						id: meal_info.id,
+						last_cooked: None,
+						name: meal_info.name.clone(),
+						times_cooked: 0,
+					},
+				)
+			})
+			.collect::<HashMap<Uuid, MealStatistics>>();
+
+		let mut ingredients: HashMap<String, ShoppingListItem> = HashMap::new();
+		let mut days = BTreeSet::new();
+		let mut total_cooked = 0;
+
+		for (date, ids) in self.completed_meals.iter() {
+			if from.is_some_and(|from| *date < from) || to.is_some_and(|to| *date > to) {
+				continue;
+			}
+
+			if !ids.is_empty() {
+				days.insert(*date);
+			}
+
+			for id in ids {
+				total_cooked += 1;
+
+				// recipes deleted since still count towards the streaks
+				let Some(meal_info) = self.all_meals.get(id) else {
+					continue;
+				};
+
+				let meal = meals.get_mut(id).unwrap();
+				meal.times_cooked += 1;
+				meal.last_cooked = meal.last_cooked.max(Some(*date));
+
+				for ingredient in meal_info.ingredients_for(&meal_info.new_stub(*date)) {
+					self.add_to_shopping_list(
+						&mut ingredients,
+						&ingredient.name,
+						&ingredient.amount,
+					);
+				}
+			}
+		}
+
+		let mut meals = meals.into_values().collect::<Vec<_>>();
+		meals.sort_by(|a, b| {
+			b.times_cooked
+				.cmp(&a.times_cooked)
+				.then(b.last_cooked.cmp(&a.last_cooked))
+				.then(a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+		});
+
+		let mut ingredients = ingredients
+			.into_values()
+			.map(|item| Ingredient {
+				amount: item.amount,
+				name: item.name,
+			})
+			.collect::<Vec<_>>();
+		ingredients.sort_by_key(|ingredient| ingredient.name.to_lowercase());
+
+		let mut longest_streak = 0;
+		let mut streak = 0;
+		let mut previous: Option<NaiveDate> = None;
+		for date in days.iter() {
+			if previous.and_then(|previous| previous.succ_opt()) == Some(*date) {
+				streak += 1;
+			} else {
+				streak = 1;
+			}
+
+			longest_streak = longest_streak.max(streak);
+			previous = Some(*date);
+		}
+
+		let end = to.unwrap_or_else(|| Local::now().date_naive());
+		let mut day = if days.contains(&end) {
+			Some(end)
+		} else {
+			end.pred_opt()
+		};
+
+		let mut current_streak = 0;
+		while let Some(date) = day.filter(|date| days.contains(date)) {
+			current_streak += 1;
+			day = date.pred_opt();
+		}
+
+		Statistics {
+			current_streak,
+			from,
+			ingredients,
+			longest_streak,
+			meals,
+			to,
+			total_cooked,
+		}
+	}
+}
@@ -0,0 +1,134 @@
+use chrono::{Datelike, Days, NaiveDate};
+use rand::{seq::SliceRandom, Rng};
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+use crate::{MealFilter, MealInfo, MealPlan};
+
+/// Meals cooked this many days ago or longer are as likely to come up as ones never cooked.
+const FORGOTTEN_AFTER_DAYS: i64 = 60;
+
+#[derive(Clone, Debug, Default)]
+pub struct SuggestionOptions {
+	/// Meals that were already suggested, so re-rolling doesn't show them again.
+	pub exclude: HashSet<Uuid>,
+	/// Only meals matching this are suggested.
+	pub filter: MealFilter,
+	/// Makes meals that use ingredients already on a shopping list more likely.
+	pub prefer_shopping_list: bool,
+}
+
+impl MealPlan {
+	fn last_cooked(&self) -> HashMap<Uuid, NaiveDate> {
+		let mut last_cooked: HashMap<Uuid, NaiveDate> = HashMap::new();
+		for (date, ids) in self.completed_meals.iter() {
+			for id in ids {
+				let last = last_cooked.entry(*id).or_insert(*date);
+				*last = (*last).max(*date);
+			}
+		}
+
+		last_cooked
+	}
+
+	/// The meals planned in the Monday to Sunday week `date` falls in.
+	fn planned_in_week(&self, date: NaiveDate) -> HashSet<Uuid> {
+		let monday = date - Days::new(date.weekday().num_days_from_monday() as u64);
+
+		monday
+			.iter_days()
+			.take(7)
+			.filter_map(|day| self.planned_meals.get(&day))
+			.flatten()
+			.map(|meal_stub| meal_stub.id)
+			.collect()
+	}
+
+	fn shopping_list_names(&self) -> HashSet<String> {
+		self.shopping_list
+			.iter()
+			.flat_map(|shopping_list| shopping_list.items.iter())
+			.map(|item| self.canonical_name(&item.name).to_lowercase())
+			.collect()
+	}
+
+	/// How likely each meal matching the filter is to be suggested for `date`. Meals that were
+	/// excluded or are already planned that week get no weight, the rest get more the longer ago
+	/// they were cooked.
+	pub fn suggestion_weights(
+		&self,
+		date: NaiveDate,
+		options: &SuggestionOptions,
+	) -> Vec<(&MealInfo, f32)> {
+		let last_cooked = self.last_cooked();
+		let planned = self.planned_in_week(date);
+		let shopping_list_names = if options.prefer_shopping_list {
+			self.shopping_list_names()
+		} else {
+			HashSet::new()
+		};
+
+		let mut weights = self
+			.all_meals
+			.values()
+			.filter(|meal_info| options.filter.matches(meal_info))
+			.map(|meal_info| {
+				if options.exclude.contains(&meal_info.id) || planned.contains(&meal_info.id) {
+					return (meal_info, 0.0);
+				}
+
+				let days = match last_cooked.get(&meal_info.id) {
+					Some(last_cooked) => (date - *last_cooked)
+						.num_days()
+						.clamp(1, FORGOTTEN_AFTER_DAYS),
+					None => FORGOTTEN_AFTER_DAYS,
+				};
+
+				let mut weight = days as f32;
+
+				// up to twice as likely when every ingredient is already being bought
+				if !shopping_list_names.is_empty() && !meal_info.ingredients.is_empty() {
+					let shared = meal_info
+						.ingredients
+						.iter()
+						.filter(|ingredient| {
+							shopping_list_names
+								.contains(&self.canonical_name(&ingredient.name).to_lowercase())
+						})
+						.count();
+
+					weight *= 1.0 + shared as f32 / meal_info.ingredients.len() as f32;
+				}
+
+				(meal_info, weight)
+			})
+			.collect::<Vec<_>>();
+
+		// keeps the order stable so the same rng picks the same meal
+		weights.sort_by_key(|(meal_info, _)| meal_info.id);
+
+		weights
+	}
+
+	/// Picks a meal for `date` using `suggestion_weights`. When every meal that isn't excluded is
+	/// planned that week, one of them is picked anyway. Returns `None` once everything is excluded.
+	pub fn suggest_meal<R: Rng + ?Sized>(
+		&self,
+		date: NaiveDate,
+		options: &SuggestionOptions,
+		rng: &mut R,
+	) -> Option<&MealInfo> {
+		let weights = self.suggestion_weights(date, options);
+
+		if let Ok((meal_info, _)) = weights.choose_weighted(rng, |(_, weight)| *weight) {
+			return Some(meal_info);
+		}
+
+		weights
+			.iter()
+			.filter(|(meal_info, _)| !options.exclude.contains(&meal_info.id))
+			.collect::<Vec<_>>()
+			.choose(rng)
+			.map(|(meal_info, _)| *meal_info)
+	}
+}
@@ -0,0 +1,180 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+use crate::{MealInfo, MealPlan};
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+pub enum Diet {
+	DairyFree,
+	GlutenFree,
+	Vegan,
+	Vegetarian,
+}
+
+impl Diet {
+	pub const ALL: [Diet; 4] = [
+		Diet::DairyFree,
+		Diet::GlutenFree,
+		Diet::Vegan,
+		Diet::Vegetarian,
+	];
+
+	/// Reads a schema.org `RestrictedDiet`, like "https://schema.org/GlutenFreeDiet". Diets the
+	/// meal plan doesn't track are ignored.
+	pub fn from_schema(value: &str) -> Option<Diet> {
+		let name = value.trim().trim_end_matches('/');
+		let name = name.rsplit('/').next().unwrap_or(name);
+
+		match name {
+			"GlutenFreeDiet" => Some(Diet::GlutenFree),
+			"LowLactoseDiet" => Some(Diet::DairyFree),
+			"VeganDiet" => Some(Diet::Vegan),
+			"VegetarianDiet" => Some(Diet::Vegetarian),
+			_ => None,
+		}
+	}
+
+	/// The schema.org `RestrictedDiet` closest to the diet, the reverse of `from_schema`.
+	pub fn schema(&self) -> &'static str {
+		match self {
+			Diet::DairyFree => "https://schema.org/LowLactoseDiet",
+			Diet::GlutenFree => "https://schema.org/GlutenFreeDiet",
+			Diet::Vegan => "https://schema.org/VeganDiet",
+			Diet::Vegetarian => "https://schema.org/VegetarianDiet",
+		}
+	}
+}
+
+impl TryFrom<&str> for Diet {
+	type Error = ();
+
+	fn try_from(value: &str) -> Result<Self, Self::Error> {
+		Diet::ALL
+			.into_iter()
+			.find(|diet| diet.to_string().eq_ignore_ascii_case(value.trim()))
+			.ok_or(())
+	}
+}
+
+impl Display for Diet {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Diet::DairyFree => f.write_str("Dairy-free"),
+			Diet::GlutenFree => f.write_str("Gluten-free"),
+			Diet::Vegan => f.write_str("Vegan"),
+			Diet::Vegetarian => f.write_str("Vegetarian"),
+		}
+	}
+}
+
+/// Splits a comma separated field like "Dinner, Main Course" into its parts.
+fn parts(value: &str) -> impl Iterator<Item = &str> {
+	value
+		.split(',')
+		.map(str::trim)
+		.filter(|part| !part.is_empty())
+}
+
+impl MealInfo {
+	/// Vegan meals are also vegetarian and dairy-free.
+	pub fn is(&self, diet: Diet) -> bool {
+		self.diets.iter().any(|meal_diet| {
+			*meal_diet == diet
+				|| (*meal_diet == Diet::Vegan && matches!(diet, Diet::Vegetarian | Diet::DairyFree))
+		})
+	}
+
+	pub fn has_tag(&self, tag: &str) -> bool {
+		self.tags
+			.iter()
+			.any(|meal_tag| meal_tag.eq_ignore_ascii_case(tag.trim()))
+	}
+
+	pub fn total_minutes(&self) -> Option<u32> {
+		match (self.prep_minutes, self.cook_minutes) {
+			(None, None) => None,
+			(prep, cook) => Some(prep.unwrap_or(0) + cook.unwrap_or(0)),
+		}
+	}
+}
+
+/// Narrows down which meals are shown or suggested. Empty fields match every meal.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct MealFilter {
+	pub course: Option<String>,
+	pub cuisine: Option<String>,
+	/// Meals have to be all of these.
+	pub diets: Vec<Diet>,
+	/// Meals without any times are left out once this is set.
+	pub max_minutes: Option<u32>,
+	pub tag: Option<String>,
+}
+
+impl MealFilter {
+	pub fn matches(&self, meal_info: &MealInfo) -> bool {
+		let matches_part = |value: &str, wanted: &Option<String>| match wanted {
+			Some(wanted) => parts(value).any(|part| part.eq_ignore_ascii_case(wanted.trim())),
+			None => true,
+		};
+
+		let quick_enough = match self.max_minutes {
+			Some(max_minutes) => meal_info
+				.total_minutes()
+				.is_some_and(|minutes| minutes <= max_minutes),
+			None => true,
+		};
+
+		let tagged = match &self.tag {
+			Some(tag) => meal_info.has_tag(tag),
+			None => true,
+		};
+
+		matches_part(&meal_info.course, &self.course)
+			&& matches_part(&meal_info.cuisine, &self.cuisine)
+			&& self.diets.iter().all(|diet| meal_info.is(*diet))
+			&& quick_enough
+			&& tagged
+	}
+}
+
+impl MealPlan {
+	/// The meals `filter` matches, sorted by name.
+	pub fn filter_meals(&self, filter: &MealFilter) -> Vec<&MealInfo> {
+		let mut meals = self
+			.all_meals
+			.values()
+			.filter(|meal_info| filter.matches(meal_info))
+			.collect::<Vec<_>>();
+		meals.sort_by_key(|meal_info| meal_info.name.to_lowercase());
+
+		meals
+	}
+
+	fn distinct<'a>(&'a self, values: impl Fn(&'a MealInfo) -> Vec<&'a str>) -> Vec<String> {
+		let mut distinct: Vec<String> = vec![];
+		for value in self.all_meals.values().flat_map(values) {
+			if !distinct
+				.iter()
+				.any(|other| other.eq_ignore_ascii_case(value))
+			{
+				distinct.push(value.into());
+			}
+		}
+
+		distinct.sort_by_key(|value| value.to_lowercase());
+		distinct
+	}
+
+	/// Every course used by a meal, for offering as filters.
+	pub fn courses(&self) -> Vec<String> {
+		self.distinct(|meal_info| parts(&meal_info.course).collect())
+	}
+
+	pub fn cuisines(&self) -> Vec<String> {
+		self.distinct(|meal_info| parts(&meal_info.cuisine).collect())
+	}
+
+	pub fn tags(&self) -> Vec<String> {
+		self.distinct(|meal_info| meal_info.tags.iter().map(String::as_str).collect())
+	}
+}
@@ -1,4 +1,4 @@
-use chrono::NaiveDate;
+use chrono::{NaiveDate, TimeDelta};
 use serde::{Deserialize, Serialize};
 use std::{
 	collections::{HashMap, HashSet},
@@ -6,11 +6,38 @@ use std::{
 };
 use uuid::Uuid;
 
+use crate::{
+	CatalogEntry, Diet, MealPlanChange, MealPlanError, NutritionEntry, PantryItem, Staple,
+};
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub enum MealPlanMessage {
+	Changed {
+		/// Boxed since a change can be much bigger than the rest of the message.
+		change: Box<MealPlanChange>,
+		revision: u64,
+	},
 	Update,
 }
 
+/// Densities in grams per milliliter, used to combine volumes and weights of the same ingredient.
+/// The meal plan's own densities take precedence.
+const DEFAULT_DENSITIES: &[(&str, f32)] = &[
+	("brown sugar", 0.93),
+	("butter", 0.96),
+	("cocoa powder", 0.42),
+	("flour", 0.53),
+	("honey", 1.42),
+	("milk", 1.03),
+	("oats", 0.38),
+	("oil", 0.92),
+	("powdered sugar", 0.51),
+	("rice", 0.78),
+	("salt", 1.2),
+	("sugar", 0.85),
+	("water", 1.0),
+];
+
 #[derive(Clone, Debug, Default, Deserialize, Eq, Ord, PartialEq, PartialOrd, Serialize)]
 pub enum Units {
 	#[default]
@@ -20,6 +47,14 @@ pub enum Units {
 	Tablespoon = 3,
 	Teaspoon = 4,
 	Milliliters = 5,
+	FluidOunces = 6,
+	Grams = 7,
+	Kilograms = 8,
+	Liters = 9,
+	Pinches = 10,
+	Pints = 11,
+	Pounds = 12,
+	Quarts = 13,
 }
 
 impl TryFrom<&str> for Units {
@@ -33,6 +68,14 @@ impl TryFrom<&str> for Units {
 			"thumb" | "tablespoon" | "tablespoons" => Ok(Units::Tablespoon),
 			"clove" | "teaspoon" | "teaspoons" => Ok(Units::Teaspoon),
 			"milliliters" => Ok(Units::Milliliters),
+			"fluid ounce" | "fluid ounces" => Ok(Units::FluidOunces),
+			"gram" | "grams" => Ok(Units::Grams),
+			"kilogram" | "kilograms" => Ok(Units::Kilograms),
+			"liter" | "liters" => Ok(Units::Liters),
+			"pinch" | "pinches" => Ok(Units::Pinches),
+			"pint" | "pints" => Ok(Units::Pints),
+			"pound" | "pounds" => Ok(Units::Pounds),
+			"quart" | "quarts" => Ok(Units::Quarts),
 			_ => Err(()),
 		}
 	}
@@ -47,34 +90,62 @@ impl Display for Units {
 			Units::Tablespoon => f.write_str("tbsp"),
 			Units::Teaspoon => f.write_str("tsp"),
 			Units::Milliliters => f.write_str("ml"),
+			Units::FluidOunces => f.write_str("fl oz"),
+			Units::Grams => f.write_str("g"),
+			Units::Kilograms => f.write_str("kg"),
+			Units::Liters => f.write_str("l"),
+			Units::Pinches => f.write_str("pinch"),
+			Units::Pints => f.write_str("pt"),
+			Units::Pounds => f.write_str("lb"),
+			Units::Quarts => f.write_str("qt"),
 		}
 	}
 }
 
 impl Units {
 	pub fn is_compatible(&self, other: &Units) -> bool {
-		if self == other {
-			return true;
-		}
+		self == other || self.is_volume() && other.is_volume() || self.is_mass() && other.is_mass()
+	}
 
+	pub fn is_volume(&self) -> bool {
 		match self {
-			Units::Count => false,
-			Units::Cup => other.is_volume(),
-			Units::Ounce => false,
-			Units::Tablespoon => other.is_volume(),
-			Units::Teaspoon => other.is_volume(),
-			Units::Milliliters => other.is_volume(),
+			Units::Cup
+			| Units::FluidOunces
+			| Units::Liters
+			| Units::Milliliters
+			| Units::Pinches
+			| Units::Pints
+			| Units::Quarts
+			| Units::Tablespoon
+			| Units::Teaspoon => true,
+			Units::Count | Units::Grams | Units::Kilograms | Units::Ounce | Units::Pounds => false,
 		}
 	}
 
-	pub fn is_volume(&self) -> bool {
+	pub fn is_mass(&self) -> bool {
+		matches!(
+			self,
+			Units::Grams | Units::Kilograms | Units::Ounce | Units::Pounds
+		)
+	}
+
+	/// The size of the unit in milliliters for volumes and grams for masses.
+	fn base_size(&self) -> f32 {
 		match self {
-			Units::Count => return false,
-			Units::Cup => return true,
-			Units::Ounce => return false,
-			Units::Tablespoon => return true,
-			Units::Teaspoon => return true,
-			Units::Milliliters => return true,
+			Units::Count => 1.0,
+			Units::Cup => 236.588,
+			Units::FluidOunces => 29.5735,
+			Units::Grams => 1.0,
+			Units::Kilograms => 1000.0,
+			Units::Liters => 1000.0,
+			Units::Milliliters => 1.0,
+			Units::Ounce => 28.3495,
+			Units::Pinches => 0.308,
+			Units::Pints => 473.176,
+			Units::Pounds => 453.592,
+			Units::Quarts => 946.353,
+			Units::Tablespoon => 14.7868,
+			Units::Teaspoon => 4.92892,
 		}
 	}
 
@@ -83,73 +154,36 @@ impl Units {
 			return None;
 		}
 
-		let result = match self {
-			Units::Count => false,
-			Units::Cup => match other {
-				Units::Tablespoon | Units::Teaspoon | Units::Milliliters => true,
-				_ => false,
-			},
-			Units::Ounce => false,
-			Units::Tablespoon => match other {
-				Units::Teaspoon | Units::Milliliters => true,
-				_ => false,
-			},
-			Units::Teaspoon => match other {
-				Units::Milliliters => true,
-				_ => false,
-			},
-			Units::Milliliters => false,
-		};
-
-		Some(result)
+		Some(self.base_size() > other.base_size())
 	}
 
+	/// What one of `other` is in this unit.
 	pub fn conversion_factor(&self, other: &Units) -> Option<f32> {
 		if !self.is_compatible(other) {
 			return None;
 		}
 
-		if self == other {
-			return Some(1.0);
-		}
-
-		let left = if self.is_bigger(other).unwrap() {
-			self
-		} else {
-			other
-		};
-
-		let right = if self.is_bigger(other).unwrap() {
-			other
-		} else {
-			self
-		};
+		Some(other.base_size() / self.base_size())
+	}
 
-		let conversion = match left {
-			Units::Count => unreachable!(),
-			Units::Cup => match right {
-				Units::Tablespoon => 16.0,
-				Units::Teaspoon => 48.0,
-				Units::Milliliters => 236.588,
-				_ => unreachable!(),
-			},
-			Units::Ounce => unreachable!(),
-			Units::Tablespoon => match right {
-				Units::Teaspoon => 3.0,
-				Units::Milliliters => 14.7868,
-				_ => unreachable!(),
-			},
-			Units::Teaspoon => match right {
-				Units::Milliliters => 4.92892,
-				_ => unreachable!(),
-			},
-			Units::Milliliters => unreachable!(),
-		};
+	/// Like `conversion_factor`, but also converts between volume and mass given the ingredient's
+	/// density in grams per milliliter.
+	pub fn conversion_factor_with_density(
+		&self,
+		other: &Units,
+		density: Option<f32>,
+	) -> Option<f32> {
+		if let Some(conversion_factor) = self.conversion_factor(other) {
+			return Some(conversion_factor);
+		}
 
-		if self.is_bigger(other).unwrap() {
-			Some(1.0 / conversion)
+		let density = density?;
+		if self.is_mass() && other.is_volume() {
+			Some(other.base_size() * density / self.base_size())
+		} else if self.is_volume() && other.is_mass() {
+			Some(other.base_size() / density / self.base_size())
 		} else {
-			Some(conversion)
+			None
 		}
 	}
 }
@@ -168,9 +202,18 @@ impl Amount {
 		}
 	}
 
-	pub fn add(&self, other: &Amount) -> Option<Amount> {
+	/// Adds two amounts, in the bigger of the two units. Volumes and weights can only be added if
+	/// the ingredient's density is known, and the result is in the units of `self`.
+	pub fn add(&self, other: &Amount, density: Option<f32>) -> Option<Amount> {
 		if !self.units.is_compatible(&other.units) {
-			return None;
+			let conversion_factor = self
+				.units
+				.conversion_factor_with_density(&other.units, density)?;
+
+			return Some(Amount {
+				units: self.units.clone(),
+				value: self.value + other.value * conversion_factor,
+			});
 		}
 
 		let left = if self.units.is_bigger(&other.units).unwrap() {
@@ -190,6 +233,63 @@ impl Amount {
 			value: left.value + right.value * left.units.conversion_factor(&right.units).unwrap(),
 		})
 	}
+
+	/// Takes `other` away from the amount, keeping the units of `self`. Like `add`, volumes and weights
+	/// need the ingredient's density. The result can be negative.
+	pub fn subtract(&self, other: &Amount, density: Option<f32>) -> Option<Amount> {
+		let conversion_factor = self
+			.units
+			.conversion_factor_with_density(&other.units, density)?;
+
+		Some(Amount {
+			units: self.units.clone(),
+			value: self.value - other.value * conversion_factor,
+		})
+	}
+
+	/// Multiplies the amount, then moves it into whichever unit reads best at its new size.
+	pub fn scale(&self, scale_factor: f32) -> Amount {
+		Amount {
+			units: self.units.clone(),
+			value: self.value * scale_factor,
+		}
+		.promoted()
+	}
+
+	/// Moves the amount into the unit a cook would use for it, e.g. 48 teaspoons becomes 1 cup and
+	/// 1500 grams becomes 1.5 kilograms. Only units of the same family are considered, so metric stays
+	/// metric and spoons stay spoons.
+	pub fn promoted(&self) -> Amount {
+		// (unit, the smallest amount of it worth writing down), biggest unit first
+		let family: &[(Units, f32)] = match self.units {
+			Units::Cup | Units::Tablespoon | Units::Teaspoon => &[
+				(Units::Cup, 0.25),
+				(Units::Tablespoon, 1.0),
+				(Units::Teaspoon, 0.0),
+			],
+			Units::Kilograms | Units::Grams => &[(Units::Kilograms, 1.0), (Units::Grams, 0.0)],
+			Units::Liters | Units::Milliliters => {
+				&[(Units::Liters, 1.0), (Units::Milliliters, 0.0)]
+			}
+			Units::Pounds | Units::Ounce => &[(Units::Pounds, 1.0), (Units::Ounce, 0.0)],
+			_ => return self.clone(),
+		};
+
+		for (units, minimum) in family {
+			let value = self.value * units.conversion_factor(&self.units).unwrap();
+
+			// conversions between spoons and cups are only exact up to float error
+			let value = (value * 1000.0).round() / 1000.0;
+			if value >= *minimum {
+				return Amount {
+					units: units.clone(),
+					value,
+				};
+			}
+		}
+
+		self.clone()
+	}
 }
 
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
@@ -211,12 +311,26 @@ pub struct RecipeStep {
 
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct MealInfo {
+	#[serde(default)]
+	pub cook_minutes: Option<u32>,
+	/// Comma separated, like "Dinner, Main Course".
+	#[serde(default)]
+	pub course: String,
+	/// Comma separated, like "Indian, Thai".
+	#[serde(default)]
+	pub cuisine: String,
+	#[serde(default)]
+	pub diets: Vec<Diet>,
 	pub id: Uuid,
 	pub image: String,
 	pub ingredients: Vec<Ingredient>,
 	pub name: String,
+	#[serde(default)]
+	pub prep_minutes: Option<u32>,
 	pub recipe: Vec<RecipeStep>,
 	pub serving_size: usize,
+	#[serde(default)]
+	pub tags: Vec<String>,
 }
 
 impl MealInfo {
@@ -225,7 +339,42 @@ impl MealInfo {
 			date,
 			id: self.id,
 			leftovers: false,
+			servings: None,
+		}
+	}
+
+	/// How much the recipe's amounts have to be multiplied by to make `servings`. Recipes without a
+	/// serving size can't be scaled.
+	pub fn scale_factor(&self, servings: usize) -> f32 {
+		if self.serving_size == 0 {
+			return 1.0;
+		}
+
+		servings as f32 / self.serving_size as f32
+	}
+
+	pub fn scaled_ingredients(&self, servings: usize) -> Vec<Ingredient> {
+		let scale_factor = self.scale_factor(servings);
+		if scale_factor == 1.0 {
+			return self.ingredients.clone();
 		}
+
+		self.ingredients
+			.iter()
+			.map(|ingredient| Ingredient {
+				amount: ingredient.amount.scale(scale_factor),
+				name: ingredient.name.clone(),
+			})
+			.collect()
+	}
+
+	pub fn servings_for(&self, meal_stub: &MealStub) -> usize {
+		meal_stub.servings.unwrap_or(self.serving_size)
+	}
+
+	/// The ingredients needed to cook the planned meal.
+	pub fn ingredients_for(&self, meal_stub: &MealStub) -> Vec<Ingredient> {
+		self.scaled_ingredients(self.servings_for(meal_stub))
 	}
 }
 
@@ -234,26 +383,73 @@ pub struct MealStub {
 	pub date: NaiveDate,
 	pub id: Uuid,
 	pub leftovers: bool,
+	/// How many servings to make. `None` makes the recipe's own serving size.
+	#[serde(default)]
+	pub servings: Option<usize>,
 }
 
-#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct ShoppingListItem {
 	pub amount: Amount,
 	pub have: bool,
+	/// Items saved before they had ids get a new one when loaded.
+	#[serde(default = "Uuid::new_v4")]
+	pub id: Uuid,
 	pub name: String,
 }
 
 impl ShoppingListItem {
+	pub fn new(name: impl Into<String>, amount: Amount) -> Self {
+		ShoppingListItem {
+			amount,
+			have: false,
+			id: Uuid::new_v4(),
+			name: name.into(),
+		}
+	}
+
 	pub fn name(&self) -> String {
 		self.name.to_lowercase()
 	}
 }
 
-#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+impl Default for ShoppingListItem {
+	fn default() -> Self {
+		ShoppingListItem::new("", Amount::default())
+	}
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct ShoppingListInfo {
+	/// The day a list generated from the whole meal plan was made. Only those lists take in the staples
+	/// and extra items.
+	#[serde(default)]
+	pub created: Option<NaiveDate>,
+	/// The extra items this list took in, as they were when it was made.
+	#[serde(default)]
+	pub extra_items: Vec<ShoppingListItem>,
 	#[serde(default)]
 	pub for_meals: Vec<MealStub>,
+	/// Lists saved before they had ids get a new one when loaded.
+	#[serde(default = "Uuid::new_v4")]
+	pub id: Uuid,
 	pub items: Vec<ShoppingListItem>,
+	#[serde(default)]
+	pub name: String,
+}
+
+/// Written out so every list, including ones built with `..Default::default()`, gets its own id.
+impl Default for ShoppingListInfo {
+	fn default() -> Self {
+		ShoppingListInfo {
+			created: None,
+			extra_items: vec![],
+			for_meals: vec![],
+			id: Uuid::new_v4(),
+			items: vec![],
+			name: String::new(),
+		}
+	}
 }
 
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
@@ -261,13 +457,95 @@ pub struct MealPlan {
 	pub all_meals: HashMap<Uuid, MealInfo>,
 	#[serde(default)]
 	pub completed_meals: HashMap<NaiveDate, Vec<Uuid>>,
+	/// Grams per milliliter by lowercase ingredient name, on top of the built-in densities.
+	#[serde(default)]
+	pub densities: HashMap<String, f32>,
+	/// Free-form items to put on the next generated shopping list.
+	#[serde(default)]
+	pub extra_items: Vec<ShoppingListItem>,
+	/// Catalog entries by lowercase name.
+	#[serde(default)]
+	pub ingredient_catalog: HashMap<String, CatalogEntry>,
+	/// Nutrition entries by lowercase catalog name.
+	#[serde(default)]
+	pub nutrition: HashMap<String, NutritionEntry>,
+	/// Pantry items by lowercase catalog name.
+	#[serde(default)]
+	pub pantry: HashMap<String, PantryItem>,
 	pub planned_meals: HashMap<NaiveDate, Vec<MealStub>>,
+	#[serde(default)]
+	pub revision: u64,
 	pub shopping_list: Vec<ShoppingListInfo>,
+	/// Staples by lowercase catalog name.
+	#[serde(default)]
+	pub staples: HashMap<String, Staple>,
 }
 
 impl MealPlan {
-	pub fn remove_meal(&mut self, id: Uuid) {
-		self.all_meals.remove(&id);
+	/// The density of an ingredient in grams per milliliter. Falls back to the longest known name the
+	/// ingredient contains, so "all-purpose flour" uses the density of "flour".
+	pub fn density(&self, name: &str) -> Option<f32> {
+		let name = name.to_lowercase();
+		if let Some(density) = self.densities.get(&name) {
+			return Some(*density);
+		}
+
+		// pad with spaces so only whole words match, e.g. "rice" but not "licorice"
+		let words = format!(
+			" {} ",
+			name.split(|character: char| !character.is_alphanumeric())
+				.filter(|word| !word.is_empty())
+				.collect::<Vec<_>>()
+				.join(" ")
+		);
+
+		self.densities
+			.iter()
+			.map(|(known_name, density)| (known_name.as_str(), *density))
+			.chain(DEFAULT_DENSITIES.iter().cloned())
+			.filter(|(known_name, _)| words.contains(&format!(" {} ", known_name)))
+			.max_by_key(|(known_name, _)| known_name.len())
+			.map(|(_, density)| density)
+	}
+
+	pub fn set_density(&mut self, name: &str, grams_per_milliliter: Option<f32>) {
+		let name = name.to_lowercase();
+		match grams_per_milliliter {
+			Some(grams_per_milliliter) => {
+				self.densities.insert(name, grams_per_milliliter);
+			}
+			None => {
+				self.densities.remove(&name);
+			}
+		}
+	}
+
+	/// Puts an amount into the ingredient's unit from the catalog, or the most readable unit if it
+	/// has none or the amount can't be converted into it.
+	fn purchase_amount(&self, name: &str, amount: &Amount) -> Amount {
+		let default_units = self
+			.catalog_entry(name)
+			.and_then(|entry| entry.default_units.clone());
+
+		if let Some(units) = default_units {
+			if let Some(conversion_factor) =
+				units.conversion_factor_with_density(&amount.units, self.density(name))
+			{
+				return Amount::new(amount.value * conversion_factor, units);
+			}
+		}
+
+		amount.promoted()
+	}
+
+	pub fn upsert_meal(&mut self, meal_info: MealInfo) {
+		self.all_meals.insert(meal_info.id, meal_info);
+	}
+
+	pub fn remove_meal(&mut self, id: Uuid) -> Result<MealInfo, MealPlanError> {
+		let Some(meal_info) = self.all_meals.remove(&id) else {
+			return Err(MealPlanError::MealNotFound { id });
+		};
 
 		for meals in self.planned_meals.values_mut() {
 			if let Some(index) = meals.iter().position(|meal| id == meal.id) {
@@ -284,9 +562,178 @@ impl MealPlan {
 				shopping_list.for_meals.remove(index);
 			}
 		}
+
+		Ok(meal_info)
+	}
+
+	pub fn plan_meal(&mut self, meal_stub: MealStub) -> Result<(), MealPlanError> {
+		if !self.all_meals.contains_key(&meal_stub.id) {
+			return Err(MealPlanError::MealNotFound { id: meal_stub.id });
+		}
+
+		self.planned_meals
+			.entry(meal_stub.date)
+			.or_default()
+			.push(meal_stub);
+
+		Ok(())
+	}
+
+	pub fn unplan_meal(&mut self, date: NaiveDate, id: Uuid) -> Result<MealStub, MealPlanError> {
+		let Some(meals) = self.planned_meals.get_mut(&date) else {
+			return Err(MealPlanError::PlannedMealNotFound { date, id });
+		};
+
+		let Some(meal_stub) = meals.iter().find(|meal_stub| meal_stub.id == id).cloned() else {
+			return Err(MealPlanError::PlannedMealNotFound { date, id });
+		};
+
+		meals.retain(|meal_stub| meal_stub.id != id);
+
+		if meals.is_empty() {
+			self.planned_meals.remove(&date);
+		}
+
+		Ok(meal_stub)
+	}
+
+	pub fn move_meal(
+		&mut self,
+		date: NaiveDate,
+		id: Uuid,
+		new_date: NaiveDate,
+	) -> Result<(), MealPlanError> {
+		let mut meal_stub = self.unplan_meal(date, id)?;
+		meal_stub.date = new_date;
+
+		self.planned_meals
+			.entry(new_date)
+			.or_default()
+			.push(meal_stub);
+
+		Ok(())
+	}
+
+	pub fn shift_meals(&mut self, days: i64) {
+		let mut new_meals = vec![];
+		for planned_meals in self.planned_meals.values() {
+			for planned_meal in planned_meals.iter() {
+				new_meals.push(MealStub {
+					date: planned_meal.date + TimeDelta::days(days),
+					id: planned_meal.id,
+					leftovers: planned_meal.leftovers,
+					servings: planned_meal.servings,
+				});
+			}
+		}
+
+		self.planned_meals.clear();
+
+		for new_meal in new_meals {
+			self.planned_meals
+				.entry(new_meal.date)
+				.or_default()
+				.push(new_meal);
+		}
+	}
+
+	pub fn set_leftovers(
+		&mut self,
+		date: NaiveDate,
+		id: Uuid,
+		leftovers: bool,
+	) -> Result<(), MealPlanError> {
+		let Some(meal_stub) = self
+			.planned_meals
+			.get_mut(&date)
+			.and_then(|meals| meals.iter_mut().find(|meal_stub| meal_stub.id == id))
+		else {
+			return Err(MealPlanError::PlannedMealNotFound { date, id });
+		};
+
+		meal_stub.leftovers = leftovers;
+
+		Ok(())
+	}
+
+	pub fn set_servings(
+		&mut self,
+		date: NaiveDate,
+		id: Uuid,
+		servings: Option<usize>,
+	) -> Result<(), MealPlanError> {
+		let Some(meal_stub) = self
+			.planned_meals
+			.get_mut(&date)
+			.and_then(|meals| meals.iter_mut().find(|meal_stub| meal_stub.id == id))
+		else {
+			return Err(MealPlanError::PlannedMealNotFound { date, id });
+		};
+
+		meal_stub.servings = servings;
+
+		Ok(())
+	}
+
+	pub fn complete_meal(&mut self, date: NaiveDate, id: Uuid) -> Result<(), MealPlanError> {
+		let meal_stub = self.unplan_meal(date, id)?;
+
+		// leftovers were already taken out of the pantry when the meal was first cooked
+		if !meal_stub.leftovers {
+			if let Some(meal_info) = self.all_meals.get(&meal_stub.id) {
+				for ingredient in meal_info.ingredients_for(&meal_stub) {
+					self.use_pantry(&ingredient.name, &ingredient.amount);
+				}
+			}
+		}
+
+		self.completed_meals
+			.entry(date)
+			.or_default()
+			.push(meal_stub.id);
+
+		Ok(())
+	}
+
+	pub fn set_shopping_list_item(
+		&mut self,
+		shopping_list_id: Uuid,
+		id: Uuid,
+		have: bool,
+	) -> Result<(), MealPlanError> {
+		let shopping_list_item = self.shopping_list_item_mut(shopping_list_id, id)?;
+
+		if shopping_list_item.have == have {
+			return Ok(());
+		}
+
+		shopping_list_item.have = have;
+
+		// checking an item off means it was bought
+		let name = shopping_list_item.name.clone();
+		let amount = shopping_list_item.amount.clone();
+		if have {
+			self.restock_pantry(&name, &amount);
+		} else {
+			self.use_pantry(&name, &amount);
+		}
+
+		Ok(())
+	}
+
+	pub fn prune_shopping_list(&mut self, shopping_list_id: Uuid) -> Result<(), MealPlanError> {
+		let shopping_list = self.shopping_list_by_id_mut(shopping_list_id)?;
+		shopping_list.items.retain(|item| !item.have);
+
+		if shopping_list.items.is_empty() {
+			self.shopping_list
+				.retain(|shopping_list| shopping_list.id != shopping_list_id);
+		}
+
+		Ok(())
 	}
 
-	pub fn generate_shopping_list(&self) -> Option<ShoppingListInfo> {
+	pub fn generate_shopping_list(&self, today: NaiveDate) -> Option<ShoppingListInfo> {
 		let mut items: HashMap<String, ShoppingListItem> = HashMap::new();
 
 		let mut meals_in_shopping_list_already: HashSet<(NaiveDate, Uuid)> = HashSet::new();
@@ -308,55 +755,119 @@ impl MealPlan {
 				}
 
 				let meal = self.all_meals.get(&meal_stub.id).unwrap();
-				for ingredient in meal.ingredients.iter() {
-					let item = items.entry(ingredient.name()).or_insert(ShoppingListItem {
-						amount: Amount::new(0.0, ingredient.amount.units.clone()),
-						have: false,
-						name: ingredient.name.clone(),
-					});
-
-					if item.amount.units.is_compatible(&ingredient.amount.units) {
-						item.amount = item.amount.add(&ingredient.amount).unwrap();
-					}
+				for ingredient in meal.ingredients_for(meal_stub).iter() {
+					self.add_to_shopping_list(&mut items, &ingredient.name, &ingredient.amount);
 				}
 
-				for_meals.push(meal_stub.clone());
+				for_meals.push(*meal_stub);
 			}
 		}
 
-		let mut items = items.into_values().collect::<Vec<_>>();
-		items.sort_by(|item1, item2| item1.amount.units.cmp(&item2.amount.units));
+		// recipes use up the pantry, but staples and extra items are bought regardless
+		let mut items = items
+			.into_values()
+			.filter_map(|item| {
+				let amount = self.still_needed(&item.name, &item.amount, today)?;
+				Some((item.name(), ShoppingListItem { amount, ..item }))
+			})
+			.collect::<HashMap<_, _>>();
+
+		for staple in self.due_staples(today) {
+			self.add_to_shopping_list(&mut items, &staple.name, &staple.amount);
+		}
+
+		for item in self.extra_items.iter() {
+			self.add_to_shopping_list(&mut items, &item.name, &item.amount);
+		}
 
-		if items.len() != 0 {
-			Some(ShoppingListInfo { items, for_meals })
+		let mut items = items
+			.into_values()
+			.map(|item| ShoppingListItem {
+				amount: self.purchase_amount(&item.name, &item.amount),
+				..item
+			})
+			.collect::<Vec<_>>();
+
+		items.sort_by_key(|item| (self.aisle(&item.name), item.name()));
+
+		if !items.is_empty() {
+			Some(ShoppingListInfo {
+				created: Some(today),
+				extra_items: self.extra_items.clone(),
+				for_meals,
+				items,
+				..Default::default()
+			})
 		} else {
 			None
 		}
 	}
 
-	pub fn generate_shopping_list_for_meal(&self, date: NaiveDate, id: Uuid) -> ShoppingListInfo {
-		let meal_stub = self
+	pub(crate) fn add_to_shopping_list(
+		&self,
+		items: &mut HashMap<String, ShoppingListItem>,
+		name: &str,
+		amount: &Amount,
+	) {
+		let name = self.canonical_name(name);
+		let item = items
+			.entry(name.to_lowercase())
+			.or_insert(ShoppingListItem::new(
+				name.clone(),
+				Amount::new(0.0, amount.units.clone()),
+			));
+
+		match item.amount.add(amount, self.density(&name)) {
+			Some(total) => item.amount = total,
+			None => log::warn!(
+				"Cannot add {} {} to {} {} of '{}'",
+				amount.value,
+				amount.units,
+				item.amount.value,
+				item.amount.units,
+				name
+			),
+		}
+	}
+
+	pub fn generate_shopping_list_for_meal(
+		&self,
+		date: NaiveDate,
+		id: Uuid,
+		today: NaiveDate,
+	) -> Result<ShoppingListInfo, MealPlanError> {
+		let Some(meal_stub) = self
 			.planned_meals
 			.get(&date)
-			.unwrap()
-			.iter()
-			.find(|meal_stub| meal_stub.id == id)
-			.unwrap()
-			.clone();
+			.and_then(|meals| meals.iter().find(|meal_stub| meal_stub.id == id))
+			.cloned()
+		else {
+			return Err(MealPlanError::PlannedMealNotFound { date, id });
+		};
+
+		let Some(meal) = self.all_meals.get(&id) else {
+			return Err(MealPlanError::MealNotFound { id });
+		};
 
-		let meal = self.all_meals.get(&id).unwrap();
 		let mut items = vec![];
-		for ingredient in meal.ingredients.iter() {
-			items.push(ShoppingListItem {
-				amount: ingredient.amount.clone(),
-				have: false,
-				name: ingredient.name.clone(),
-			});
+		for ingredient in meal.ingredients_for(&meal_stub) {
+			let name = self.canonical_name(&ingredient.name);
+			let Some(amount) = self.still_needed(&name, &ingredient.amount, today) else {
+				continue;
+			};
+
+			items.push(ShoppingListItem::new(
+				name.clone(),
+				self.purchase_amount(&name, &amount),
+			));
 		}
 
-		ShoppingListInfo {
-			items,
+		items.sort_by_key(|item| (self.aisle(&item.name), item.name()));
+
+		Ok(ShoppingListInfo {
 			for_meals: vec![meal_stub],
-		}
+			items,
+			..Default::default()
+		})
 	}
 }
@@ -0,0 +1,110 @@
+use chrono::NaiveDate;
+use meals_database::{
+	Aisle, Amount, CatalogEntry, Ingredient, MealInfo, MealPlan, MealPlanChange, Units,
+};
+use uuid::Uuid;
+
+fn meal_plan() -> MealPlan {
+	let mut meal_plan = MealPlan::default();
+	meal_plan.save_catalog_entry(CatalogEntry {
+		aisle: Aisle::Produce,
+		aliases: vec!["garlic clove".into(), "Garlic  Cloves".into()],
+		default_units: None,
+		name: "Garlic".into(),
+	});
+
+	meal_plan.save_catalog_entry(CatalogEntry {
+		aisle: Aisle::Dairy,
+		aliases: vec![],
+		default_units: Some(Units::Pounds),
+		name: "Butter".into(),
+	});
+
+	let date = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+	for ingredients in [
+		vec![
+			("Garlic clove", Amount::new(2.0, Units::Count)),
+			("Butter", Amount::new(8.0, Units::Tablespoon)),
+			("Rice", Amount::new(1.0, Units::Cup)),
+		],
+		vec![
+			("garlic", Amount::new(3.0, Units::Count)),
+			("butter", Amount::new(113.0, Units::Grams)),
+		],
+	] {
+		let meal_info = MealInfo {
+			id: Uuid::new_v4(),
+			ingredients: ingredients
+				.into_iter()
+				.map(|(name, amount)| Ingredient {
+					amount,
+					name: name.into(),
+				})
+				.collect(),
+			..Default::default()
+		};
+
+		let meal_stub = meal_info.new_stub(date);
+		meal_plan.upsert_meal(meal_info);
+		meal_plan.plan_meal(meal_stub).unwrap();
+	}
+
+	meal_plan
+}
+
+#[test]
+fn lookup() {
+	let meal_plan = meal_plan();
+
+	assert_eq!(meal_plan.canonical_name("GARLIC CLOVES"), "Garlic");
+	assert_eq!(meal_plan.canonical_name(" rice "), "rice");
+	assert_eq!(meal_plan.aisle("butter"), Aisle::Dairy);
+	assert_eq!(meal_plan.aisle("Rice"), Aisle::Other);
+	assert_eq!(Aisle::try_from("produce"), Ok(Aisle::Produce));
+}
+
+#[test]
+fn shopping_list_merges_aliases() {
+	let meal_plan = meal_plan();
+	let shopping_list = meal_plan
+		.generate_shopping_list(NaiveDate::from_ymd_opt(2024, 1, 1).unwrap())
+		.unwrap();
+
+	let names = shopping_list
+		.items
+		.iter()
+		.map(|item| item.name.as_str())
+		.collect::<Vec<_>>();
+
+	assert_eq!(names, vec!["Garlic", "Butter", "Rice"]);
+
+	let garlic = &shopping_list.items[0].amount;
+	assert_eq!(garlic.units, Units::Count);
+	assert!((garlic.value - 5.0).abs() < 0.01);
+
+	let butter = &shopping_list.items[1].amount;
+	assert_eq!(butter.units, Units::Pounds);
+	assert!((butter.value - 0.5).abs() < 0.01, "{}", butter.value);
+
+	let groups = meal_plan.group_by_aisle(&shopping_list.items);
+	let aisles = groups.iter().map(|(aisle, _)| *aisle).collect::<Vec<_>>();
+	assert_eq!(aisles, vec![Aisle::Produce, Aisle::Dairy, Aisle::Other]);
+}
+
+#[test]
+fn catalog_changes() {
+	let mut meal_plan = meal_plan();
+
+	meal_plan
+		.apply(&MealPlanChange::CatalogEntryDeleted {
+			name: "garlic".into(),
+		})
+		.unwrap();
+
+	assert_eq!(meal_plan.canonical_name("garlic clove"), "garlic clove");
+	assert!(meal_plan
+		.apply(&MealPlanChange::CatalogEntryDeleted {
+			name: "garlic".into(),
+		})
+		.is_err());
+}
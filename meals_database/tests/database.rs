@@ -0,0 +1,53 @@
+use meals_database::{Database, JsonDatabase, MealInfo, MealPlan, MealPlanChange, SqliteDatabase};
+use uuid::Uuid;
+
+fn meal_saved() -> MealPlanChange {
+	MealPlanChange::MealSaved {
+		meal_info: MealInfo {
+			id: Uuid::new_v4(),
+			name: "Fried Rice".into(),
+			serving_size: 1,
+			..Default::default()
+		},
+	}
+}
+
+#[tokio::test]
+async fn json_keeps_meal_plan_when_save_fails() {
+	let directory = std::env::temp_dir().join(format!("meals-json-{}", Uuid::new_v4()));
+	std::fs::create_dir_all(&directory).unwrap();
+	let path = directory.join("meals.json");
+	let database = JsonDatabase::<MealPlan>::new(path.to_str().unwrap());
+
+	database.apply(meal_saved()).await.unwrap();
+	assert_eq!(Database::get(&database).revision, 1);
+
+	// nowhere to write the next save to
+	std::fs::remove_dir_all(&directory).unwrap();
+	assert!(database.apply(meal_saved()).await.is_err());
+
+	let meal_plan = Database::get(&database);
+	assert_eq!(meal_plan.revision, 1);
+	assert_eq!(meal_plan.all_meals.len(), 1);
+}
+
+#[tokio::test]
+async fn sqlite_keeps_meal_plan_when_commit_fails() {
+	let path = std::env::temp_dir().join(format!("meals-{}.sqlite", Uuid::new_v4()));
+	let path = path.to_str().unwrap();
+	let database = SqliteDatabase::open(path).unwrap();
+
+	database.apply(meal_saved()).await.unwrap();
+	assert_eq!(database.get().revision, 1);
+
+	// break the next write from outside
+	rusqlite::Connection::open(path)
+		.unwrap()
+		.execute("DROP TABLE meals", [])
+		.unwrap();
+	assert!(database.apply(meal_saved()).await.is_err());
+
+	let meal_plan = database.get();
+	assert_eq!(meal_plan.revision, 1);
+	assert_eq!(meal_plan.all_meals.len(), 1);
+}
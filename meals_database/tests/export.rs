@@ -0,0 +1,56 @@
+use meals_database::{
+	shopping_list_to_markdown, shopping_list_to_text, Aisle, Amount, CatalogEntry, MealPlan,
+	ShoppingListInfo, ShoppingListItem, Units,
+};
+use uuid::Uuid;
+
+fn meal_plan() -> MealPlan {
+	let mut meal_plan = MealPlan::default();
+	meal_plan.save_catalog_entry(CatalogEntry {
+		aisle: Aisle::Produce,
+		name: "Onion".into(),
+		..Default::default()
+	});
+	meal_plan.save_catalog_entry(CatalogEntry {
+		aisle: Aisle::Dairy,
+		name: "Milk".into(),
+		..Default::default()
+	});
+
+	meal_plan.shopping_list.push(ShoppingListInfo {
+		items: vec![
+			ShoppingListItem {
+				have: true,
+				..ShoppingListItem::new("Milk", Amount::new(1.0, Units::Cup))
+			},
+			ShoppingListItem::new("Onion", Amount::new(2.0, Units::Count)),
+		],
+		..Default::default()
+	});
+
+	meal_plan
+}
+
+#[test]
+fn text() {
+	let meal_plan = meal_plan();
+	let shopping_list = &meal_plan.shopping_list[0];
+
+	assert_eq!(
+		shopping_list_to_text(&meal_plan, shopping_list),
+		"Produce\n  Onion, 2\n\nDairy\n  Milk, 1 cup (have)\n"
+	);
+}
+
+#[test]
+fn markdown() {
+	let meal_plan = meal_plan();
+	let shopping_list = &meal_plan.shopping_list[0];
+
+	assert_eq!(
+		shopping_list_to_markdown(&meal_plan, shopping_list),
+		"# Shopping list\n\n## Produce\n\n- [ ] Onion, 2\n\n## Dairy\n\n- [x] Milk, 1 cup\n"
+	);
+
+	assert!(meal_plan.shopping_list_by_id(Uuid::new_v4()).is_err());
+}
@@ -0,0 +1,53 @@
+use meals_database::{format_amount, format_amount_compound, kitchen_fraction, Amount, Units};
+
+#[test]
+fn kitchen_fractions() {
+	assert_eq!(kitchen_fraction(1.0 / 3.0), "⅓");
+	assert_eq!(kitchen_fraction(2.3), "2⅓");
+	assert_eq!(kitchen_fraction(1.74), "1¾");
+	assert_eq!(kitchen_fraction(2.98), "3");
+	assert_eq!(kitchen_fraction(0.07), "⅛");
+	assert_eq!(kitchen_fraction(0.01), "0");
+	assert_eq!(kitchen_fraction(0.0), "0");
+}
+
+#[test]
+fn amounts() {
+	assert_eq!(format_amount(&Amount::new(1.0 / 3.0, Units::Cup)), "⅓ cup");
+	assert_eq!(format_amount(&Amount::new(1.5, Units::Cup)), "1½ cups");
+	assert_eq!(format_amount(&Amount::new(48.0, Units::Teaspoon)), "1 cup");
+	assert_eq!(format_amount(&Amount::new(3.0, Units::Count)), "3");
+	assert_eq!(
+		format_amount(&Amount::new(4.0, Units::Pinches)),
+		"4 pinches"
+	);
+	assert_eq!(format_amount(&Amount::new(1250.0, Units::Grams)), "1.25 kg");
+	assert_eq!(
+		format_amount(&Amount::new(333.333, Units::Milliliters)),
+		"333 ml"
+	);
+}
+
+#[test]
+fn compound_amounts() {
+	assert_eq!(
+		format_amount_compound(&Amount::new(1.125, Units::Cup)),
+		"1 cup + 2 tbsp"
+	);
+	assert_eq!(
+		format_amount_compound(&Amount::new(4.0, Units::Teaspoon)),
+		"1 tbsp + 1 tsp"
+	);
+	assert_eq!(
+		format_amount_compound(&Amount::new(1.25, Units::Pounds)),
+		"1¼ lb"
+	);
+	assert_eq!(
+		format_amount_compound(&Amount::new(20.0, Units::Ounce)),
+		"1¼ lb"
+	);
+	assert_eq!(
+		format_amount_compound(&Amount::new(2.0 / 3.0, Units::Cup)),
+		"⅔ cup"
+	);
+}
@@ -0,0 +1,133 @@
+use meals_database::{parse_ingredient, Units};
+
+fn parsed(line: &str) -> (Option<f32>, Option<Units>, String, bool) {
+	let parsed_ingredient = parse_ingredient(line);
+	let amount = parsed_ingredient.amount;
+
+	(
+		amount.as_ref().map(|amount| amount.value),
+		amount.map(|amount| amount.units),
+		parsed_ingredient.name,
+		parsed_ingredient.confident,
+	)
+}
+
+fn assert_parsed(line: &str, value: f32, units: Units, name: &str) {
+	let (parsed_value, parsed_units, parsed_name, confident) = parsed(line);
+
+	let parsed_value = parsed_value.unwrap_or_else(|| panic!("no amount parsed from '{}'", line));
+	assert!(
+		(parsed_value - value).abs() < 0.001,
+		"'{}' parsed as {} instead of {}",
+		line,
+		parsed_value,
+		value
+	);
+	assert_eq!(parsed_units, Some(units), "units of '{}'", line);
+	assert_eq!(parsed_name, name, "name of '{}'", line);
+	assert!(confident, "'{}' should be parsed confidently", line);
+}
+
+#[test]
+fn simple_amounts() {
+	assert_parsed("2 cups flour", 2.0, Units::Cup, "flour");
+	assert_parsed("1 cup of milk", 1.0, Units::Cup, "milk");
+	assert_parsed(
+		"0.5 ml vanilla extract",
+		0.5,
+		Units::Milliliters,
+		"vanilla extract",
+	);
+	assert_parsed("3 eggs", 3.0, Units::Count, "eggs");
+}
+
+#[test]
+fn fractions() {
+	assert_parsed("1/2 cup sugar", 0.5, Units::Cup, "sugar");
+	assert_parsed("1 1/2 cups stock", 1.5, Units::Cup, "stock");
+	assert_parsed("1-1/2 cups stock", 1.5, Units::Cup, "stock");
+	assert_parsed("½ teaspoon salt", 0.5, Units::Teaspoon, "salt");
+	assert_parsed("1½ cups rice", 1.5, Units::Cup, "rice");
+	assert_parsed("1 ⅓ cups oats", 4.0 / 3.0, Units::Cup, "oats");
+	assert_parsed("1\u{2044}4 cup honey", 0.25, Units::Cup, "honey");
+}
+
+#[test]
+fn ranges() {
+	let parsed_ingredient = parse_ingredient("2-3 cloves garlic");
+	assert_eq!(parsed_ingredient.range_minimum, Some(2.0));
+	assert_parsed("2-3 cloves garlic", 3.0, Units::Teaspoon, "garlic");
+	assert_parsed(
+		"2 – 3 tablespoons olive oil",
+		3.0,
+		Units::Tablespoon,
+		"olive oil",
+	);
+	assert_parsed("1 to 2 cups water", 2.0, Units::Cup, "water");
+}
+
+#[test]
+fn abbreviated_units() {
+	assert_parsed("1 tbsp butter", 1.0, Units::Tablespoon, "butter");
+	assert_parsed("2 Tbsp. soy sauce", 2.0, Units::Tablespoon, "soy sauce");
+	assert_parsed("1 T honey", 1.0, Units::Tablespoon, "honey");
+	assert_parsed("1 t cumin", 1.0, Units::Teaspoon, "cumin");
+	assert_parsed("2 tsp. paprika", 2.0, Units::Teaspoon, "paprika");
+	assert_parsed("8 oz. cream cheese", 8.0, Units::Ounce, "cream cheese");
+	assert_parsed("250ml milk", 250.0, Units::Milliliters, "milk");
+	assert_parsed("3 C. flour", 3.0, Units::Cup, "flour");
+	assert_parsed("200 grams flour", 200.0, Units::Grams, "flour");
+	assert_parsed("500g pasta", 500.0, Units::Grams, "pasta");
+	assert_parsed("1.5 kg potatoes", 1.5, Units::Kilograms, "potatoes");
+	assert_parsed("2 lbs. ground beef", 2.0, Units::Pounds, "ground beef");
+	assert_parsed("1 L stock", 1.0, Units::Liters, "stock");
+	assert_parsed("4 fl oz cream", 4.0, Units::FluidOunces, "cream");
+	assert_parsed("1 pint strawberries", 1.0, Units::Pints, "strawberries");
+	assert_parsed("1 pinch nutmeg", 1.0, Units::Pinches, "nutmeg");
+}
+
+#[test]
+fn parenthetical_notes() {
+	let parsed_ingredient = parse_ingredient("1 (14 ounce) can diced tomatoes (undrained)");
+	assert_eq!(parsed_ingredient.name, "can diced tomatoes");
+	assert_eq!(
+		parsed_ingredient.note.as_deref(),
+		Some("14 ounce; undrained")
+	);
+	assert!(parsed_ingredient.confident);
+
+	assert_parsed("2 cups (about 250g) flour", 2.0, Units::Cup, "flour");
+}
+
+#[test]
+fn without_quantity() {
+	let parsed_ingredient = parse_ingredient("Salt to taste");
+	assert!(parsed_ingredient.amount.is_none());
+	assert_eq!(parsed_ingredient.name, "Salt");
+	assert_eq!(parsed_ingredient.note.as_deref(), Some("to taste"));
+	assert!(parsed_ingredient.confident);
+
+	let parsed_ingredient = parse_ingredient("fresh parsley, for garnish");
+	assert_eq!(parsed_ingredient.name, "fresh parsley");
+	assert!(parsed_ingredient.confident);
+
+	let ingredient = parse_ingredient("black pepper, to taste").into_ingredient();
+	assert_eq!(ingredient.name, "black pepper");
+	assert_eq!(ingredient.amount.units, Units::Count);
+	assert_eq!(ingredient.amount.value, 1.0);
+}
+
+#[test]
+fn unconfident() {
+	let (_, _, name, confident) = parsed("Juice of 1 lemon");
+	assert_eq!(name, "Juice of 1 lemon");
+	assert!(!confident);
+
+	let (value, _, name, confident) = parsed("2 gallons water");
+	assert_eq!(value, Some(2.0));
+	assert_eq!(name, "gallons water");
+	assert!(!confident);
+
+	let (_, _, _, confident) = parsed("");
+	assert!(!confident);
+}
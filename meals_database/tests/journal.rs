@@ -0,0 +1,95 @@
+use chrono::NaiveDate;
+use meals_database::{
+	Amount, MealPlan, MealPlanChange, MealPlanSnapshot, MealStub, NutritionEntry, ShoppingListInfo,
+	ShoppingListItem, Units,
+};
+use uuid::Uuid;
+
+fn meal_plan() -> MealPlan {
+	let mut meal_plan = MealPlan::default();
+	meal_plan.shopping_list.push(ShoppingListInfo {
+		items: vec![ShoppingListItem::new(
+			"Eggs",
+			Amount::new(6.0, Units::Count),
+		)],
+		..Default::default()
+	});
+	meal_plan.planned_meals.insert(
+		NaiveDate::from_ymd_opt(2024, 3, 4).unwrap(),
+		vec![MealStub {
+			date: NaiveDate::from_ymd_opt(2024, 3, 4).unwrap(),
+			id: Uuid::new_v4(),
+			leftovers: false,
+			servings: None,
+		}],
+	);
+
+	meal_plan
+}
+
+#[test]
+fn snapshots_only_what_changed() {
+	let mut meal_plan = meal_plan();
+	let original = meal_plan.clone();
+
+	let change = MealPlanChange::ShoppingListItemSet {
+		have: true,
+		id: meal_plan.shopping_list[0].items[0].id,
+		shopping_list_id: meal_plan.shopping_list[0].id,
+	};
+
+	let before = meal_plan.snapshot(change.meal_ids(), change.touches_nutrition());
+	meal_plan.apply(&change).unwrap();
+	let after = meal_plan.snapshot(change.meal_ids(), change.touches_nutrition());
+
+	assert!(before.nutrition.is_none());
+
+	let (before, after) = MealPlanSnapshot::difference(before, after);
+	assert!(before.shopping_list.is_some());
+	assert!(after.shopping_list.is_some());
+	assert!(before.planned_meals.is_none());
+	assert!(before.completed_meals.is_none());
+	assert!(before.ingredient_catalog.is_none());
+	assert!(before.staples.is_none());
+	assert!(before.meals.is_empty());
+
+	// restoring the smaller snapshot still undoes the change
+	meal_plan.restore(&before);
+	assert!(!meal_plan.shopping_list[0].items[0].have);
+	assert_eq!(meal_plan.planned_meals.len(), original.planned_meals.len());
+	assert_eq!(meal_plan.pantry.len(), original.pantry.len());
+}
+
+#[test]
+fn snapshots_nutrition_only_when_imported() {
+	let mut meal_plan = meal_plan();
+
+	let change = MealPlanChange::NutritionImported {
+		entries: vec![NutritionEntry {
+			name: "Eggs".into(),
+			..Default::default()
+		}],
+	};
+	assert!(change.touches_nutrition());
+
+	let before = meal_plan.snapshot(change.meal_ids(), change.touches_nutrition());
+	meal_plan.apply(&change).unwrap();
+	let after = meal_plan.snapshot(change.meal_ids(), change.touches_nutrition());
+
+	let (before, after) = MealPlanSnapshot::difference(before, after);
+	assert!(before
+		.nutrition
+		.as_ref()
+		.is_some_and(|nutrition| nutrition.is_empty()));
+	assert_eq!(
+		after.nutrition.as_ref().map(|nutrition| nutrition.len()),
+		Some(1)
+	);
+	assert!(before.shopping_list.is_none());
+
+	// undoing it journals the nutrition table too
+	let undo = MealPlanChange::SnapshotRestored { snapshot: before };
+	assert!(undo.touches_nutrition());
+	meal_plan.apply(&undo).unwrap();
+	assert!(meal_plan.nutrition.is_empty());
+}
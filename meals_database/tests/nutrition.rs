@@ -0,0 +1,103 @@
+use chrono::NaiveDate;
+use meals_database::{
+	parse_nutrition_csv, Amount, Ingredient, MealInfo, MealPlan, MealStub, NutritionError, Units,
+};
+use uuid::Uuid;
+
+const CSV: &str = "\
+name,calories,protein,carbs,fat,fiber,sodium,per,each
+Rice,130,2.7,28,0.3,0.4,1,,
+\"Eggs, large\",143,12.6,0.7,9.5,0,142,g,50
+Milk,42,3.4,5,1,0,44,ml,
+";
+
+fn ingredient(name: &str, value: f32, units: Units) -> Ingredient {
+	Ingredient {
+		amount: Amount::new(value, units),
+		name: name.into(),
+	}
+}
+
+fn meal_plan() -> MealPlan {
+	let mut meal_plan = MealPlan::default();
+	meal_plan.import_nutrition(parse_nutrition_csv(CSV.as_bytes()).unwrap());
+
+	meal_plan
+}
+
+fn assert_close(left: f32, right: f32) {
+	assert!((left - right).abs() < 0.01, "{} != {}", left, right);
+}
+
+#[test]
+fn parses_csv() {
+	let entries = parse_nutrition_csv(CSV.as_bytes()).unwrap();
+
+	assert_eq!(entries.len(), 3);
+	assert_eq!(entries[0].name, "Rice");
+	assert!(!entries[0].liquid);
+	assert_eq!(entries[0].each, None);
+	assert_eq!(entries[1].name, "Eggs, large");
+	assert_eq!(entries[1].each, Some(50.0));
+	assert!(entries[2].liquid);
+	assert_close(entries[2].nutrients.protein, 3.4);
+
+	let invalid = "name,calories,protein,carbs,fat,fiber,sodium,per\nRice,1,1,1,1,1,1,cup\n";
+	assert!(matches!(
+		parse_nutrition_csv(invalid.as_bytes()),
+		Err(NutritionError::InvalidBasis { .. })
+	));
+	assert!(parse_nutrition_csv("name,calories\nRice,lots\n".as_bytes()).is_err());
+}
+
+#[test]
+fn meal_and_day_totals() {
+	let mut meal_plan = meal_plan();
+	let date = NaiveDate::from_ymd_opt(2024, 3, 4).unwrap();
+
+	let meal_info = MealInfo {
+		id: Uuid::new_v4(),
+		ingredients: vec![
+			ingredient("Rice", 200.0, Units::Grams),
+			ingredient("Eggs, large", 2.0, Units::Count),
+			ingredient("Milk", 0.5, Units::Liters),
+			ingredient("Saffron", 1.0, Units::Pinches),
+		],
+		name: "Rice Pudding".into(),
+		serving_size: 2,
+		..Default::default()
+	};
+
+	let nutrition = meal_plan.meal_nutrition(&meal_info);
+	// (260 + 143 + 210) / 2 servings
+	assert_close(nutrition.per_serving.calories, 306.5);
+	// (2 + 142 + 220) / 2 servings
+	assert_close(nutrition.per_serving.sodium, 182.0);
+	assert_eq!(nutrition.missing, vec!["Saffron".to_string()]);
+
+	meal_plan.upsert_meal(meal_info.clone());
+	meal_plan
+		.plan_meal(MealStub {
+			date,
+			id: meal_info.id,
+			leftovers: false,
+			servings: None,
+		})
+		.unwrap();
+	meal_plan
+		.plan_meal(MealStub {
+			date,
+			id: meal_info.id,
+			leftovers: true,
+			servings: None,
+		})
+		.unwrap();
+
+	let day = meal_plan.day_nutrition(date);
+	assert_eq!(day.meals.len(), 2);
+	assert_close(day.total.calories, 613.0);
+
+	let report = meal_plan.nutrition_report(date, date.succ_opt().unwrap());
+	assert_eq!(report.len(), 1);
+	assert_eq!(report[0].date, date);
+}
@@ -0,0 +1,85 @@
+use meals_database::{
+	Amount, Ingredient, MealInfo, MealPlan, ShoppingListInfo, ShoppingListItem, Units,
+};
+use uuid::Uuid;
+
+fn meal(name: &str, ingredients: &[&str]) -> MealInfo {
+	MealInfo {
+		id: Uuid::new_v4(),
+		ingredients: ingredients
+			.iter()
+			.map(|name| Ingredient {
+				amount: Amount::new(1.0, Units::Count),
+				name: name.to_string(),
+			})
+			.collect(),
+		name: name.into(),
+		serving_size: 1,
+		..Default::default()
+	}
+}
+
+fn meal_plan() -> MealPlan {
+	let mut meal_plan = MealPlan::default();
+	meal_plan.upsert_meal(meal("Fried Rice", &["Rice", "Eggs", "Peas"]));
+	meal_plan.upsert_meal(meal("Omelette", &["Eggs", "Butter"]));
+	meal_plan.upsert_meal(meal("Chicken and Rice", &["Chicken thighs", "Rice"]));
+	meal_plan.upsert_meal(meal("Toast", &["Bread"]));
+
+	meal_plan
+}
+
+#[test]
+fn ranks_by_coverage() {
+	let meal_plan = meal_plan();
+
+	let meals = meal_plan.cookable_meals(&["eggs".into(), "Rice".into(), "chicken".into()]);
+	let ranked = meals
+		.iter()
+		.map(|meal| (meal.name.as_str(), meal.missing.clone()))
+		.collect::<Vec<_>>();
+
+	// "chicken" covers "Chicken thighs", and meals without any of the ingredients are left out
+	assert_eq!(
+		ranked,
+		vec![
+			("Chicken and Rice", vec![]),
+			("Fried Rice", vec!["Peas".to_string()]),
+			("Omelette", vec!["Butter".to_string()]),
+		]
+	);
+	assert_eq!(meals[0].coverage, 1.0);
+	assert_eq!(meals[2].coverage, 0.5);
+
+	// whole words only
+	assert!(meal_plan.cookable_meals(&["ric".into()]).is_empty());
+	assert!(meal_plan.cookable_meals(&[]).is_empty());
+}
+
+#[test]
+fn checked_items() {
+	let mut meal_plan = meal_plan();
+	meal_plan.shopping_list.push(ShoppingListInfo {
+		items: vec![
+			ShoppingListItem {
+				have: true,
+				..ShoppingListItem::new("Bread", Amount::new(1.0, Units::Count))
+			},
+			ShoppingListItem::new("Butter", Amount::new(1.0, Units::Count)),
+		],
+		..Default::default()
+	});
+	meal_plan.shopping_list.push(ShoppingListInfo {
+		items: vec![ShoppingListItem {
+			have: true,
+			..ShoppingListItem::new("bread", Amount::new(2.0, Units::Count))
+		}],
+		..Default::default()
+	});
+
+	assert_eq!(meal_plan.checked_items(), vec!["Bread".to_string()]);
+
+	let meals = meal_plan.cookable_meals(&meal_plan.checked_items());
+	assert_eq!(meals.len(), 1);
+	assert_eq!(meals[0].name, "Toast");
+}
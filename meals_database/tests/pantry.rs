@@ -0,0 +1,143 @@
+use chrono::NaiveDate;
+use meals_database::{Amount, Ingredient, MealInfo, MealPlan, PantryItem, Units};
+use uuid::Uuid;
+
+fn assert_amount(amount: &Amount, value: f32, units: Units) {
+	assert_eq!(amount.units, units, "{} {}", amount.value, amount.units);
+	assert!(
+		(amount.value - value).abs() < 0.01,
+		"{} is not {}",
+		amount.value,
+		value
+	);
+}
+
+fn omelette() -> MealInfo {
+	MealInfo {
+		id: Uuid::new_v4(),
+		ingredients: vec![
+			Ingredient {
+				amount: Amount::new(3.0, Units::Count),
+				name: "Eggs".into(),
+			},
+			Ingredient {
+				amount: Amount::new(0.5, Units::Cup),
+				name: "Milk".into(),
+			},
+		],
+		serving_size: 1,
+		..Default::default()
+	}
+}
+
+fn planned_omelette(meal_plan: &mut MealPlan) -> (NaiveDate, Uuid) {
+	let meal_info = omelette();
+	let date = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+
+	meal_plan.upsert_meal(meal_info.clone());
+	meal_plan.plan_meal(meal_info.new_stub(date)).unwrap();
+
+	(date, meal_info.id)
+}
+
+#[test]
+fn shopping_list_subtracts_pantry() {
+	let mut meal_plan = MealPlan::default();
+	let (today, _) = planned_omelette(&mut meal_plan);
+
+	meal_plan.set_pantry_item(PantryItem {
+		amount: Amount::new(1.0, Units::Count),
+		expires: None,
+		name: "eggs".into(),
+	});
+	meal_plan.set_pantry_item(PantryItem {
+		amount: Amount::new(1.0, Units::Cup),
+		expires: None,
+		name: "Milk".into(),
+	});
+
+	let shopping_list = meal_plan.generate_shopping_list(today).unwrap();
+	assert_eq!(shopping_list.items.len(), 1);
+	assert_eq!(shopping_list.items[0].name, "Eggs");
+	assert_amount(&shopping_list.items[0].amount, 2.0, Units::Count);
+
+	// expired milk has to be bought again
+	let yesterday = today.pred_opt().unwrap();
+	meal_plan.set_pantry_item(PantryItem {
+		amount: Amount::new(1.0, Units::Cup),
+		expires: Some(yesterday),
+		name: "Milk".into(),
+	});
+
+	let shopping_list = meal_plan.generate_shopping_list(today).unwrap();
+	assert_eq!(shopping_list.items.len(), 2);
+}
+
+#[test]
+fn completing_meal_uses_pantry() {
+	let mut meal_plan = MealPlan::default();
+	let (date, id) = planned_omelette(&mut meal_plan);
+
+	meal_plan.set_pantry_item(PantryItem {
+		amount: Amount::new(4.0, Units::Count),
+		expires: None,
+		name: "Eggs".into(),
+	});
+	meal_plan.set_pantry_item(PantryItem {
+		amount: Amount::new(8.0, Units::Tablespoon),
+		expires: None,
+		name: "Milk".into(),
+	});
+
+	meal_plan.complete_meal(date, id).unwrap();
+
+	assert_amount(
+		&meal_plan.pantry_item("Eggs").unwrap().amount,
+		1.0,
+		Units::Count,
+	);
+
+	// half a cup is exactly eight tablespoons
+	assert!(meal_plan.pantry_item("Milk").is_none());
+}
+
+#[test]
+fn checking_item_restocks_pantry() {
+	let mut meal_plan = MealPlan::default();
+	let (today, _) = planned_omelette(&mut meal_plan);
+
+	let shopping_list = meal_plan.generate_shopping_list(today).unwrap();
+	let eggs = shopping_list
+		.items
+		.iter()
+		.find(|item| item.name == "Eggs")
+		.unwrap()
+		.id;
+
+	let shopping_list_id = shopping_list.id;
+	meal_plan.shopping_list.push(shopping_list);
+
+	meal_plan
+		.set_shopping_list_item(shopping_list_id, eggs, true)
+		.unwrap();
+	assert_amount(
+		&meal_plan.pantry_item("Eggs").unwrap().amount,
+		3.0,
+		Units::Count,
+	);
+
+	// checking it twice doesn't buy it twice
+	meal_plan
+		.set_shopping_list_item(shopping_list_id, eggs, true)
+		.unwrap();
+	assert_amount(
+		&meal_plan.pantry_item("Eggs").unwrap().amount,
+		3.0,
+		Units::Count,
+	);
+
+	meal_plan
+		.set_shopping_list_item(shopping_list_id, eggs, false)
+		.unwrap();
+	assert!(meal_plan.pantry_item("Eggs").is_none());
+}
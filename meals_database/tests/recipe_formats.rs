@@ -0,0 +1,111 @@
+use meals_database::{
+	export_recipes, import_recipes, Amount, Diet, Ingredient, MealInfo, RecipeFormat, RecipeStep,
+	Units,
+};
+use uuid::Uuid;
+
+fn meal_info() -> MealInfo {
+	MealInfo {
+		cook_minutes: Some(20),
+		course: "Dinner".into(),
+		cuisine: "Chinese".into(),
+		diets: vec![Diet::Vegetarian],
+		id: Uuid::new_v4(),
+		image: "https://example.com/fried-rice.jpg".into(),
+		ingredients: vec![
+			Ingredient {
+				amount: Amount::new(0.3, Units::Cup),
+				name: "rice".into(),
+			},
+			Ingredient {
+				amount: Amount::new(3.0, Units::Count),
+				name: "eggs".into(),
+			},
+		],
+		name: "Fried Rice".into(),
+		prep_minutes: Some(10),
+		recipe: vec![
+			RecipeStep {
+				description: "Cook the rice.".into(),
+			},
+			RecipeStep {
+				description: "Fry it with the eggs.".into(),
+			},
+		],
+		serving_size: 4,
+		tags: vec!["Quick".into(), "Leftovers".into()],
+	}
+}
+
+#[test]
+fn round_trips() {
+	let original = meal_info();
+	for format in RecipeFormat::ALL {
+		let exported = export_recipes([&original, &original], format);
+		let meals = import_recipes(&exported, format).unwrap();
+		assert_eq!(meals.len(), 2, "{}", format);
+
+		let meal = &meals[0];
+		assert_ne!(meal.id, original.id);
+		assert_eq!(meal.name, original.name, "{}", format);
+		assert_eq!(meal.serving_size, 4, "{}", format);
+		assert_eq!(meal.image, original.image, "{}", format);
+		assert_eq!(meal.prep_minutes, Some(10), "{}", format);
+		assert_eq!(meal.cook_minutes, Some(20), "{}", format);
+		assert_eq!(meal.tags, original.tags, "{}", format);
+		assert_eq!(meal.recipe.len(), 2, "{}", format);
+		assert_eq!(meal.recipe[1].description, "Fry it with the eggs.");
+		assert_eq!(meal.ingredients.len(), 2, "{}", format);
+		assert_eq!(meal.ingredients[0].name, "rice", "{}", format);
+		for (ingredient, original) in meal.ingredients.iter().zip(original.ingredients.iter()) {
+			assert_eq!(ingredient.amount.value, original.amount.value, "{}", format);
+			assert_eq!(ingredient.amount.units, original.amount.units, "{}", format);
+		}
+
+		// Paprika has nowhere to keep these
+		if format != RecipeFormat::Paprika {
+			assert_eq!(meal.course, "Dinner", "{}", format);
+			assert_eq!(meal.cuisine, "Chinese", "{}", format);
+			assert_eq!(meal.diets, vec![Diet::Vegetarian], "{}", format);
+		}
+	}
+
+	// amounts aren't rounded to the nearest kitchen fraction on the way out
+	let exported = export_recipes([&original], RecipeFormat::Markdown);
+	assert!(exported.contains("0.3 cup rice"), "{}", exported);
+
+	assert!(import_recipes("[]", RecipeFormat::JsonLd).is_err());
+	assert!(import_recipes("no recipes here", RecipeFormat::Markdown).is_err());
+}
+
+#[test]
+fn imports_mealie() {
+	let mealie = r#"{
+		"recipes": [{
+			"name": "Pancakes",
+			"recipeYield": "Makes 8 pancakes",
+			"prepTime": "1 hr 5 mins",
+			"cookTime": "PT15M",
+			"tags": [{ "name": "Breakfast" }],
+			"recipeIngredient": [
+				{ "display": "1 cup flour", "note": "" },
+				"2 eggs"
+			],
+			"recipeInstructions": [{ "text": "Mix." }, { "text": "Fry." }]
+		}]
+	}"#;
+
+	let meals = import_recipes(mealie, RecipeFormat::Paprika).unwrap();
+	assert_eq!(meals.len(), 1);
+
+	let meal = &meals[0];
+	assert_eq!(meal.name, "Pancakes");
+	assert_eq!(meal.serving_size, 8);
+	assert_eq!(meal.prep_minutes, Some(65));
+	assert_eq!(meal.cook_minutes, Some(15));
+	assert_eq!(meal.tags, vec!["Breakfast".to_string()]);
+	assert_eq!(meal.ingredients[0].name, "flour");
+	assert_eq!(meal.ingredients[1].amount.value, 2.0);
+	assert_eq!(meal.ingredients[1].amount.units, Units::Count);
+	assert_eq!(meal.recipe.len(), 2);
+}
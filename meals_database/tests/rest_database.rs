@@ -0,0 +1,204 @@
+use meals_database::{
+	ChangeResponse, Database, DatabaseError, MealPlan, MealPlanChange, RestDatabase,
+};
+use serde_json::json;
+use std::{
+	path::PathBuf,
+	sync::{Arc, Mutex},
+};
+use tokio::{
+	io::{AsyncReadExt, AsyncWriteExt},
+	net::TcpListener,
+};
+use uuid::Uuid;
+
+/// The `If-Match` revisions of the writes the fake server accepted and rejected.
+#[derive(Default)]
+struct Writes {
+	accepted: Vec<u64>,
+	rejected: Vec<u64>,
+}
+
+/// Serves a meal plan at `revision`, accepting writes made against the current revision only.
+async fn serve(revision: u64) -> (String, Arc<Mutex<Writes>>) {
+	let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+	let url = format!("http://{}", listener.local_addr().unwrap());
+	let writes = Arc::new(Mutex::new(Writes::default()));
+
+	let server_writes = writes.clone();
+	tokio::spawn(async move {
+		let mut revision = revision;
+		loop {
+			let (mut stream, _) = listener.accept().await.unwrap();
+
+			let mut request = vec![];
+			let mut buffer = [0; 4096];
+			let head = loop {
+				let read = stream.read(&mut buffer).await.unwrap();
+				request.extend_from_slice(&buffer[..read]);
+
+				let text = String::from_utf8_lossy(&request).to_string();
+				if let Some((head, body)) = text.split_once("\r\n\r\n") {
+					let length = head
+						.lines()
+						.find_map(|line| {
+							line.to_lowercase()
+								.strip_prefix("content-length:")
+								.map(|length| length.trim().parse::<usize>().unwrap())
+						})
+						.unwrap_or(0);
+
+					if body.len() >= length {
+						break head.to_string();
+					}
+				}
+			};
+
+			let (status, body) = if head.starts_with("GET /meals/all") {
+				let meal_plan = MealPlan {
+					revision,
+					..Default::default()
+				};
+
+				("200 OK", json!(meal_plan))
+			} else {
+				let if_match = head
+					.lines()
+					.find_map(|line| {
+						line.to_lowercase()
+							.strip_prefix("if-match:")
+							.map(|value| value.trim().trim_matches('"').parse::<u64>().unwrap())
+					})
+					.unwrap();
+
+				let mut writes = server_writes.lock().unwrap();
+				if if_match == revision {
+					writes.accepted.push(if_match);
+					revision += 1;
+					(
+						"200 OK",
+						json!(ChangeResponse {
+							change: None,
+							revision
+						}),
+					)
+				} else {
+					writes.rejected.push(if_match);
+					("409 Conflict", json!({}))
+				}
+			};
+
+			let body = body.to_string();
+			let response = format!(
+				"HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+				status,
+				body.len(),
+				body
+			);
+			stream.write_all(response.as_bytes()).await.unwrap();
+		}
+	});
+
+	(url, writes)
+}
+
+/// A data directory with an auth token and changes queued while offline at `base_revision`.
+fn data_directory(base_revision: u64, changes: usize) -> PathBuf {
+	let data_directory = std::env::temp_dir().join(format!("meals-rest-{}", Uuid::new_v4()));
+	std::fs::create_dir_all(&data_directory).unwrap();
+	std::fs::write(data_directory.join("auth-token"), "token").unwrap();
+
+	let queue = (0..changes)
+		.map(|days| {
+			json!({
+				"base_revision": base_revision,
+				"change": MealPlanChange::MealsShifted { days: days as i64 + 1 },
+			})
+		})
+		.collect::<Vec<_>>();
+	std::fs::write(
+		data_directory.join("meals-queue.json"),
+		json!(queue).to_string(),
+	)
+	.unwrap();
+
+	data_directory
+}
+
+#[tokio::test]
+async fn replays_queued_changes_in_order() {
+	let (url, writes) = serve(5).await;
+	let database =
+		RestDatabase::with_data_directory(&url, "ws://127.0.0.1:1", data_directory(5, 2));
+
+	database.load().await.unwrap();
+
+	// the second change was made on top of the first, so it goes in at the first one's new revision
+	assert_eq!(writes.lock().unwrap().accepted, vec![5, 6]);
+	assert_eq!(database.queued_changes(), 0);
+	assert_eq!(database.get().revision, 7);
+}
+
+#[tokio::test]
+async fn stops_at_conflicting_queued_change() {
+	let (url, writes) = serve(7).await;
+	let database =
+		RestDatabase::with_data_directory(&url, "ws://127.0.0.1:1", data_directory(5, 1));
+
+	assert!(matches!(
+		database.load().await,
+		Err(DatabaseError::Conflict)
+	));
+	assert!(writes.lock().unwrap().accepted.is_empty());
+	assert_eq!(writes.lock().unwrap().rejected, vec![5]);
+	assert_eq!(database.queued_changes(), 1);
+
+	// trying again doesn't clobber the server either
+	assert!(matches!(
+		database.load().await,
+		Err(DatabaseError::Conflict)
+	));
+	assert_eq!(database.queued_changes(), 1);
+
+	database.overwrite_queued_change().await.unwrap();
+	assert_eq!(writes.lock().unwrap().accepted, vec![7]);
+	assert_eq!(database.queued_changes(), 0);
+}
+
+#[tokio::test]
+async fn queues_changes_made_during_conflict() {
+	let (url, writes) = serve(7).await;
+	let database =
+		RestDatabase::with_data_directory(&url, "ws://127.0.0.1:1", data_directory(5, 1));
+
+	assert!(matches!(
+		database.load().await,
+		Err(DatabaseError::Conflict)
+	));
+
+	// the new change can't go in ahead of the conflicting one, but it isn't thrown away either
+	assert!(matches!(
+		database
+			.apply(MealPlanChange::MealsShifted { days: 3 })
+			.await,
+		Err(DatabaseError::Conflict)
+	));
+	assert_eq!(database.queued_changes(), 2);
+
+	database.overwrite_queued_change().await.unwrap();
+	assert_eq!(writes.lock().unwrap().accepted, vec![7, 8]);
+	assert_eq!(database.queued_changes(), 0);
+}
+
+#[tokio::test]
+async fn reports_missing_auth_token() {
+	let (url, _) = serve(5).await;
+	let data_directory = data_directory(5, 0);
+	std::fs::remove_file(data_directory.join("auth-token")).unwrap();
+
+	let database = RestDatabase::with_data_directory(&url, "ws://127.0.0.1:1", data_directory);
+	assert!(matches!(
+		database.load().await,
+		Err(DatabaseError::InvalidConfig(_))
+	));
+}
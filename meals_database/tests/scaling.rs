@@ -0,0 +1,143 @@
+use chrono::NaiveDate;
+use meals_database::{Amount, Ingredient, MealInfo, MealPlan, Units};
+use uuid::Uuid;
+
+fn assert_amount(amount: &Amount, value: f32, units: Units) {
+	assert_eq!(amount.units, units, "{} {}", amount.value, amount.units);
+	assert!(
+		(amount.value - value).abs() < 0.01,
+		"{} is not {}",
+		amount.value,
+		value
+	);
+}
+
+fn pancakes() -> MealInfo {
+	MealInfo {
+		id: Uuid::new_v4(),
+		ingredients: vec![
+			Ingredient {
+				amount: Amount::new(1.0, Units::Cup),
+				name: "Flour".into(),
+			},
+			Ingredient {
+				amount: Amount::new(2.0, Units::Teaspoon),
+				name: "Baking powder".into(),
+			},
+			Ingredient {
+				amount: Amount::new(2.0, Units::Count),
+				name: "Eggs".into(),
+			},
+			Ingredient {
+				amount: Amount::new(300.0, Units::Grams),
+				name: "Blueberries".into(),
+			},
+		],
+		serving_size: 4,
+		..Default::default()
+	}
+}
+
+#[test]
+fn promotion() {
+	assert_amount(
+		&Amount::new(48.0, Units::Teaspoon).promoted(),
+		1.0,
+		Units::Cup,
+	);
+	assert_amount(
+		&Amount::new(6.0, Units::Teaspoon).promoted(),
+		2.0,
+		Units::Tablespoon,
+	);
+	assert_amount(
+		&Amount::new(0.125, Units::Cup).promoted(),
+		2.0,
+		Units::Tablespoon,
+	);
+	assert_amount(
+		&Amount::new(1500.0, Units::Grams).promoted(),
+		1.5,
+		Units::Kilograms,
+	);
+	assert_amount(
+		&Amount::new(24.0, Units::Ounce).promoted(),
+		1.5,
+		Units::Pounds,
+	);
+	assert_amount(
+		&Amount::new(3.0, Units::Count).promoted(),
+		3.0,
+		Units::Count,
+	);
+}
+
+#[test]
+fn scaled_ingredients() {
+	let meal_info = pancakes();
+
+	let ingredients = meal_info.scaled_ingredients(12);
+	assert_amount(&ingredients[0].amount, 3.0, Units::Cup);
+	assert_amount(&ingredients[1].amount, 2.0, Units::Tablespoon);
+	assert_amount(&ingredients[2].amount, 6.0, Units::Count);
+	assert_amount(&ingredients[3].amount, 900.0, Units::Grams);
+
+	let ingredients = meal_info.scaled_ingredients(2);
+	assert_amount(&ingredients[0].amount, 0.5, Units::Cup);
+	assert_amount(&ingredients[1].amount, 1.0, Units::Teaspoon);
+
+	// recipes without a serving size are left alone
+	let meal_info = MealInfo {
+		serving_size: 0,
+		..pancakes()
+	};
+
+	assert_amount(&meal_info.scaled_ingredients(8)[0].amount, 1.0, Units::Cup);
+}
+
+#[test]
+fn shopping_list_uses_servings() {
+	let mut meal_plan = MealPlan::default();
+	let meal_info = pancakes();
+	let date = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+
+	let mut meal_stub = meal_info.new_stub(date);
+	meal_stub.servings = Some(8);
+
+	let mut leftovers = meal_info.new_stub(date.succ_opt().unwrap());
+	leftovers.leftovers = true;
+
+	meal_plan.upsert_meal(meal_info.clone());
+	meal_plan.plan_meal(meal_stub).unwrap();
+	meal_plan
+		.plan_meal(meal_info.new_stub(date.pred_opt().unwrap()))
+		.unwrap();
+	meal_plan.plan_meal(leftovers).unwrap();
+
+	let shopping_list = meal_plan.generate_shopping_list(date).unwrap();
+	let item = |name: &str| {
+		&shopping_list
+			.items
+			.iter()
+			.find(|item| item.name == name)
+			.unwrap()
+			.amount
+	};
+
+	assert_amount(item("Flour"), 3.0, Units::Cup);
+	assert_amount(item("Baking powder"), 2.0, Units::Tablespoon);
+	assert_amount(item("Eggs"), 6.0, Units::Count);
+	assert_amount(item("Blueberries"), 900.0, Units::Grams);
+
+	let shopping_list = meal_plan
+		.generate_shopping_list_for_meal(date, meal_info.id, date)
+		.unwrap();
+
+	let flour = shopping_list
+		.items
+		.iter()
+		.find(|item| item.name == "Flour")
+		.unwrap();
+
+	assert_amount(&flour.amount, 2.0, Units::Cup);
+}
@@ -0,0 +1,165 @@
+use meals_database::{extract_recipe_json, parse_recipe_html, Diet, ScraperError, Units};
+
+const GRAPH_URL: &str = "https://www.simmerandsauce.com/weeknight-chicken-curry/?utm_source=feed";
+const LIST_URL: &str = "https://pantrynotes.example/recipes/lemon-garlic-pasta";
+const MICRODATA_URL: &str = "http://www.oldfamilyrecipes.example/banana-bread.html";
+
+#[test]
+fn json_ld_graph() {
+	let recipe_json =
+		extract_recipe_json(include_str!("fixtures/json_ld_graph.html"), GRAPH_URL).unwrap();
+
+	assert_eq!(recipe_json.title, "Weeknight Chicken Curry");
+	assert_eq!(recipe_json.author, "Dana Ortiz");
+	assert_eq!(
+		recipe_json.description,
+		"A quick & creamy curry that's on the table in 30 minutes."
+	);
+	assert_eq!(
+		recipe_json.image,
+		"https://www.simmerandsauce.com/wp-content/uploads/curry-1x1.jpg"
+	);
+	assert_eq!(
+		recipe_json.canonical_url,
+		"https://www.simmerandsauce.com/weeknight-chicken-curry/"
+	);
+	assert_eq!(recipe_json.host, "simmerandsauce.com");
+	assert_eq!(recipe_json.category, "Dinner, Main Course");
+	assert_eq!(recipe_json.cuisine, "Indian");
+	assert_eq!(recipe_json.yields, "4");
+	assert_eq!(
+		recipe_json.ingredients,
+		vec![
+			"2 tablespoons vegetable oil",
+			"1 unit onion, diced",
+			"3 clove garlic, minced",
+			"1 cup coconut milk",
+			"1 ½ cups chicken stock",
+			"1 pound chicken thighs",
+		]
+	);
+	assert_eq!(
+		recipe_json.instructions_list,
+		vec![
+			"Heat the oil in a large pan over medium heat.",
+			"Add the onion and garlic and cook until soft, about 5 minutes.",
+			"Stir in the coconut milk and stock, then add the chicken.",
+			"Simmer for 20 minutes – until the chicken is cooked through.",
+		]
+	);
+}
+
+#[test]
+fn json_ld_graph_normalized() {
+	let recipe = parse_recipe_html(include_str!("fixtures/json_ld_graph.html"), GRAPH_URL).unwrap();
+
+	assert_eq!(recipe.name, "Weeknight Chicken Curry");
+	assert_eq!(recipe.recipe.len(), 4);
+
+	let ingredients = recipe
+		.ingredients
+		.iter()
+		.map(|ingredient| {
+			let amount = ingredient.amount.clone().unwrap();
+			(
+				amount.value,
+				amount.units,
+				ingredient.name.as_str(),
+				ingredient.confident,
+			)
+		})
+		.collect::<Vec<_>>();
+
+	assert_eq!(
+		ingredients,
+		vec![
+			(2.0, Units::Tablespoon, "vegetable oil", true),
+			(1.0, Units::Count, "onion, diced", true),
+			(3.0, Units::Teaspoon, "garlic, minced", true),
+			(1.0, Units::Cup, "coconut milk", true),
+			(1.5, Units::Cup, "chicken stock", true),
+			(1.0, Units::Pounds, "chicken thighs", true),
+		]
+	);
+}
+
+#[test]
+fn json_ld_list() {
+	let recipe_json =
+		extract_recipe_json(include_str!("fixtures/json_ld_list.html"), LIST_URL).unwrap();
+
+	assert_eq!(recipe_json.title, "Lemon Garlic Pasta");
+	assert_eq!(recipe_json.author, "Sam Lee, Robin Park");
+	assert_eq!(
+		recipe_json.image,
+		"https://pantrynotes.example/images/lemon-pasta.jpg"
+	);
+	assert_eq!(recipe_json.canonical_url, LIST_URL);
+	assert_eq!(recipe_json.host, "pantrynotes.example");
+	assert_eq!(recipe_json.category, "Pasta");
+	assert_eq!(recipe_json.yields, "2");
+	assert_eq!(recipe_json.ingredients.len(), 4);
+	assert_eq!(
+		recipe_json.instructions_list,
+		vec![
+			"Boil the spaghetti until al dente.",
+			"Melt the butter with the garlic.",
+			"Toss everything together with the lemon zest.",
+		]
+	);
+}
+
+#[test]
+fn json_ld_list_details() {
+	let recipe = parse_recipe_html(include_str!("fixtures/json_ld_list.html"), LIST_URL).unwrap();
+
+	assert_eq!(recipe.course, "Pasta");
+	assert_eq!(recipe.cuisine, "Italian");
+	assert_eq!(recipe.prep_minutes, Some(10));
+	assert_eq!(recipe.cook_minutes, Some(65));
+	assert_eq!(recipe.tags, vec!["quick", "weeknight"]);
+	assert_eq!(recipe.diets, vec![Diet::Vegetarian]);
+}
+
+#[test]
+fn microdata() {
+	let recipe_json =
+		extract_recipe_json(include_str!("fixtures/microdata.html"), MICRODATA_URL).unwrap();
+
+	assert_eq!(recipe_json.title, "Grandma’s Banana Bread");
+	assert_eq!(recipe_json.author, "Margaret Hill");
+	assert_eq!(
+		recipe_json.description,
+		"Moist, simple, and perfect for overripe bananas."
+	);
+	assert_eq!(
+		recipe_json.image,
+		"http://oldfamilyrecipes.example/img/banana-bread.jpg"
+	);
+	assert_eq!(recipe_json.host, "oldfamilyrecipes.example");
+	assert_eq!(recipe_json.category, "Baking");
+	assert_eq!(recipe_json.yields, "1 loaf");
+	assert_eq!(
+		recipe_json.ingredients,
+		vec![
+			"3 unit ripe bananas",
+			"2 cup flour",
+			"1 teaspoon baking soda",
+			"1/2 cup sugar",
+		]
+	);
+	assert_eq!(
+		recipe_json.instructions_list,
+		vec![
+			"Preheat the oven to 350°F.",
+			"Mash the bananas & mix in the remaining ingredients.",
+			"Bake for 60 minutes.",
+		]
+	);
+}
+
+#[test]
+fn no_recipe() {
+	let result = extract_recipe_json(include_str!("fixtures/no_recipe.html"), LIST_URL);
+	assert!(matches!(result, Err(ScraperError::NoRecipe)));
+}
@@ -0,0 +1,103 @@
+use meals_database::{Amount, Ingredient, MealInfo, MealPlan, RecipeStep, Units};
+use uuid::Uuid;
+
+fn meal(name: &str, ingredients: &[&str], steps: &[&str], tags: &[&str]) -> MealInfo {
+	MealInfo {
+		id: Uuid::new_v4(),
+		ingredients: ingredients
+			.iter()
+			.map(|name| Ingredient {
+				amount: Amount::new(1.0, Units::Count),
+				name: name.to_string(),
+			})
+			.collect(),
+		name: name.into(),
+		recipe: steps
+			.iter()
+			.map(|description| RecipeStep {
+				description: description.to_string(),
+			})
+			.collect(),
+		serving_size: 1,
+		tags: tags.iter().map(|tag| tag.to_string()).collect(),
+		..Default::default()
+	}
+}
+
+fn meal_plan() -> MealPlan {
+	let mut meal_plan = MealPlan::default();
+	meal_plan.upsert_meal(meal(
+		"Chicken Noodle Soup",
+		&["Chicken thighs", "Egg noodles", "Carrots"],
+		&["Simmer the chicken in broth."],
+		&["Comfort"],
+	));
+	meal_plan.upsert_meal(meal(
+		"Curry",
+		&["Chicken breast", "Coconut milk"],
+		&["Toast the spices, then braise."],
+		&["Weeknight"],
+	));
+	meal_plan.upsert_meal(meal(
+		"Tomato Soup",
+		&["Tomatoes", "Cream"],
+		&["Blend until smooth."],
+		&["Comfort", "Vegetarian"],
+	));
+
+	meal_plan
+}
+
+fn names(meal_plan: &MealPlan, query: &str) -> Vec<String> {
+	meal_plan
+		.search(query)
+		.into_iter()
+		.map(|result| result.name)
+		.collect()
+}
+
+#[test]
+fn ranks_names_first() {
+	let meal_plan = meal_plan();
+
+	// both mention chicken, but only one has it in the name
+	assert_eq!(
+		names(&meal_plan, "chicken"),
+		vec!["Chicken Noodle Soup", "Curry"]
+	);
+	assert_eq!(
+		names(&meal_plan, "soup"),
+		vec!["Chicken Noodle Soup", "Tomato Soup"]
+	);
+	assert_eq!(names(&meal_plan, "tmato"), vec!["Tomato Soup"]);
+	assert!(names(&meal_plan, "lasagna").is_empty());
+
+	// an empty query lists every meal by name
+	assert_eq!(
+		names(&meal_plan, "  "),
+		vec!["Chicken Noodle Soup", "Curry", "Tomato Soup"]
+	);
+}
+
+#[test]
+fn fields() {
+	let meal_plan = meal_plan();
+
+	assert_eq!(names(&meal_plan, "ingredient:coconut"), vec!["Curry"]);
+	assert!(names(&meal_plan, "name:coconut").is_empty());
+	assert_eq!(
+		names(&meal_plan, "tag:comfort"),
+		vec!["Chicken Noodle Soup", "Tomato Soup"]
+	);
+	assert_eq!(names(&meal_plan, "step:braise"), vec!["Curry"]);
+
+	// every term has to match
+	assert_eq!(
+		names(&meal_plan, "soup ingredient:chicken"),
+		vec!["Chicken Noodle Soup"]
+	);
+	assert_eq!(
+		names(&meal_plan, r#"ingredient:"chicken breast""#),
+		vec!["Curry"]
+	);
+}
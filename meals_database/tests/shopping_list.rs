@@ -0,0 +1,128 @@
+use meals_database::{Amount, MealPlan, ShoppingListInfo, ShoppingListItem, Units};
+
+fn assert_amount(amount: &Amount, value: f32, units: Units) {
+	assert_eq!(amount.units, units, "{} {}", amount.value, amount.units);
+	assert!(
+		(amount.value - value).abs() < 0.01,
+		"{} is not {}",
+		amount.value,
+		value
+	);
+}
+
+fn meal_plan() -> MealPlan {
+	let mut meal_plan = MealPlan::default();
+	meal_plan.shopping_list.push(ShoppingListInfo {
+		items: vec![ShoppingListItem::new(
+			"Eggs",
+			Amount::new(6.0, Units::Count),
+		)],
+		..Default::default()
+	});
+	meal_plan.shopping_list.push(ShoppingListInfo {
+		items: vec![
+			ShoppingListItem::new("Eggs", Amount::new(6.0, Units::Count)),
+			ShoppingListItem::new("Butter", Amount::new(0.5, Units::Cup)),
+		],
+		name: "Party".into(),
+		..Default::default()
+	});
+
+	meal_plan
+}
+
+#[test]
+fn add_edit_remove_items() {
+	let mut meal_plan = meal_plan();
+	let list_id = meal_plan.shopping_list[0].id;
+
+	// adding something already on the list tops it up
+	meal_plan
+		.add_shopping_list_item(
+			list_id,
+			ShoppingListItem::new("eggs", Amount::new(2.0, Units::Count)),
+		)
+		.unwrap();
+	assert_eq!(meal_plan.shopping_list[0].items.len(), 1);
+	assert_amount(
+		&meal_plan.shopping_list[0].items[0].amount,
+		8.0,
+		Units::Count,
+	);
+
+	let flour = ShoppingListItem::new("Flour", Amount::new(2.0, Units::Cup));
+	let id = flour.id;
+	meal_plan.add_shopping_list_item(list_id, flour).unwrap();
+
+	meal_plan
+		.edit_shopping_list_item(
+			list_id,
+			ShoppingListItem {
+				id,
+				..ShoppingListItem::new("Bread flour", Amount::new(3.0, Units::Cup))
+			},
+		)
+		.unwrap();
+
+	let item = meal_plan.shopping_list[0]
+		.items
+		.iter()
+		.find(|item| item.id == id)
+		.unwrap();
+	assert_eq!(item.name, "Bread flour");
+	assert_amount(&item.amount, 3.0, Units::Cup);
+
+	assert_eq!(
+		meal_plan
+			.remove_shopping_list_item(list_id, id)
+			.unwrap()
+			.name,
+		"Bread flour"
+	);
+	assert!(meal_plan.remove_shopping_list_item(list_id, id).is_err());
+	assert!(meal_plan.set_shopping_list_item(list_id, id, true).is_err());
+}
+
+#[test]
+fn rename_and_merge_lists() {
+	let mut meal_plan = meal_plan();
+	let weekly = meal_plan.shopping_list[0].id;
+	let party = meal_plan.shopping_list[1].id;
+
+	meal_plan.rename_shopping_list(weekly, " Weekly ").unwrap();
+	assert_eq!(meal_plan.shopping_list[0].name, "Weekly");
+
+	meal_plan.merge_shopping_lists(party, weekly).unwrap();
+	assert_eq!(meal_plan.shopping_list.len(), 1);
+
+	let shopping_list = &meal_plan.shopping_list[0];
+	assert_eq!(shopping_list.id, weekly);
+	assert_eq!(shopping_list.name, "Weekly");
+	assert_eq!(shopping_list.items.len(), 2);
+
+	let eggs = shopping_list
+		.items
+		.iter()
+		.find(|item| item.name == "Eggs")
+		.unwrap();
+	assert_amount(&eggs.amount, 12.0, Units::Count);
+
+	assert!(meal_plan.merge_shopping_lists(weekly, party).is_err());
+}
+
+#[test]
+fn old_lists_get_ids() {
+	let shopping_list: ShoppingListInfo = serde_json::from_str(
+		r#"{
+			"items": [
+				{ "amount": { "units": "Count", "value": 6.0 }, "have": false, "name": "Eggs" },
+				{ "amount": { "units": "Cup", "value": 0.5 }, "have": true, "name": "Butter" }
+			]
+		}"#,
+	)
+	.unwrap();
+
+	assert!(!shopping_list.id.is_nil());
+	assert!(!shopping_list.items[0].id.is_nil());
+	assert_ne!(shopping_list.items[0].id, shopping_list.items[1].id);
+}
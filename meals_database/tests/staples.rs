@@ -0,0 +1,139 @@
+use chrono::NaiveDate;
+use meals_database::{
+	Amount, Frequency, Ingredient, MealInfo, MealPlan, ShoppingListItem, Staple, Units,
+};
+use uuid::Uuid;
+
+fn assert_amount(amount: &Amount, value: f32, units: Units) {
+	assert_eq!(amount.units, units, "{} {}", amount.value, amount.units);
+	assert!(
+		(amount.value - value).abs() < 0.01,
+		"{} is not {}",
+		amount.value,
+		value
+	);
+}
+
+fn today() -> NaiveDate {
+	NaiveDate::from_ymd_opt(2024, 1, 1).unwrap()
+}
+
+fn milk() -> Staple {
+	Staple {
+		amount: Amount::new(1.0, Units::Liters),
+		frequency: Frequency::Weekly,
+		last_added: None,
+		name: "Milk".into(),
+	}
+}
+
+#[test]
+fn frequencies() {
+	let date = NaiveDate::from_ymd_opt(2024, 1, 31).unwrap();
+
+	assert_eq!(Frequency::EveryTrip.next_due(date), date);
+	assert_eq!(
+		Frequency::Weekly.next_due(date),
+		NaiveDate::from_ymd_opt(2024, 2, 7).unwrap()
+	);
+	assert_eq!(
+		Frequency::EveryOtherWeek.next_due(date),
+		NaiveDate::from_ymd_opt(2024, 2, 14).unwrap()
+	);
+	assert_eq!(
+		Frequency::Monthly.next_due(date),
+		NaiveDate::from_ymd_opt(2024, 2, 29).unwrap()
+	);
+
+	let staple = Staple {
+		last_added: Some(date),
+		..milk()
+	};
+
+	assert!(!staple.is_due(NaiveDate::from_ymd_opt(2024, 2, 6).unwrap()));
+	assert!(staple.is_due(NaiveDate::from_ymd_opt(2024, 2, 7).unwrap()));
+}
+
+#[test]
+fn staples_join_shopping_list() {
+	let mut meal_plan = MealPlan::default();
+	meal_plan.save_staple(milk());
+	meal_plan.save_staple(Staple {
+		amount: Amount::new(1.0, Units::Count),
+		frequency: Frequency::Monthly,
+		last_added: Some(today()),
+		name: "Paper towels".into(),
+	});
+
+	let meal_info = MealInfo {
+		id: Uuid::new_v4(),
+		ingredients: vec![Ingredient {
+			amount: Amount::new(250.0, Units::Milliliters),
+			name: "milk".into(),
+		}],
+		serving_size: 1,
+		..Default::default()
+	};
+
+	meal_plan.upsert_meal(meal_info.clone());
+	meal_plan
+		.plan_meal(meal_info.new_stub(NaiveDate::from_ymd_opt(2024, 1, 1).unwrap()))
+		.unwrap();
+
+	// the recipe's milk is added to the staple's, and the paper towels aren't due yet
+	let shopping_list = meal_plan.generate_shopping_list(today()).unwrap();
+	assert_eq!(shopping_list.items.len(), 1);
+	assert_amount(&shopping_list.items[0].amount, 1.25, Units::Liters);
+
+	meal_plan.add_shopping_list(shopping_list);
+	assert!(!meal_plan.staple("milk").unwrap().is_due(today()));
+	assert!(meal_plan.generate_shopping_list(today()).is_none());
+}
+
+#[test]
+fn extra_items_join_next_shopping_list() {
+	let mut meal_plan = MealPlan::default();
+	meal_plan.add_extra_item(ShoppingListItem::new(
+		"Eggs",
+		Amount::new(6.0, Units::Count),
+	));
+	meal_plan.add_extra_item(ShoppingListItem::new(
+		"eggs",
+		Amount::new(6.0, Units::Count),
+	));
+
+	assert_eq!(meal_plan.extra_items.len(), 1);
+
+	let shopping_list = meal_plan.generate_shopping_list(today()).unwrap();
+	assert_eq!(shopping_list.items[0].name, "Eggs");
+	assert_amount(&shopping_list.items[0].amount, 12.0, Units::Count);
+
+	meal_plan.add_shopping_list(shopping_list);
+	assert!(meal_plan.extra_items.is_empty());
+	assert!(meal_plan.remove_extra_item("Eggs").is_err());
+}
+
+#[test]
+fn shopping_list_keeps_extra_items_added_since() {
+	let mut meal_plan = MealPlan::default();
+	meal_plan.add_extra_item(ShoppingListItem::new(
+		"Eggs",
+		Amount::new(6.0, Units::Count),
+	));
+
+	// made offline, then replayed after someone else added more extra items
+	let shopping_list = meal_plan.generate_shopping_list(today()).unwrap();
+	meal_plan.add_extra_item(ShoppingListItem::new(
+		"Eggs",
+		Amount::new(4.0, Units::Count),
+	));
+	meal_plan.add_extra_item(ShoppingListItem::new(
+		"Bread",
+		Amount::new(1.0, Units::Count),
+	));
+
+	meal_plan.add_shopping_list(shopping_list);
+	assert_eq!(meal_plan.extra_items.len(), 2);
+	assert_amount(&meal_plan.extra_items[0].amount, 4.0, Units::Count);
+	assert_eq!(meal_plan.extra_items[1].name, "Bread");
+}
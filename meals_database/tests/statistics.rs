@@ -0,0 +1,80 @@
+use chrono::NaiveDate;
+use meals_database::{Amount, Ingredient, MealInfo, MealPlan, Units};
+use uuid::Uuid;
+
+fn date(day: u32) -> NaiveDate {
+	NaiveDate::from_ymd_opt(2024, 1, day).unwrap()
+}
+
+fn meal(name: &str, eggs: f32) -> MealInfo {
+	MealInfo {
+		id: Uuid::new_v4(),
+		ingredients: vec![Ingredient {
+			amount: Amount::new(eggs, Units::Count),
+			name: "Eggs".into(),
+		}],
+		name: name.into(),
+		serving_size: 1,
+		..Default::default()
+	}
+}
+
+fn cook(meal_plan: &mut MealPlan, meal_info: &MealInfo, day: u32) {
+	meal_plan.plan_meal(meal_info.new_stub(date(day))).unwrap();
+	meal_plan.complete_meal(date(day), meal_info.id).unwrap();
+}
+
+#[test]
+fn counts_and_streaks() {
+	let mut meal_plan = MealPlan::default();
+	let omelette = meal("Omelette", 3.0);
+	let quiche = meal("Quiche", 4.0);
+	let salad = meal("Salad", 0.0);
+	for meal_info in [&omelette, &quiche, &salad] {
+		meal_plan.upsert_meal(meal_info.clone());
+	}
+
+	cook(&mut meal_plan, &omelette, 1);
+	cook(&mut meal_plan, &omelette, 2);
+	cook(&mut meal_plan, &quiche, 3);
+	cook(&mut meal_plan, &omelette, 6);
+	cook(&mut meal_plan, &quiche, 7);
+
+	let statistics = meal_plan.statistics(None, Some(date(8)));
+	assert_eq!(statistics.total_cooked, 5);
+	assert_eq!(statistics.longest_streak, 3);
+	assert_eq!(statistics.current_streak, 2);
+
+	let most_cooked = statistics.most_cooked(5).collect::<Vec<_>>();
+	assert_eq!(most_cooked.len(), 2);
+	assert_eq!(most_cooked[0].name, "Omelette");
+	assert_eq!(most_cooked[0].times_cooked, 3);
+	assert_eq!(most_cooked[0].last_cooked, Some(date(6)));
+
+	let least_cooked = statistics.least_cooked(1).next().unwrap();
+	assert_eq!(least_cooked.name, "Salad");
+	assert_eq!(least_cooked.last_cooked, None);
+
+	assert_eq!(statistics.ingredients.len(), 1);
+	assert_eq!(statistics.ingredients[0].amount.value, 17.0);
+}
+
+#[test]
+fn date_range() {
+	let mut meal_plan = MealPlan::default();
+	let omelette = meal("Omelette", 3.0);
+	meal_plan.upsert_meal(omelette.clone());
+
+	cook(&mut meal_plan, &omelette, 1);
+	cook(&mut meal_plan, &omelette, 5);
+	cook(&mut meal_plan, &omelette, 9);
+
+	let statistics = meal_plan.statistics(Some(date(2)), Some(date(8)));
+	assert_eq!(statistics.total_cooked, 1);
+	assert_eq!(statistics.meals[0].last_cooked, Some(date(5)));
+	assert_eq!(statistics.ingredients[0].amount.value, 3.0);
+
+	// nothing on the last day or the day before ends the streak
+	assert_eq!(statistics.current_streak, 0);
+	assert_eq!(statistics.longest_streak, 1);
+}
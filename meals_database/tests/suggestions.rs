@@ -0,0 +1,106 @@
+use chrono::NaiveDate;
+use meals_database::{
+	Amount, Ingredient, MealInfo, MealPlan, ShoppingListInfo, ShoppingListItem, SuggestionOptions,
+	Units,
+};
+use rand::{rngs::StdRng, SeedableRng};
+use uuid::Uuid;
+
+fn date(day: u32) -> NaiveDate {
+	NaiveDate::from_ymd_opt(2024, 1, day).unwrap()
+}
+
+fn meal(name: &str, ingredient: &str) -> MealInfo {
+	MealInfo {
+		id: Uuid::new_v4(),
+		ingredients: vec![Ingredient {
+			amount: Amount::new(1.0, Units::Count),
+			name: ingredient.into(),
+		}],
+		name: name.into(),
+		serving_size: 1,
+		..Default::default()
+	}
+}
+
+fn weight(meal_plan: &MealPlan, id: Uuid, options: &SuggestionOptions) -> f32 {
+	meal_plan
+		.suggestion_weights(date(20), options)
+		.into_iter()
+		.find(|(meal_info, _)| meal_info.id == id)
+		.unwrap()
+		.1
+}
+
+#[test]
+fn weights() {
+	let mut meal_plan = MealPlan::default();
+	let omelette = meal("Omelette", "Eggs");
+	let pasta = meal("Pasta", "Noodles");
+	let salad = meal("Salad", "Lettuce");
+	for meal_info in [&omelette, &pasta, &salad] {
+		meal_plan.upsert_meal(meal_info.clone());
+	}
+
+	// cooked recently, so less likely than something never cooked
+	meal_plan.plan_meal(omelette.new_stub(date(15))).unwrap();
+	meal_plan.complete_meal(date(15), omelette.id).unwrap();
+
+	// the 20th is a saturday, so the 17th is the same week
+	meal_plan.plan_meal(pasta.new_stub(date(17))).unwrap();
+
+	let mut options = SuggestionOptions::default();
+	assert!(weight(&meal_plan, omelette.id, &options) < weight(&meal_plan, salad.id, &options));
+	assert_eq!(weight(&meal_plan, pasta.id, &options), 0.0);
+
+	meal_plan.shopping_list.push(ShoppingListInfo {
+		items: vec![ShoppingListItem::new(
+			"eggs",
+			Amount::new(6.0, Units::Count),
+		)],
+		..Default::default()
+	});
+
+	let before = weight(&meal_plan, omelette.id, &options);
+	options.prefer_shopping_list = true;
+	assert_eq!(weight(&meal_plan, omelette.id, &options), before * 2.0);
+
+	options.exclude.insert(salad.id);
+	assert_eq!(weight(&meal_plan, salad.id, &options), 0.0);
+}
+
+#[test]
+fn rerolls_skip_excluded() {
+	let mut meal_plan = MealPlan::default();
+	let omelette = meal("Omelette", "Eggs");
+	let pasta = meal("Pasta", "Noodles");
+	meal_plan.upsert_meal(omelette.clone());
+	meal_plan.upsert_meal(pasta.clone());
+
+	let mut rng = StdRng::seed_from_u64(0);
+	let mut options = SuggestionOptions::default();
+
+	let first = meal_plan
+		.suggest_meal(date(20), &options, &mut rng)
+		.unwrap()
+		.id;
+	options.exclude.insert(first);
+
+	let second = meal_plan
+		.suggest_meal(date(20), &options, &mut rng)
+		.unwrap()
+		.id;
+	assert_ne!(first, second);
+	options.exclude.insert(second);
+
+	assert!(meal_plan
+		.suggest_meal(date(20), &options, &mut rng)
+		.is_none());
+
+	// everything planned that week still leaves something to suggest
+	meal_plan.plan_meal(omelette.new_stub(date(16))).unwrap();
+	meal_plan.plan_meal(pasta.new_stub(date(16))).unwrap();
+	assert!(meal_plan
+		.suggest_meal(date(20), &SuggestionOptions::default(), &mut rng)
+		.is_some());
+}
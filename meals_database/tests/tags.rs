@@ -0,0 +1,107 @@
+use meals_database::{Diet, MealFilter, MealInfo, MealPlan};
+use uuid::Uuid;
+
+fn meal_plan() -> MealPlan {
+	let mut meal_plan = MealPlan::default();
+	meal_plan.upsert_meal(MealInfo {
+		cook_minutes: Some(20),
+		course: "Dinner, Main Course".into(),
+		cuisine: "Indian".into(),
+		id: Uuid::new_v4(),
+		name: "Chana Masala".into(),
+		prep_minutes: Some(5),
+		diets: vec![Diet::Vegan, Diet::GlutenFree],
+		tags: vec!["Weeknight".into()],
+		..Default::default()
+	});
+	meal_plan.upsert_meal(MealInfo {
+		cook_minutes: Some(120),
+		course: "Dinner".into(),
+		cuisine: "italian".into(),
+		id: Uuid::new_v4(),
+		name: "Lasagna".into(),
+		diets: vec![Diet::Vegetarian],
+		..Default::default()
+	});
+	meal_plan.upsert_meal(MealInfo {
+		cuisine: "Italian".into(),
+		id: Uuid::new_v4(),
+		name: "Bolognese".into(),
+		..Default::default()
+	});
+
+	meal_plan
+}
+
+fn names(meal_plan: &MealPlan, filter: MealFilter) -> Vec<String> {
+	meal_plan
+		.filter_meals(&filter)
+		.into_iter()
+		.map(|meal_info| meal_info.name.clone())
+		.collect()
+}
+
+#[test]
+fn filters() {
+	let meal_plan = meal_plan();
+
+	assert_eq!(
+		names(&meal_plan, MealFilter::default()),
+		vec!["Bolognese", "Chana Masala", "Lasagna"]
+	);
+
+	// vegan meals count as vegetarian
+	assert_eq!(
+		names(
+			&meal_plan,
+			MealFilter {
+				diets: vec![Diet::Vegetarian],
+				..Default::default()
+			}
+		),
+		vec!["Chana Masala", "Lasagna"]
+	);
+
+	assert_eq!(
+		names(
+			&meal_plan,
+			MealFilter {
+				course: Some("main course".into()),
+				..Default::default()
+			}
+		),
+		vec!["Chana Masala"]
+	);
+
+	assert_eq!(
+		names(
+			&meal_plan,
+			MealFilter {
+				cuisine: Some("Italian".into()),
+				max_minutes: Some(60),
+				..Default::default()
+			}
+		),
+		Vec::<String>::new()
+	);
+
+	assert_eq!(
+		names(
+			&meal_plan,
+			MealFilter {
+				tag: Some("weeknight".into()),
+				..Default::default()
+			}
+		),
+		vec!["Chana Masala"]
+	);
+}
+
+#[test]
+fn facets() {
+	let meal_plan = meal_plan();
+
+	assert_eq!(meal_plan.courses(), vec!["Dinner", "Main Course"]);
+	assert_eq!(meal_plan.cuisines().len(), 2);
+	assert_eq!(meal_plan.tags(), vec!["Weeknight"]);
+}
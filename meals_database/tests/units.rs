@@ -0,0 +1,112 @@
+use chrono::NaiveDate;
+use meals_database::{Amount, Ingredient, MealInfo, MealPlan, MealStub, Units};
+use uuid::Uuid;
+
+fn assert_close(left: f32, right: f32) {
+	assert!((left - right).abs() < 0.01, "{} is not {}", left, right);
+}
+
+#[test]
+fn conversions() {
+	assert_close(
+		Units::Cup.conversion_factor(&Units::Tablespoon).unwrap(),
+		1.0 / 16.0,
+	);
+	assert_close(
+		Units::Pounds.conversion_factor(&Units::Ounce).unwrap(),
+		1.0 / 16.0,
+	);
+	assert_close(
+		Units::Kilograms.conversion_factor(&Units::Grams).unwrap(),
+		0.001,
+	);
+	assert_close(Units::Quarts.conversion_factor(&Units::Pints).unwrap(), 0.5);
+	assert_close(
+		Units::Liters
+			.conversion_factor(&Units::Milliliters)
+			.unwrap(),
+		0.001,
+	);
+	assert_close(
+		Units::Cup.conversion_factor(&Units::FluidOunces).unwrap(),
+		0.125,
+	);
+
+	assert!(Units::Ounce.is_compatible(&Units::Grams));
+	assert!(!Units::Ounce.is_compatible(&Units::FluidOunces));
+	assert!(Units::Count.conversion_factor(&Units::Grams).is_none());
+}
+
+#[test]
+fn add_with_density() {
+	let cup = Amount::new(1.0, Units::Cup);
+	let grams = Amount::new(200.0, Units::Grams);
+
+	assert!(cup.add(&grams, None).is_none());
+
+	let total = grams.add(&cup, Some(0.5)).unwrap();
+	assert_eq!(total.units, Units::Grams);
+	assert_close(total.value, 200.0 + 236.588 * 0.5);
+
+	let total = cup.add(&grams, Some(0.5)).unwrap();
+	assert_eq!(total.units, Units::Cup);
+	assert_close(total.value, 1.0 + 400.0 / 236.588);
+}
+
+#[test]
+fn densities() {
+	let mut meal_plan = MealPlan::default();
+	assert_eq!(meal_plan.density("All-Purpose Flour"), Some(0.53));
+	assert_eq!(meal_plan.density("light brown sugar"), Some(0.93));
+	assert_eq!(meal_plan.density("licorice"), None);
+
+	meal_plan.set_density("bread flour", Some(0.57));
+	assert_eq!(meal_plan.density("Bread Flour"), Some(0.57));
+	assert_eq!(meal_plan.density("unbleached bread flour"), Some(0.57));
+	assert_eq!(meal_plan.density("flour"), Some(0.53));
+
+	meal_plan.set_density("bread flour", None);
+	assert_eq!(meal_plan.density("bread flour"), Some(0.53));
+}
+
+#[test]
+fn shopping_list_combines_volume_and_weight() {
+	let date = NaiveDate::from_ymd_opt(2024, 8, 1).unwrap();
+	let mut meal_plan = MealPlan::default();
+
+	for amount in [
+		Amount::new(1.0, Units::Cup),
+		Amount::new(200.0, Units::Grams),
+	] {
+		let meal_info = MealInfo {
+			id: Uuid::new_v4(),
+			ingredients: vec![Ingredient {
+				amount,
+				name: "Flour".into(),
+			}],
+			..Default::default()
+		};
+
+		let meal_stub = MealStub {
+			date,
+			id: meal_info.id,
+			leftovers: false,
+			servings: None,
+		};
+
+		meal_plan.upsert_meal(meal_info);
+		meal_plan.plan_meal(meal_stub).unwrap();
+	}
+
+	let shopping_list = meal_plan.generate_shopping_list(date).unwrap();
+	assert_eq!(shopping_list.items.len(), 1);
+
+	let amount = &shopping_list.items[0].amount;
+	let grams = match amount.units {
+		Units::Cup => amount.value * 236.588 * 0.53,
+		Units::Grams => amount.value,
+		_ => panic!("unexpected units {:?}", amount.units),
+	};
+
+	assert_close(grams, 200.0 + 236.588 * 0.53);
+}
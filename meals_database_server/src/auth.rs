@@ -9,8 +9,7 @@ use crate::{Context, Error};
 
 #[derive(Debug)]
 pub struct User {
-	#[allow(dead_code)]
-	name: String,
+	pub name: String,
 }
 
 #[rocket::async_trait]
@@ -53,7 +52,9 @@ impl<'r> FromRequest<'r> for User {
 	}
 }
 
-pub struct RestUser;
+pub struct RestUser {
+	pub name: String,
+}
 
 #[rocket::async_trait]
 impl<'r> FromRequest<'r> for RestUser {
@@ -77,7 +78,9 @@ impl<'r> FromRequest<'r> for RestUser {
 
 		let expected_token = std::fs::read_to_string("./auth-token").unwrap();
 		if split[1] == expected_token.trim() {
-			Outcome::Success(RestUser)
+			Outcome::Success(RestUser {
+				name: "rest".into(),
+			})
 		} else {
 			let error = Error::AuthenticationError {
 				message: "Invalid auth token".into(),
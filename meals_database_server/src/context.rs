@@ -1,3 +1,4 @@
+use meals_database::DatabaseError;
 use std::{collections::HashSet, sync::Arc};
 use tokio::sync::RwLock;
 use uuid::Uuid;
@@ -10,10 +11,10 @@ pub struct Context {
 }
 
 impl Context {
-	pub fn new() -> Self {
-		Self {
-			meals_database: Arc::new(RwLock::new(MealsDatabaseService::new())),
+	pub async fn new() -> Result<Self, DatabaseError> {
+		Ok(Self {
+			meals_database: Arc::new(RwLock::new(MealsDatabaseService::new().await?)),
 			valid_sids: Arc::new(RwLock::new(HashSet::new())),
-		}
+		})
 	}
 }
@@ -1,3 +1,4 @@
+use meals_database::{DatabaseError, MealPlanError, ScraperError};
 use rocket::serde::json::json;
 use rocket::{
 	http::{ContentType, Status},
@@ -15,19 +16,27 @@ pub enum Error {
 	#[error("authentication error")]
 	AuthorizationError { message: String },
 
+	#[error("conflict")]
+	Conflict { message: String },
+
 	#[error("internal server error")]
 	InternalServerError { message: String },
 
+	#[error("not found")]
+	NotFound { message: String },
+
 	#[error("payload problem")]
 	PayloadProblem { message: String },
 }
 
 impl Error {
-	pub fn get_message<'a>(&'a self) -> Option<&'a str> {
+	pub fn get_message(&self) -> Option<&str> {
 		match self {
 			Error::AuthenticationError { message } => Some(message),
 			Error::AuthorizationError { message } => Some(message),
+			Error::Conflict { message } => Some(message),
 			Error::InternalServerError { message } => Some(message),
+			Error::NotFound { message } => Some(message),
 			Error::PayloadProblem { message } => Some(message),
 		}
 	}
@@ -36,7 +45,9 @@ impl Error {
 		match self {
 			Error::AuthenticationError { .. } => Status::Unauthorized,
 			Error::AuthorizationError { .. } => Status::Forbidden,
+			Error::Conflict { .. } => Status::Conflict,
 			Error::InternalServerError { .. } => Status::InternalServerError,
+			Error::NotFound { .. } => Status::NotFound,
 			Error::PayloadProblem { .. } => Status::BadRequest,
 		}
 	}
@@ -66,4 +77,39 @@ impl<'r, 'o: 'r> Responder<'r, 'o> for Error {
 	}
 }
 
+impl From<DatabaseError> for Error {
+	fn from(error: DatabaseError) -> Self {
+		match error {
+			DatabaseError::Conflict => Error::Conflict {
+				message: error.to_string(),
+			},
+			DatabaseError::MealPlan(error) => error.into(),
+			error => Error::InternalServerError {
+				message: format!("Could not save database: {}", error),
+			},
+		}
+	}
+}
+
+impl From<MealPlanError> for Error {
+	fn from(error: MealPlanError) -> Self {
+		Error::NotFound {
+			message: error.to_string(),
+		}
+	}
+}
+
+impl From<ScraperError> for Error {
+	fn from(error: ScraperError) -> Self {
+		match error {
+			ScraperError::NoRecipe => Error::PayloadProblem {
+				message: error.to_string(),
+			},
+			ScraperError::Request(_) => Error::InternalServerError {
+				message: error.to_string(),
+			},
+		}
+	}
+}
+
 pub type Result<T> = std::result::Result<T, Error>;
@@ -1,11 +1,11 @@
 use rocket::catch;
 use rocket::catchers;
-use rocket::launch;
 
 mod auth;
 mod context;
 mod error;
 mod rendering;
+mod revision;
 mod routes;
 mod services;
 
@@ -22,13 +22,29 @@ fn not_authorized() -> Redirect {
 	Redirect::to("/login")
 }
 
-#[launch]
-fn rocket() -> _ {
+#[rocket::main]
+async fn main() {
 	env_logger::init();
-	rocket::build()
-		.manage(context::Context::new())
+
+	let context = match context::Context::new().await {
+		Ok(context) => context,
+		Err(error) => {
+			log::error!("Could not start the meals database: {}", error);
+			std::process::exit(1);
+		}
+	};
+
+	let result = rocket::build()
+		.manage(context)
 		.mount("/rest/", rest_routes())
 		.mount("/ws/", ws_routes())
 		.mount("/", htmx_routes())
 		.register("/", catchers![not_authorized])
+		.launch()
+		.await;
+
+	if let Err(error) = result {
+		log::error!("Could not launch: {}", error);
+		std::process::exit(1);
+	}
 }
@@ -0,0 +1,34 @@
+use maud::{html, Markup};
+use meals_database::CookableMeal;
+
+/// `ingredients` is what the meals were ranked against, shown back in the form.
+pub fn render_cook_with(ingredients: &[String], meals: &[CookableMeal]) -> Markup {
+	html! {
+		div class="flex flex-col items-center gap-4 pt-6 px-4 text-lg" {
+			a href="/" { "Back" }
+
+			form action="/cook-with" class="flex flex-wrap gap-2 items-center justify-center" method="get" {
+				input class="w-[300px]" name="ingredients" placeholder="Checked shopping list items" type="text" value=(ingredients.join(", "));
+				button type="submit" { "Find recipes" }
+			}
+
+			div class="cook-with flex flex-col gap-2 w-full sm:w-[500px] p-2" {
+				@if meals.is_empty() {
+					span class="text-center" { "No recipes use these ingredients" }
+				}
+
+				@for meal in meals {
+					div class="flex flex-col w-full" {
+						div class="flex justify-between gap-2" {
+							span class="font-bold" { (meal.name) }
+							span { (format!("{:.0}%", meal.coverage * 100.0)) }
+						}
+						@if !meal.missing.is_empty() {
+							span class="text-base" { "Missing: " (meal.missing.join(", ")) }
+						}
+					}
+				}
+			}
+		}
+	}
+}
@@ -0,0 +1,40 @@
+use maud::{html, Markup, DOCTYPE};
+use meals_database::{format_amount, MealPlan, ShoppingListInfo};
+
+/// A standalone page without the app's stylesheet or scripts, meant to be printed.
+pub fn render_printable_shopping_list(
+	meal_plan: &MealPlan,
+	shopping_list: &ShoppingListInfo,
+) -> Markup {
+	html! {
+		(DOCTYPE)
+		html {
+			head {
+				title { "Shopping list" }
+				style {
+					"body { font-family: sans-serif; margin: 2rem; }"
+					"h2 { border-bottom: 1px solid black; font-size: 1.1rem; margin-bottom: 0.25rem; }"
+					"ul { list-style: none; padding: 0; }"
+					"li { padding: 0.15rem 0; }"
+					".have { color: gray; text-decoration: line-through; }"
+					"@media print { a { display: none; } }"
+				}
+			}
+			body {
+				a href="/" { "Back" }
+				h1 { "Shopping list" }
+				@for (aisle, items) in meal_plan.group_by_aisle(&shopping_list.items) {
+					h2 { (aisle) }
+					ul {
+						@for item in items {
+							li class=[item.have.then_some("have")] {
+								(if item.have { "☑ " } else { "☐ " })
+								(item.name) ", " (format_amount(&item.amount))
+							}
+						}
+					}
+				}
+			}
+		}
+	}
+}
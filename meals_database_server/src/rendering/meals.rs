@@ -1,38 +1,124 @@
 use maud::{html, Markup};
-use meals_database::{MealInfo, ShoppingListInfo, ShoppingListItem};
+use meals_database::{
+	format_amount, format_amount_compound, MealInfo, MealPlan, MealStub, ShoppingListInfo,
+	ShoppingListItem,
+};
+
+use uuid::Uuid;
+
+use super::new_recipe::render_units_options;
 
 pub fn render_shopping_list(
+	meal_plan: &MealPlan,
 	shopping_list_index: usize,
 	shopping_list: &ShoppingListInfo,
 ) -> Markup {
 	html! {
 		div class="shopping-list flex flex-col gap-2 text-lg w-full sm:w-[500px] p-2" {
-			@for shopping_list_item in shopping_list.items.iter() {
-				(render_shopping_list_item(shopping_list_index, &shopping_list_item))
+			div class="flex justify-between" {
+				span class="font-bold" {
+					@if shopping_list.name.is_empty() {
+						"Shopping list #" (shopping_list_index + 1)
+					} @else {
+						(shopping_list.name)
+					}
+				}
+				a href=(format!("/shopping-list/{}/print", shopping_list.id)) { "Print" }
+			}
+			@for (aisle, shopping_list_items) in meal_plan.group_by_aisle(&shopping_list.items) {
+				span class="font-bold" { (aisle) }
+				@for shopping_list_item in shopping_list_items {
+					(render_shopping_list_item(shopping_list.id, shopping_list_item))
+				}
+			}
+			(render_shopping_list_editor(meal_plan, shopping_list))
+		}
+	}
+}
+
+fn render_shopping_list_editor(meal_plan: &MealPlan, shopping_list: &ShoppingListInfo) -> Markup {
+	html! {
+		details {
+			summary { "Edit list" }
+			div class="flex flex-col gap-2 pt-2" {
+				form class="flex gap-2" method="post" action="/shopping-list/rename" {
+					input type="hidden" name="shopping_list_id" value=(shopping_list.id);
+					input name="name" placeholder="List name" type="text" value=(shopping_list.name);
+					button type="submit" { "Rename" }
+				}
+
+				form class="flex flex-wrap gap-2" method="post" action="/shopping-list/add-item" {
+					input type="hidden" name="shopping_list_id" value=(shopping_list.id);
+					input name="name" placeholder="Name" type="text";
+					input class="w-[70px]" name="amount" placeholder="Amount" type="text";
+					select class="h-[36px]" name="units" {
+						(render_units_options(None))
+					}
+					button type="submit" { "Add item" }
+				}
+
+				@if !shopping_list.items.is_empty() {
+					form class="flex flex-wrap gap-2" method="post" action="/shopping-list/edit-item" {
+						input type="hidden" name="shopping_list_id" value=(shopping_list.id);
+						select class="h-[36px]" name="id" {
+							@for item in shopping_list.items.iter() {
+								option value=(item.id) { (item.name) }
+							}
+						}
+						input name="name" placeholder="New name" type="text";
+						input class="w-[70px]" name="amount" placeholder="Amount" type="text";
+						select class="h-[36px]" name="units" {
+							(render_units_options(None))
+						}
+						button type="submit" { "Edit item" }
+					}
+				}
+
+				@if meal_plan.shopping_list.len() > 1 {
+					form class="flex gap-2" method="post" action="/shopping-list/merge" {
+						input type="hidden" name="from_id" value=(shopping_list.id);
+						select class="h-[36px]" name="into_id" {
+							@for (index, other) in meal_plan.shopping_list.iter().enumerate() {
+								@if other.id != shopping_list.id {
+									option value=(other.id) {
+										@if other.name.is_empty() {
+											"Shopping list #" (index + 1)
+										} @else {
+											(other.name)
+										}
+									}
+								}
+							}
+						}
+						button type="submit" { "Merge into" }
+					}
+				}
 			}
 		}
 	}
 }
 
 pub fn render_shopping_list_item(
-	shopping_list_index: usize,
+	shopping_list_id: Uuid,
 	shopping_list_item: &ShoppingListItem,
 ) -> Markup {
 	html! {
-		div class="grid grid-cols-[2.5rem_auto_5rem] w-full gap-2 items-center" {
+		div class="grid grid-cols-[2.5rem_auto_5rem_2rem] w-full gap-2 items-center" {
 			div class="flex items-center justify-center h-full" {
-				(render_checkbox(shopping_list_index, shopping_list_item))
+				(render_checkbox(shopping_list_id, shopping_list_item))
 			}
 			span { (shopping_list_item.name) }
-			span { (format!("{} {}", shopping_list_item.amount.value, shopping_list_item.amount.units)) }
+			span { (format_amount(&shopping_list_item.amount)) }
+			form method="post" action="/shopping-list/remove-item" {
+				input type="hidden" name="shopping_list_id" value=(shopping_list_id);
+				input type="hidden" name="id" value=(shopping_list_item.id);
+				button type="submit" title="Remove" { "✕" }
+			}
 		}
 	}
 }
 
-pub fn render_checkbox(
-	shopping_list_index: usize,
-	shopping_list_item: &ShoppingListItem,
-) -> Markup {
+pub fn render_checkbox(shopping_list_id: Uuid, shopping_list_item: &ShoppingListItem) -> Markup {
 	html! {
 		input
 			.shopping-list-checkbox
@@ -40,9 +126,9 @@ pub fn render_checkbox(
 			hx-post="/update-checkbox"
 			hx-swap="outerHTML"
 			hx-vals=(format!(
-				r#"{{ "shopping_list_index": {}, "name": "{}", "checked": {} }}"#,
-				shopping_list_index,
-				shopping_list_item.name,
+				r#"{{ "shopping_list_id": "{}", "id": "{}", "checked": {} }}"#,
+				shopping_list_id,
+				shopping_list_item.id,
 				!shopping_list_item.have
 			))
 			checked[shopping_list_item.have]
@@ -50,18 +136,44 @@ pub fn render_checkbox(
 	}
 }
 
-pub fn render_meal(meal_info: &MealInfo) -> Markup {
+pub fn render_history_button(action: &str, label: &str, enabled: bool) -> Markup {
+	html! {
+		form method="post" action=(action) {
+			button type="submit" disabled[!enabled] { (label) }
+		}
+	}
+}
+
+pub fn render_servings(meal_info: &MealInfo, meal_stub: &MealStub) -> Markup {
+	html! {
+		form class="flex gap-2 items-center" method="post" action="/update-servings" {
+			input type="hidden" name="date" value=(meal_stub.date);
+			input type="hidden" name="id" value=(meal_stub.id);
+			label for=(format!("servings-{}-{}", meal_stub.date, meal_stub.id)) { "Serves" }
+			input
+				id=(format!("servings-{}-{}", meal_stub.date, meal_stub.id))
+				class="w-16"
+				type="number"
+				name="servings"
+				min="1"
+				value=(meal_info.servings_for(meal_stub));
+			button type="submit" { "Update" }
+		}
+	}
+}
+
+pub fn render_meal(meal_info: &MealInfo, meal_stub: &MealStub) -> Markup {
 	html! {
 		div class="meal flex flex-col gap-2 text-lg w-full sm:w-[500px] p-3 items-start" {
 			img class="w-full" src=(meal_info.image);
 			span { (meal_info.name) }
-			span { "Serves " (meal_info.serving_size) }
+			(render_servings(meal_info, meal_stub))
 			hr;
 			span { "Ingredients:" }
-			@for (index, ingredient) in meal_info.ingredients.iter().enumerate() {
+			@for (index, ingredient) in meal_info.ingredients_for(meal_stub).iter().enumerate() {
 				div class="grid grid-cols-[2.25rem,1fr,2fr] w-full" {
 					span { (index + 1) "." }
-					span { (ingredient.amount.value) " " (format!("{}", ingredient.amount.units)) }
+					span { (format_amount_compound(&ingredient.amount)) }
 					span { (ingredient.name) }
 				}
 			}
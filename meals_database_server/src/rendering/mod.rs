@@ -1,7 +1,17 @@
+mod cook_with;
+mod export;
 mod meals;
 mod new_recipe;
+mod nutrition;
+mod pantry;
+mod recipes;
+mod staples;
+mod statistics;
 
+pub use cook_with::render_cook_with;
+pub use export::render_printable_shopping_list;
 pub use meals::render_checkbox;
+pub use meals::render_history_button;
 pub use meals::render_meal;
 pub use meals::render_shopping_list;
 pub use meals::root;
@@ -10,3 +20,8 @@ pub use new_recipe::render_add_recipe;
 pub use new_recipe::render_ingredient;
 pub use new_recipe::render_parsed_recipe;
 pub use new_recipe::render_step;
+pub use nutrition::render_nutrition;
+pub use pantry::render_pantry;
+pub use recipes::render_recipes;
+pub use staples::render_staples;
+pub use statistics::render_statistics;
@@ -1,27 +1,43 @@
 use maud::{html, Markup};
+use meals_database::{Diet, MealInfo, ParsedIngredient, Units};
 
+/// The units a form can pick from, with the value `Units::try_from` reads back and a label.
+const UNITS_OPTIONS: &[(Units, &str, &str)] = &[
+	(Units::Count, "unit", "Count"),
+	(Units::Cup, "cup", "Cup"),
+	(Units::Ounce, "ounce", "Ounce"),
+	(Units::Tablespoon, "tablespoon", "Tablespoon"),
+	(Units::Teaspoon, "teaspoon", "Teaspoon"),
+	(Units::Milliliters, "milliliters", "Milliliters"),
+	(Units::Liters, "liters", "Liters"),
+	(Units::FluidOunces, "fluid ounces", "Fluid ounces"),
+	(Units::Pints, "pints", "Pints"),
+	(Units::Quarts, "quarts", "Quarts"),
+	(Units::Pinches, "pinches", "Pinches"),
+	(Units::Grams, "grams", "Grams"),
+	(Units::Kilograms, "kilograms", "Kilograms"),
+	(Units::Pounds, "pounds", "Pounds"),
+];
+
+/// `details` holds the name, image, course, cuisine, tags, times and diets. Ingredients the parser
+/// wasn't sure about are highlighted along with the line they came from.
 pub fn render_parsed_recipe(
-	name: &str,
+	details: &MealInfo,
 	serving_size: &str,
-	image: &str,
-	ingredient_names: Vec<String>,
-	ingredient_amounts: Vec<String>,
-	ingredient_units: Vec<String>,
+	ingredients: Vec<ParsedIngredient>,
 	steps: Vec<String>,
 ) -> Markup {
-	let ingredient_count = ingredient_names
-		.len()
-		.min(ingredient_amounts.len())
-		.min(ingredient_units.len());
+	let ingredient_count = ingredients.len();
 
 	html! {
 		form action="/add-meal" class="flex flex-col items-center gap-4 text-xl pt-10" method="post" {
-			(render_name(name, serving_size))
-			(render_image(image))
+			(render_name(&details.name, serving_size))
+			(render_image(&details.image))
+			(render_details(details))
 			div class="flex flex-col gap-2" {
 				span { "Ingredients:" }
-				@for i in 0..ingredient_count {
-					(render_ingredient(i, &ingredient_names[i], &ingredient_amounts[i], &ingredient_units[i]))
+				@for (i, parsed_ingredient) in ingredients.into_iter().enumerate() {
+					(render_parsed_ingredient(i, parsed_ingredient))
 				}
 				(add_button("/add-ingredient", "ingredient", ingredient_count))
 			}
@@ -50,9 +66,10 @@ pub fn render_add_recipe() -> Markup {
 		form action="/add-meal" class="flex flex-col items-center gap-4 text-xl pt-10" method="post" {
 			(render_name("", ""))
 			(render_image(""))
+			(render_details(&MealInfo::default()))
 			div class="flex flex-col gap-2" {
 				span { "Ingredients:" }
-				(render_ingredient(0, "", "", ""))
+				(render_ingredient(0, "", "", &Units::Count, None))
 				(add_button("/add-ingredient", "ingredient", 1))
 			}
 			div class="flex flex-col gap-2" {
@@ -105,37 +122,91 @@ pub fn render_image(value: &str) -> Markup {
 	}
 }
 
-pub fn render_ingredient(number: usize, name: &str, amount: &str, unit: &str) -> Markup {
+/// The course, cuisine, tags, times and diets.
+pub fn render_details(details: &MealInfo) -> Markup {
+	html! {
+		div class="flex flex-col gap-2" {
+			div class="flex gap-2 items-center" {
+				label for="course" { "Course:" }
+				input id="course" name="course" type="text" value=(details.course);
+				label for="cuisine" { "Cuisine:" }
+				input id="cuisine" name="cuisine" type="text" value=(details.cuisine);
+			}
+			div class="flex gap-2 items-center" {
+				label for="tags" { "Tags:" }
+				input id="tags" name="tags" placeholder="Comma separated" type="text" value=(details.tags.join(", "));
+			}
+			div class="flex gap-2 items-center" {
+				label for="prep_minutes" { "Prep minutes:" }
+				input class="w-[70px]" id="prep_minutes" name="prep_minutes" type="text" value=[details.prep_minutes];
+				label for="cook_minutes" { "Cook minutes:" }
+				input class="w-[70px]" id="cook_minutes" name="cook_minutes" type="text" value=[details.cook_minutes];
+			}
+			div class="flex gap-4 items-center" {
+				@for diet in Diet::ALL {
+					label {
+						input name="diet" type="checkbox" value=(diet) checked[details.diets.contains(&diet)];
+						" " (diet)
+					}
+				}
+			}
+		}
+	}
+}
+
+fn render_parsed_ingredient(number: usize, parsed_ingredient: ParsedIngredient) -> Markup {
+	let unsure = (!parsed_ingredient.confident).then(|| parsed_ingredient.original.clone());
+	let ingredient = parsed_ingredient.into_ingredient();
+
+	render_ingredient(
+		number,
+		&ingredient.name,
+		&ingredient.amount.value.to_string(),
+		&ingredient.amount.units,
+		unsure.as_deref(),
+	)
+}
+
+/// `unsure` is the recipe's own line for an ingredient that should be checked by hand.
+pub fn render_ingredient(
+	number: usize,
+	name: &str,
+	amount: &str,
+	units: &Units,
+	unsure: Option<&str>,
+) -> Markup {
 	let name_id = format!("ingredient_name[{}]", number);
 	let amount_id = format!("ingredient_amount[{}]", number);
 	let select_id = format!("ingredient_unit[{}]", number);
 
+	let class = if unsure.is_some() {
+		"flex gap-2 items-center bg-yellow-200"
+	} else {
+		"flex gap-2 items-center"
+	};
+
 	html! {
-		div class="flex gap-2 items-center" {
+		div class=(class) {
 			label for=(name_id) {
 				(format!("{}.", number + 1))
 			}
 			input id=(name_id) name=(name_id) type="text" value=(name);
 			input class="w-[70px]" id=(amount_id) name=(amount_id) type="text" value=(amount);
-			select class="h-[36px]" id=(select_id) name=(select_id) value=(unit) {
-				option value="unit" {
-					"Count"
-				}
-				option value="cup" {
-					"Cup"
-				}
-				option value="ounce" {
-					"Ounce"
-				}
-				option value="tablespoon" {
-					"Tablespoon"
-				}
-				option value="teaspoon" {
-					"Teaspoon"
-				}
-				option value="milliliters" {
-					"Milliliters"
-				}
+			select class="h-[36px]" id=(select_id) name=(select_id) {
+				(render_units_options(Some(units)))
+			}
+		}
+		@if let Some(original) = unsure {
+			span class="text-red-500 text-base" { "Check this one, the recipe says \"" (original) "\"" }
+		}
+	}
+}
+
+pub fn render_units_options(selected: Option<&Units>) -> Markup {
+	html! {
+		@for (units, value, label) in UNITS_OPTIONS {
+			option value=(value) selected[selected == Some(units)] {
+				(label)
 			}
 		}
 	}
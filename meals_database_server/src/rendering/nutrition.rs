@@ -0,0 +1,60 @@
+use chrono::NaiveDate;
+use maud::{html, Markup};
+use meals_database::{DayNutrition, Nutrients};
+
+fn render_nutrients(nutrients: &Nutrients) -> Markup {
+	html! {
+		span { (format!("{:.0}", nutrients.calories)) " kcal" }
+		span { (format!("{:.0}", nutrients.protein)) " g" }
+		span { (format!("{:.0}", nutrients.carbs)) " g" }
+		span { (format!("{:.0}", nutrients.fat)) " g" }
+		span { (format!("{:.0}", nutrients.fiber)) " g" }
+		span { (format!("{:.0}", nutrients.sodium)) " mg" }
+	}
+}
+
+pub fn render_nutrition(from: NaiveDate, to: NaiveDate, days: &[DayNutrition]) -> Markup {
+	html! {
+		div class="flex flex-col items-center gap-4 pt-6 px-4 text-lg" {
+			a href="/" { "Back" }
+
+			form action="/nutrition" class="flex flex-wrap gap-2 items-center justify-center" method="get" {
+				input name="from" type="date" value=(from);
+				span { "to" }
+				input name="to" type="date" value=(to);
+				button type="submit" { "Show" }
+			}
+
+			div class="nutrition flex flex-col gap-2 w-full sm:w-[700px] p-2" {
+				@if days.is_empty() {
+					span class="text-center" { "No meals planned" }
+				}
+
+				@for day in days {
+					div class="grid grid-cols-[auto_6rem_4rem_4rem_4rem_4rem_5rem] w-full gap-2 items-center" {
+						span class="font-bold pt-2" { (day.date.format("%A, %B %-d")) }
+						span { "Calories" }
+						span { "Protein" }
+						span { "Carbs" }
+						span { "Fat" }
+						span { "Fiber" }
+						span { "Sodium" }
+
+						@for meal in day.meals.iter() {
+							span {
+								(meal.name)
+								@if !meal.missing.is_empty() {
+									span class="text-base" { " (without " (meal.missing.join(", ")) ")" }
+								}
+							}
+							(render_nutrients(&meal.per_serving))
+						}
+
+						span class="font-bold" { "Total" }
+						(render_nutrients(&day.total))
+					}
+				}
+			}
+		}
+	}
+}
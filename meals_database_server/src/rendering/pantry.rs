@@ -0,0 +1,52 @@
+use chrono::Local;
+use maud::{html, Markup};
+use meals_database::{format_amount, MealPlan};
+
+use super::new_recipe::render_units_options;
+
+pub fn render_pantry(meal_plan: &MealPlan) -> Markup {
+	let today = Local::now().date_naive();
+
+	let mut items = meal_plan.pantry.values().collect::<Vec<_>>();
+	items.sort_by_key(|item| item.name.to_lowercase());
+
+	html! {
+		div class="flex flex-col items-center gap-4 pt-6 px-4 text-lg" {
+			a href="/" { "Back" }
+
+			form action="/pantry" class="flex flex-wrap gap-2 items-center justify-center" method="post" {
+				input name="name" placeholder="Name" type="text";
+				input class="w-[70px]" name="amount" placeholder="Amount" type="text";
+				select class="h-[36px]" name="units" {
+					(render_units_options(None))
+				}
+				input name="expires" type="date";
+				button type="submit" { "Put in pantry" }
+			}
+
+			div class="pantry flex flex-col gap-2 w-full sm:w-[500px] p-2" {
+				@if items.is_empty() {
+					span class="text-center" { "The pantry is empty" }
+				}
+
+				@for item in items {
+					div class="grid grid-cols-[auto_6rem_7rem_4rem] w-full gap-2 items-center" {
+						span { (item.name) }
+						span { (format_amount(&item.amount)) }
+						@match item.expires {
+							Some(expires) if item.is_expired(today) => {
+								span class="text-red-500" { "Expired " (expires) }
+							}
+							Some(expires) => span { (expires) },
+							None => span {},
+						}
+						form action="/pantry/remove" method="post" {
+							input type="hidden" name="name" value=(item.name);
+							button type="submit" { "Remove" }
+						}
+					}
+				}
+			}
+		}
+	}
+}
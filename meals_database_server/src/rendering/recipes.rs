@@ -0,0 +1,78 @@
+use maud::{html, Markup};
+use meals_database::{Diet, MealFilter, MealPlan};
+
+fn render_options(values: Vec<String>, selected: &Option<String>) -> Markup {
+	html! {
+		option value="" { "Any" }
+		@for value in values {
+			@let is_selected = selected
+				.as_ref()
+				.is_some_and(|selected| selected.eq_ignore_ascii_case(&value));
+			option value=(value) selected[is_selected] { (value) }
+		}
+	}
+}
+
+pub fn render_recipes(meal_plan: &MealPlan, filter: &MealFilter) -> Markup {
+	let meals = meal_plan.filter_meals(filter);
+
+	html! {
+		div class="flex flex-col items-center gap-4 pt-6 px-4 text-lg" {
+			a href="/" { "Back" }
+
+			form action="/recipes" class="flex flex-wrap gap-2 items-center justify-center" method="get" {
+				select class="h-[36px]" name="course" {
+					(render_options(meal_plan.courses(), &filter.course))
+				}
+				select class="h-[36px]" name="cuisine" {
+					(render_options(meal_plan.cuisines(), &filter.cuisine))
+				}
+				select class="h-[36px]" name="tag" {
+					(render_options(meal_plan.tags(), &filter.tag))
+				}
+				input class="w-[90px]" name="max_minutes" placeholder="Max min" type="text" value=[filter.max_minutes];
+				@for diet in Diet::ALL {
+					label {
+						input name="diet" type="checkbox" value=(diet) checked[filter.diets.contains(&diet)];
+						" " (diet)
+					}
+				}
+				button type="submit" { "Filter" }
+			}
+
+			div class="recipes flex flex-col gap-2 w-full sm:w-[500px] p-2" {
+				@if meals.is_empty() {
+					span class="text-center" { "No recipes match" }
+				}
+
+				@for meal_info in meals {
+					div class="flex flex-col w-full" {
+						div class="flex justify-between gap-2" {
+							span class="font-bold" { (meal_info.name) }
+							@if let Some(minutes) = meal_info.total_minutes() {
+								span { (minutes) " min" }
+							}
+						}
+						span class="text-base" {
+							(meal_info.course)
+							@if !meal_info.course.is_empty() && !meal_info.cuisine.is_empty() {
+								" · "
+							}
+							(meal_info.cuisine)
+						}
+						@if !meal_info.diets.is_empty() || !meal_info.tags.is_empty() {
+							div class="flex flex-wrap gap-2 text-base" {
+								@for diet in meal_info.diets.iter() {
+									span class="font-bold" { (diet) }
+								}
+								@for tag in meal_info.tags.iter() {
+									span { "#" (tag) }
+								}
+							}
+						}
+					}
+				}
+			}
+		}
+	}
+}
@@ -0,0 +1,79 @@
+use maud::{html, Markup};
+use meals_database::{format_amount, Frequency, MealPlan};
+
+use super::new_recipe::render_units_options;
+
+pub fn render_staples(meal_plan: &MealPlan) -> Markup {
+	let mut staples = meal_plan.staples.values().collect::<Vec<_>>();
+	staples.sort_by_key(|staple| staple.name.to_lowercase());
+
+	html! {
+		div class="flex flex-col items-center gap-4 pt-6 px-4 text-lg" {
+			a href="/" { "Back" }
+
+			span { "Staples" }
+
+			form action="/staples" class="flex flex-wrap gap-2 items-center justify-center" method="post" {
+				input name="name" placeholder="Name" type="text";
+				input class="w-[70px]" name="amount" placeholder="Amount" type="text";
+				select class="h-[36px]" name="units" {
+					(render_units_options(None))
+				}
+				select class="h-[36px]" name="frequency" {
+					@for frequency in Frequency::ALL {
+						option value=(frequency) selected[frequency == Frequency::default()] {
+							(frequency)
+						}
+					}
+				}
+				button type="submit" { "Save staple" }
+			}
+
+			div class="staples flex flex-col gap-2 w-full sm:w-[500px] p-2" {
+				@if staples.is_empty() {
+					span class="text-center" { "No staples" }
+				}
+
+				@for staple in staples {
+					div class="grid grid-cols-[auto_6rem_8rem_4rem] w-full gap-2 items-center" {
+						span { (staple.name) }
+						span { (format_amount(&staple.amount)) }
+						span { (staple.frequency) }
+						form action="/staples/remove" method="post" {
+							input type="hidden" name="name" value=(staple.name);
+							button type="submit" { "Remove" }
+						}
+					}
+				}
+			}
+
+			span { "Add to the next shopping list" }
+
+			form action="/extra-items" class="flex flex-wrap gap-2 items-center justify-center" method="post" {
+				input name="name" placeholder="Name" type="text";
+				input class="w-[70px]" name="amount" placeholder="Amount" type="text";
+				select class="h-[36px]" name="units" {
+					(render_units_options(None))
+				}
+				button type="submit" { "Add item" }
+			}
+
+			div class="extra-items flex flex-col gap-2 w-full sm:w-[500px] p-2" {
+				@if meal_plan.extra_items.is_empty() {
+					span class="text-center" { "No extra items" }
+				}
+
+				@for item in meal_plan.extra_items.iter() {
+					div class="grid grid-cols-[auto_6rem_4rem] w-full gap-2 items-center" {
+						span { (item.name) }
+						span { (format_amount(&item.amount)) }
+						form action="/extra-items/remove" method="post" {
+							input type="hidden" name="name" value=(item.name);
+							button type="submit" { "Remove" }
+						}
+					}
+				}
+			}
+		}
+	}
+}
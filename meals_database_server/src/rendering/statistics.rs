@@ -0,0 +1,59 @@
+use maud::{html, Markup};
+use meals_database::{format_amount, Statistics};
+
+pub fn render_statistics(statistics: &Statistics) -> Markup {
+	html! {
+		div class="flex flex-col items-center gap-4 pt-6 px-4 text-lg" {
+			a href="/" { "Back" }
+
+			form action="/statistics" class="flex flex-wrap gap-2 items-center justify-center" method="get" {
+				input name="from" type="date" value=[statistics.from];
+				span { "to" }
+				input name="to" type="date" value=[statistics.to];
+				button type="submit" { "Show" }
+			}
+
+			div class="statistics flex flex-col gap-2 w-full sm:w-[500px] p-2" {
+				span { "Meals cooked: " (statistics.total_cooked) }
+				span { "Current streak: " (statistics.current_streak) " days" }
+				span { "Longest streak: " (statistics.longest_streak) " days" }
+
+				span class="font-bold pt-2" { "Most cooked" }
+				@for meal in statistics.most_cooked(5) {
+					div class="grid grid-cols-[auto_3rem_7rem] w-full gap-2 items-center" {
+						span { (meal.name) }
+						span { (meal.times_cooked) "×" }
+						@match meal.last_cooked {
+							Some(last_cooked) => span { (last_cooked) },
+							None => span {},
+						}
+					}
+				}
+
+				span class="font-bold pt-2" { "Least cooked" }
+				@for meal in statistics.least_cooked(5) {
+					div class="grid grid-cols-[auto_3rem_7rem] w-full gap-2 items-center" {
+						span { (meal.name) }
+						span { (meal.times_cooked) "×" }
+						@match meal.last_cooked {
+							Some(last_cooked) => span { (last_cooked) },
+							None => span { "Never" },
+						}
+					}
+				}
+
+				span class="font-bold pt-2" { "Ingredients used" }
+				@if statistics.ingredients.is_empty() {
+					span class="text-center" { "Nothing cooked yet" }
+				}
+
+				@for ingredient in statistics.ingredients.iter() {
+					div class="grid grid-cols-[auto_6rem] w-full gap-2 items-center" {
+						span { (ingredient.name) }
+						span { (format_amount(&ingredient.amount)) }
+					}
+				}
+			}
+		}
+	}
+}
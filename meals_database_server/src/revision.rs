@@ -0,0 +1,54 @@
+use rocket::{
+	http::Header,
+	request::{self, FromRequest, Outcome},
+	serde::json::Value,
+	Request, Responder,
+};
+
+use crate::Error;
+
+pub struct IfMatch(pub u64);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for IfMatch {
+	type Error = Error;
+
+	async fn from_request(request: &'r Request<'_>) -> request::Outcome<Self, Error> {
+		let Some(value) = request.headers().get_one("if-match") else {
+			let error = Error::PayloadProblem {
+				message: "Could not find if-match header".into(),
+			};
+			return Outcome::Error((error.get_status_code(), error));
+		};
+
+		match value
+			.trim()
+			.trim_start_matches("W/")
+			.trim_matches('"')
+			.parse::<u64>()
+		{
+			Ok(revision) => Outcome::Success(IfMatch(revision)),
+			Err(_) => {
+				let error = Error::PayloadProblem {
+					message: "Invalid if-match header".into(),
+				};
+				Outcome::Error((error.get_status_code(), error))
+			}
+		}
+	}
+}
+
+#[derive(Responder)]
+pub struct Revisioned {
+	inner: Value,
+	etag: Header<'static>,
+}
+
+impl Revisioned {
+	pub fn new(inner: Value, revision: u64) -> Self {
+		Revisioned {
+			inner,
+			etag: Header::new("ETag", format!("\"{}\"", revision)),
+		}
+	}
+}
@@ -1,8 +1,8 @@
-use std::process::Command;
-
+use chrono::{Days, Local, NaiveDate};
 use maud::{html, Markup};
 use meals_database::{
-	normalize_recipe_json, Amount, Ingredient, MealInfo, RecipeJSON, RecipeStep, Units,
+	scrape_recipe, Amount, Diet, Frequency, Ingredient, MealFilter, MealInfo, MealPlanChange,
+	PantryItem, RecipeStep, ShoppingListItem, Staple, Units,
 };
 use rocket::{
 	form::Form,
@@ -17,22 +17,26 @@ use uuid::Uuid;
 use crate::{
 	auth::User,
 	rendering::{
-		add_button, render_add_recipe, render_checkbox, render_ingredient, render_meal,
-		render_parsed_recipe, render_shopping_list, render_step, root,
+		add_button, render_add_recipe, render_checkbox, render_cook_with, render_history_button,
+		render_ingredient, render_meal, render_nutrition, render_pantry, render_parsed_recipe,
+		render_printable_shopping_list, render_recipes, render_shopping_list, render_staples,
+		render_statistics, render_step, root,
 	},
-	Context, Result,
+	Context, Error, Result,
 };
 
 #[get("/")]
 pub async fn get_root(context: &State<Context>, _user: User) -> Result<Markup> {
 	let meal_plan = context.meals_database.read().await;
+	let can_undo = meal_plan.can_undo();
+	let can_redo = meal_plan.can_redo();
 	let meal_plan = meal_plan.get();
 
 	let shopping_lists = &meal_plan.shopping_list;
 	let planned_meals = &meal_plan.planned_meals;
 	let all_meals = &meal_plan.all_meals;
 
-	let shopping_list_markup = if shopping_lists.len() == 0 {
+	let shopping_list_markup = if shopping_lists.is_empty() {
 		html! {
 			div class="flex flex-col items-center gap-4 px-4" {
 				div class="shopping-list flex gap-2 text-lg w-full sm:w-[500px] p-2 justify-center" {
@@ -43,7 +47,7 @@ pub async fn get_root(context: &State<Context>, _user: User) -> Result<Markup> {
 	} else {
 		html! {
 			@for (shopping_list_index, shopping_list) in shopping_lists.iter().enumerate() {
-				(render_shopping_list(shopping_list_index, &shopping_list))
+				(render_shopping_list(&meal_plan, shopping_list_index, shopping_list))
 			}
 		}
 	};
@@ -55,27 +59,80 @@ pub async fn get_root(context: &State<Context>, _user: User) -> Result<Markup> {
 				continue;
 			}
 
-			meals.push((meal_stub.date, all_meals.get(&meal_stub.id).unwrap()));
+			meals.push((meal_stub, all_meals.get(&meal_stub.id).unwrap()));
 		}
 	}
 
-	meals.sort_by(|(date1, _), (date2, _)| date1.cmp(date2));
+	meals.sort_by_key(|(meal_stub, _)| meal_stub.date);
 
 	Ok(root(html! {
 		div class="flex flex-col items-center gap-4 pt-6 px-4" {
-			a href="/add-recipe" {
-				"Add recipe"
+			div class="flex gap-4" {
+				a href="/add-recipe" {
+					"Add recipe"
+				}
+
+				a href="/pantry" {
+					"Pantry"
+				}
+
+				a href="/staples" {
+					"Staples"
+				}
+
+				a href="/recipes" {
+					"Recipes"
+				}
+
+				a href="/statistics" {
+					"Statistics"
+				}
+
+				a href="/cook-with" {
+					"Cook with"
+				}
+
+				a href="/nutrition" {
+					"Nutrition"
+				}
+
+				(render_history_button("/undo", "Undo", can_undo))
+				(render_history_button("/redo", "Redo", can_redo))
 			}
 
 			(shopping_list_markup)
 
 			@for meal in meals.iter() {
-				(render_meal(meal.1))
+				(render_meal(meal.1, meal.0))
 			}
 		}
 	}))
 }
 
+#[post("/undo")]
+pub async fn post_undo(context: &State<Context>, user: User) -> Result<Redirect> {
+	context
+		.meals_database
+		.write()
+		.await
+		.undo(&user.name)
+		.await?;
+
+	Ok(Redirect::to("/"))
+}
+
+#[post("/redo")]
+pub async fn post_redo(context: &State<Context>, user: User) -> Result<Redirect> {
+	context
+		.meals_database
+		.write()
+		.await
+		.redo(&user.name)
+		.await?;
+
+	Ok(Redirect::to("/"))
+}
+
 #[get("/add-recipe")]
 pub async fn get_add_recipe(_user: User) -> Result<Markup> {
 	Ok(root(html! {
@@ -85,39 +142,24 @@ pub async fn get_add_recipe(_user: User) -> Result<Markup> {
 
 #[get("/parsed-recipe?<url>")]
 pub async fn get_parsed_recipe(url: String, _user: User) -> Result<Markup> {
-	let recipe_json = Command::new("python")
-		.args([
-			"/home/me/bansheelong2/meals_database/src/bins/scraper.py",
-			&url,
-		])
-		.output()
-		.unwrap()
-		.stdout;
-
-	let recipe_json: RecipeJSON =
-		serde_json::from_str(&String::from_utf8(recipe_json).unwrap()).unwrap();
-
-	let normalized_recipe = normalize_recipe_json(recipe_json);
+	let normalized_recipe = scrape_recipe(&url).await?;
+
+	let details = MealInfo {
+		cook_minutes: normalized_recipe.cook_minutes,
+		course: normalized_recipe.course,
+		cuisine: normalized_recipe.cuisine,
+		diets: normalized_recipe.diets,
+		image: normalized_recipe.image,
+		name: normalized_recipe.name,
+		prep_minutes: normalized_recipe.prep_minutes,
+		tags: normalized_recipe.tags,
+		..Default::default()
+	};
 
 	Ok(root(render_parsed_recipe(
-		&normalized_recipe.name,
+		&details,
 		"",
-		&normalized_recipe.image,
-		normalized_recipe
-			.ingredients
-			.iter()
-			.map(|ingredient| ingredient.name.clone())
-			.collect::<Vec<_>>(),
-		normalized_recipe
-			.ingredients
-			.iter()
-			.map(|ingredient| ingredient.amount.value.to_string())
-			.collect::<Vec<_>>(),
-		normalized_recipe
-			.ingredients
-			.iter()
-			.map(|ingredient| ingredient.amount.units.to_string())
-			.collect::<Vec<_>>(),
+		normalized_recipe.ingredients,
 		normalized_recipe
 			.recipe
 			.iter()
@@ -131,6 +173,12 @@ pub struct AddMealData {
 	name: String,
 	serving_size: String,
 	image: String,
+	cook_minutes: String,
+	course: String,
+	cuisine: String,
+	diet: Vec<String>,
+	prep_minutes: String,
+	tags: String,
 	ingredient_amount: Vec<String>,
 	ingredient_name: Vec<String>,
 	ingredient_unit: Vec<String>,
@@ -147,7 +195,7 @@ impl AddMealData {
 			.min(self.ingredient_unit.len());
 
 		for i in 0..ingredients_amount {
-			if self.ingredient_name[i].trim().len() == 0 {
+			if self.ingredient_name[i].trim().is_empty() {
 				continue;
 			}
 
@@ -162,7 +210,7 @@ impl AddMealData {
 
 		let mut recipe = vec![];
 		for step in self.step.iter() {
-			if step.trim().len() == 0 {
+			if step.trim().is_empty() {
 				continue;
 			}
 
@@ -172,12 +220,28 @@ impl AddMealData {
 		}
 
 		Ok(MealInfo {
+			cook_minutes: self.cook_minutes.trim().parse::<u32>().ok(),
+			course: self.course.trim().to_string(),
+			cuisine: self.cuisine.trim().to_string(),
+			diets: self
+				.diet
+				.iter()
+				.filter_map(|diet| Diet::try_from(diet.as_str()).ok())
+				.collect(),
 			id: Uuid::new_v4(),
 			image: self.image.clone(),
 			ingredients,
 			name: self.name.to_string(),
+			prep_minutes: self.prep_minutes.trim().parse::<u32>().ok(),
 			recipe,
 			serving_size: self.serving_size.parse::<usize>().unwrap(),
+			tags: self
+				.tags
+				.split(',')
+				.map(str::trim)
+				.filter(|tag| !tag.is_empty())
+				.map(String::from)
+				.collect(),
 		})
 	}
 }
@@ -186,22 +250,16 @@ impl AddMealData {
 pub async fn post_add_meal(
 	context: &State<Context>,
 	data: Form<AddMealData>,
-	_user: User,
+	user: User,
 ) -> Result<Redirect> {
-	let meals_database = context.meals_database.write().await;
-	let mut meal_plan = meals_database.get_mut();
-
-	let maybe_meal_info = data.to_meal_info();
-	let Ok(meal_info) = maybe_meal_info else {
-		drop(meal_plan);
-		drop(meals_database);
-		return Err(maybe_meal_info.err().unwrap());
-	};
-
-	meal_plan.all_meals.insert(meal_info.id, meal_info);
-	drop(meal_plan);
+	let meal_info = data.to_meal_info()?;
 
-	meals_database.save();
+	context
+		.meals_database
+		.write()
+		.await
+		.apply(MealPlanChange::MealSaved { meal_info }, &user.name)
+		.await?;
 
 	Ok(Redirect::to("/"))
 }
@@ -211,42 +269,601 @@ pub fn get_style() -> RawCss<String> {
 	RawCss(std::fs::read_to_string("./meals_database_server/output.css").unwrap())
 }
 
+#[get("/shopping-list/<shopping_list_id>/print")]
+pub async fn get_printable_shopping_list(
+	context: &State<Context>,
+	shopping_list_id: &str,
+	_user: User,
+) -> Result<Markup> {
+	let shopping_list_id = parse_id(shopping_list_id)?;
+
+	let meals_database = context.meals_database.read().await;
+	let meal_plan = meals_database.get();
+	let shopping_list = meal_plan.shopping_list_by_id(shopping_list_id)?;
+
+	Ok(render_printable_shopping_list(&meal_plan, shopping_list))
+}
+
 #[derive(Debug, FromForm)]
 pub struct CheckboxState {
 	checked: bool,
-	name: String,
-	shopping_list_index: usize,
+	id: String,
+	shopping_list_id: String,
 }
 
 #[post("/update-checkbox", data = "<checkbox>")]
 pub async fn post_checkbox(
 	context: &State<Context>,
 	checkbox: Form<CheckboxState>,
+	user: User,
+) -> Result<Markup> {
+	let id = parse_id(&checkbox.id)?;
+	let shopping_list_id = parse_id(&checkbox.shopping_list_id)?;
+
+	let mut database = context.meals_database.write().await;
+	database
+		.apply(
+			MealPlanChange::ShoppingListItemSet {
+				have: checkbox.checked,
+				id,
+				shopping_list_id,
+			},
+			&user.name,
+		)
+		.await?;
+
+	let meal_plan = database.get();
+	let shopping_list_item = meal_plan
+		.shopping_list_by_id(shopping_list_id)?
+		.items
+		.iter()
+		.find(|item| item.id == id)
+		.unwrap();
+
+	Ok(html! { (render_checkbox(shopping_list_id, shopping_list_item)) })
+}
+
+#[derive(Debug, FromForm)]
+pub struct AddShoppingListItemData {
+	amount: String,
+	name: String,
+	shopping_list_id: String,
+	units: String,
+}
+
+#[post("/shopping-list/add-item", data = "<data>")]
+pub async fn post_shopping_list_add_item(
+	context: &State<Context>,
+	data: Form<AddShoppingListItemData>,
+	user: User,
+) -> Result<Redirect> {
+	let item = ShoppingListItem::new(data.name.trim(), parse_amount(&data.amount, &data.units)?);
+
+	context
+		.meals_database
+		.write()
+		.await
+		.apply(
+			MealPlanChange::ShoppingListItemAdded {
+				item,
+				shopping_list_id: parse_id(&data.shopping_list_id)?,
+			},
+			&user.name,
+		)
+		.await?;
+
+	Ok(Redirect::to("/"))
+}
+
+#[derive(Debug, FromForm)]
+pub struct EditShoppingListItemData {
+	amount: String,
+	id: String,
+	name: String,
+	shopping_list_id: String,
+	units: String,
+}
+
+#[post("/shopping-list/edit-item", data = "<data>")]
+pub async fn post_shopping_list_edit_item(
+	context: &State<Context>,
+	data: Form<EditShoppingListItemData>,
+	user: User,
+) -> Result<Redirect> {
+	let item = ShoppingListItem {
+		id: parse_id(&data.id)?,
+		..ShoppingListItem::new(data.name.trim(), parse_amount(&data.amount, &data.units)?)
+	};
+
+	context
+		.meals_database
+		.write()
+		.await
+		.apply(
+			MealPlanChange::ShoppingListItemEdited {
+				item,
+				shopping_list_id: parse_id(&data.shopping_list_id)?,
+			},
+			&user.name,
+		)
+		.await?;
+
+	Ok(Redirect::to("/"))
+}
+
+#[derive(Debug, FromForm)]
+pub struct RemoveShoppingListItemData {
+	id: String,
+	shopping_list_id: String,
+}
+
+#[post("/shopping-list/remove-item", data = "<data>")]
+pub async fn post_shopping_list_remove_item(
+	context: &State<Context>,
+	data: Form<RemoveShoppingListItemData>,
+	user: User,
+) -> Result<Redirect> {
+	let id = parse_id(&data.id)?;
+
+	context
+		.meals_database
+		.write()
+		.await
+		.apply(
+			MealPlanChange::ShoppingListItemRemoved {
+				id,
+				shopping_list_id: parse_id(&data.shopping_list_id)?,
+			},
+			&user.name,
+		)
+		.await?;
+
+	Ok(Redirect::to("/"))
+}
+
+#[derive(Debug, FromForm)]
+pub struct RenameShoppingListData {
+	name: String,
+	shopping_list_id: String,
+}
+
+#[post("/shopping-list/rename", data = "<data>")]
+pub async fn post_shopping_list_rename(
+	context: &State<Context>,
+	data: Form<RenameShoppingListData>,
+	user: User,
+) -> Result<Redirect> {
+	context
+		.meals_database
+		.write()
+		.await
+		.apply(
+			MealPlanChange::ShoppingListRenamed {
+				name: data.name.clone(),
+				shopping_list_id: parse_id(&data.shopping_list_id)?,
+			},
+			&user.name,
+		)
+		.await?;
+
+	Ok(Redirect::to("/"))
+}
+
+#[derive(Debug, FromForm)]
+pub struct MergeShoppingListsData {
+	from_id: String,
+	into_id: String,
+}
+
+#[post("/shopping-list/merge", data = "<data>")]
+pub async fn post_shopping_list_merge(
+	context: &State<Context>,
+	data: Form<MergeShoppingListsData>,
+	user: User,
+) -> Result<Redirect> {
+	context
+		.meals_database
+		.write()
+		.await
+		.apply(
+			MealPlanChange::ShoppingListsMerged {
+				from_id: parse_id(&data.from_id)?,
+				into_id: parse_id(&data.into_id)?,
+			},
+			&user.name,
+		)
+		.await?;
+
+	Ok(Redirect::to("/"))
+}
+
+#[derive(Debug, FromForm)]
+pub struct ServingsState {
+	date: String,
+	id: String,
+	servings: usize,
+}
+
+#[post("/update-servings", data = "<servings>")]
+pub async fn post_update_servings(
+	context: &State<Context>,
+	servings: Form<ServingsState>,
+	user: User,
+) -> Result<Redirect> {
+	let date = servings
+		.date
+		.parse::<NaiveDate>()
+		.map_err(|error| Error::PayloadProblem {
+			message: format!("Invalid date: {}", error),
+		})?;
+
+	let id = parse_id(&servings.id)?;
+
+	context
+		.meals_database
+		.write()
+		.await
+		.apply(
+			MealPlanChange::ServingsSet {
+				date,
+				id,
+				servings: Some(servings.servings.max(1)),
+			},
+			&user.name,
+		)
+		.await?;
+
+	Ok(Redirect::to("/"))
+}
+
+#[get("/pantry")]
+pub async fn get_pantry(context: &State<Context>, _user: User) -> Result<Markup> {
+	let meals_database = context.meals_database.read().await;
+	let markup = render_pantry(&meals_database.get());
+
+	Ok(root(markup))
+}
+
+#[derive(Debug, FromForm)]
+pub struct PantryItemData {
+	amount: String,
+	expires: String,
+	name: String,
+	units: String,
+}
+
+fn parse_id(id: &str) -> Result<Uuid> {
+	Uuid::parse_str(id.trim()).map_err(|error| Error::PayloadProblem {
+		message: format!("Invalid id: {}", error),
+	})
+}
+
+/// Date inputs left empty are sent as empty strings.
+fn parse_optional_date(date: Option<&str>) -> Result<Option<NaiveDate>> {
+	match date.map(str::trim) {
+		None | Some("") => Ok(None),
+		Some(date) => date
+			.parse::<NaiveDate>()
+			.map(Some)
+			.map_err(|error| Error::PayloadProblem {
+				message: format!("Invalid date: {}", error),
+			}),
+	}
+}
+
+fn parse_amount(amount: &str, units: &str) -> Result<Amount> {
+	let value = amount
+		.trim()
+		.parse::<f32>()
+		.map_err(|error| Error::PayloadProblem {
+			message: format!("Invalid amount: {}", error),
+		})?;
+
+	let units = Units::try_from(units).map_err(|_| Error::PayloadProblem {
+		message: format!("Invalid units '{}'", units),
+	})?;
+
+	Ok(Amount { units, value })
+}
+
+impl PantryItemData {
+	pub fn to_pantry_item(&self) -> Result<PantryItem> {
+		let expires = match self.expires.trim() {
+			"" => None,
+			expires => {
+				Some(
+					expires
+						.parse::<NaiveDate>()
+						.map_err(|error| Error::PayloadProblem {
+							message: format!("Invalid expiry date: {}", error),
+						})?,
+				)
+			}
+		};
+
+		Ok(PantryItem {
+			amount: parse_amount(&self.amount, &self.units)?,
+			expires,
+			name: self.name.trim().into(),
+		})
+	}
+}
+
+#[post("/pantry", data = "<data>")]
+pub async fn post_pantry(
+	context: &State<Context>,
+	data: Form<PantryItemData>,
+	user: User,
+) -> Result<Redirect> {
+	let item = data.to_pantry_item()?;
+
+	context
+		.meals_database
+		.write()
+		.await
+		.apply(MealPlanChange::PantryItemSet { item }, &user.name)
+		.await?;
+
+	Ok(Redirect::to("/pantry"))
+}
+
+#[derive(Debug, FromForm)]
+pub struct RemovePantryItemData {
+	name: String,
+}
+
+#[post("/pantry/remove", data = "<data>")]
+pub async fn post_pantry_remove(
+	context: &State<Context>,
+	data: Form<RemovePantryItemData>,
+	user: User,
+) -> Result<Redirect> {
+	context
+		.meals_database
+		.write()
+		.await
+		.apply(
+			MealPlanChange::PantryItemRemoved {
+				name: data.name.clone(),
+			},
+			&user.name,
+		)
+		.await?;
+
+	Ok(Redirect::to("/pantry"))
+}
+
+#[get("/staples")]
+pub async fn get_staples(context: &State<Context>, _user: User) -> Result<Markup> {
+	let meals_database = context.meals_database.read().await;
+	let markup = render_staples(&meals_database.get());
+
+	Ok(root(markup))
+}
+
+#[get("/recipes?<course>&<cuisine>&<tag>&<diet>&<max_minutes>")]
+pub async fn get_recipes(
+	context: &State<Context>,
+	course: Option<&str>,
+	cuisine: Option<&str>,
+	tag: Option<&str>,
+	diet: Vec<&str>,
+	max_minutes: Option<&str>,
 	_user: User,
 ) -> Result<Markup> {
-	let database = context.meals_database.write().await;
-	let mut meal_plan = database.get_mut();
+	// the filter form sends every field, empty or not
+	let non_empty = |value: Option<&str>| {
+		value
+			.map(str::trim)
+			.filter(|value| !value.is_empty())
+			.map(String::from)
+	};
 
-	let shopping_list = meal_plan
-		.shopping_list
-		.get_mut(checkbox.shopping_list_index)
-		.unwrap();
+	let filter = MealFilter {
+		course: non_empty(course),
+		cuisine: non_empty(cuisine),
+		diets: diet
+			.into_iter()
+			.map(|diet| {
+				Diet::try_from(diet).map_err(|_| Error::PayloadProblem {
+					message: format!("Invalid diet '{}'", diet),
+				})
+			})
+			.collect::<Result<_>>()?,
+		max_minutes: match non_empty(max_minutes) {
+			Some(max_minutes) => {
+				Some(
+					max_minutes
+						.parse::<u32>()
+						.map_err(|error| Error::PayloadProblem {
+							message: format!("Invalid minutes: {}", error),
+						})?,
+				)
+			}
+			None => None,
+		},
+		tag: non_empty(tag),
+	};
 
-	let shopping_list_item = shopping_list
-		.items
-		.iter_mut()
-		.find(|item| item.name == checkbox.name)
-		.unwrap();
+	let meals_database = context.meals_database.read().await;
+	let markup = render_recipes(&meals_database.get(), &filter);
 
-	shopping_list_item.have = checkbox.checked;
+	Ok(root(markup))
+}
 
-	let shopping_list_item = shopping_list_item.clone();
+/// Without any ingredients, the meals are ranked against the checked shopping list items.
+#[get("/cook-with?<ingredients>")]
+pub async fn get_cook_with(
+	context: &State<Context>,
+	ingredients: Option<&str>,
+	_user: User,
+) -> Result<Markup> {
+	let meals_database = context.meals_database.read().await;
+	let meal_plan = meals_database.get();
+
+	let ingredients = ingredients
+		.unwrap_or_default()
+		.split(',')
+		.map(str::trim)
+		.filter(|name| !name.is_empty())
+		.map(String::from)
+		.collect::<Vec<_>>();
+
+	let ingredients = if ingredients.is_empty() {
+		meal_plan.checked_items()
+	} else {
+		ingredients
+	};
+
+	let markup = render_cook_with(&ingredients, &meal_plan.cookable_meals(&ingredients));
+
+	Ok(root(markup))
+}
+
+/// Shows the coming week unless told otherwise.
+#[get("/nutrition?<from>&<to>")]
+pub async fn get_nutrition(
+	context: &State<Context>,
+	from: Option<&str>,
+	to: Option<&str>,
+	_user: User,
+) -> Result<Markup> {
+	let from = parse_optional_date(from)?.unwrap_or_else(|| Local::now().date_naive());
+	let to = parse_optional_date(to)?.unwrap_or(from + Days::new(6));
+
+	let meals_database = context.meals_database.read().await;
+	let days = meals_database.get().nutrition_report(from, to);
 
-	drop(meal_plan);
+	Ok(root(render_nutrition(from, to, &days)))
+}
+
+#[get("/statistics?<from>&<to>")]
+pub async fn get_statistics(
+	context: &State<Context>,
+	from: Option<&str>,
+	to: Option<&str>,
+	_user: User,
+) -> Result<Markup> {
+	let from = parse_optional_date(from)?;
+	let to = parse_optional_date(to)?;
+
+	let meals_database = context.meals_database.read().await;
+	let markup = render_statistics(&meals_database.get().statistics(from, to));
+
+	Ok(root(markup))
+}
+
+#[derive(Debug, FromForm)]
+pub struct StapleData {
+	amount: String,
+	frequency: String,
+	name: String,
+	units: String,
+}
+
+#[post("/staples", data = "<data>")]
+pub async fn post_staples(
+	context: &State<Context>,
+	data: Form<StapleData>,
+	user: User,
+) -> Result<Redirect> {
+	let frequency =
+		Frequency::try_from(data.frequency.as_str()).map_err(|_| Error::PayloadProblem {
+			message: format!("Invalid frequency '{}'", data.frequency),
+		})?;
+
+	let staple = Staple {
+		amount: parse_amount(&data.amount, &data.units)?,
+		frequency,
+		last_added: None,
+		name: data.name.trim().into(),
+	};
 
-	database.save();
+	context
+		.meals_database
+		.write()
+		.await
+		.apply(MealPlanChange::StapleSaved { staple }, &user.name)
+		.await?;
 
-	Ok(html! { (render_checkbox(checkbox.shopping_list_index, &shopping_list_item)) })
+	Ok(Redirect::to("/staples"))
+}
+
+#[derive(Debug, FromForm)]
+pub struct RemoveStapleData {
+	name: String,
+}
+
+#[post("/staples/remove", data = "<data>")]
+pub async fn post_staples_remove(
+	context: &State<Context>,
+	data: Form<RemoveStapleData>,
+	user: User,
+) -> Result<Redirect> {
+	context
+		.meals_database
+		.write()
+		.await
+		.apply(
+			MealPlanChange::StapleRemoved {
+				name: data.name.clone(),
+			},
+			&user.name,
+		)
+		.await?;
+
+	Ok(Redirect::to("/staples"))
+}
+
+#[derive(Debug, FromForm)]
+pub struct ExtraItemData {
+	amount: String,
+	name: String,
+	units: String,
+}
+
+#[post("/extra-items", data = "<data>")]
+pub async fn post_extra_items(
+	context: &State<Context>,
+	data: Form<ExtraItemData>,
+	user: User,
+) -> Result<Redirect> {
+	let item = ShoppingListItem::new(data.name.trim(), parse_amount(&data.amount, &data.units)?);
+
+	context
+		.meals_database
+		.write()
+		.await
+		.apply(MealPlanChange::ExtraItemAdded { item }, &user.name)
+		.await?;
+
+	Ok(Redirect::to("/staples"))
+}
+
+#[derive(Debug, FromForm)]
+pub struct RemoveExtraItemData {
+	name: String,
+}
+
+#[post("/extra-items/remove", data = "<data>")]
+pub async fn post_extra_items_remove(
+	context: &State<Context>,
+	data: Form<RemoveExtraItemData>,
+	user: User,
+) -> Result<Redirect> {
+	context
+		.meals_database
+		.write()
+		.await
+		.apply(
+			MealPlanChange::ExtraItemRemoved {
+				name: data.name.clone(),
+			},
+			&user.name,
+		)
+		.await?;
+
+	Ok(Redirect::to("/staples"))
 }
 
 #[derive(Debug, FromForm)]
@@ -257,7 +874,7 @@ pub struct AddThing {
 #[post("/add-ingredient", data = "<data>")]
 pub async fn post_add_ingredient(data: Form<AddThing>, _user: User) -> Result<Markup> {
 	Ok(html! {
-		(render_ingredient(data.id, "", "", ""))
+		(render_ingredient(data.id, "", "", &Units::Count, None))
 		(add_button("/add-ingredient", "ingredient", data.id + 1))
 	})
 }
@@ -308,7 +925,7 @@ pub async fn post_login(
 	cookies.add(Cookie::build(("SID", sid.to_string())).expires(None));
 
 	let mut valid_sids = context.valid_sids.write().await;
-	valid_sids.insert(sid.clone());
+	valid_sids.insert(sid);
 
 	Redirect::to("/")
 }
@@ -5,10 +5,27 @@ mod rest;
 mod ws;
 
 use htmx::{
-	get_add_recipe, get_login, get_parsed_recipe, get_root, get_style, post_add_ingredient,
-	post_add_meal, post_add_step, post_checkbox, post_login,
+	get_add_recipe, get_cook_with, get_login, get_nutrition, get_pantry, get_parsed_recipe,
+	get_printable_shopping_list, get_recipes, get_root, get_staples, get_statistics, get_style,
+	post_add_ingredient, post_add_meal, post_add_step, post_checkbox, post_extra_items,
+	post_extra_items_remove, post_login, post_pantry, post_pantry_remove, post_redo,
+	post_shopping_list_add_item, post_shopping_list_edit_item, post_shopping_list_merge,
+	post_shopping_list_remove_item, post_shopping_list_rename, post_staples, post_staples_remove,
+	post_undo, post_update_servings,
+};
+use rest::{
+	get_export_recipes, get_journal, get_meal_statistics, get_meals, get_planned_meals, get_search,
+	get_shopping_list, get_shopping_list_by_id, get_shopping_list_markdown, get_shopping_list_text,
+	post_add_shopping_list, post_add_shopping_list_item, post_catalog_entry, post_complete_meal,
+	post_delete_catalog_entry, post_delete_recipe, post_density, post_edit_shopping_list_item,
+	post_extra_item, post_generate_shopping_list, post_generate_shopping_list_for_meal,
+	post_import_recipes, post_leftovers, post_merge_shopping_lists, post_move_meal, post_nutrition,
+	post_pantry_item, post_plan_meal, post_prune_shopping_list, post_recipe, post_recipes,
+	post_redo_change, post_remove_extra_item, post_remove_pantry_item,
+	post_remove_shopping_list_item, post_remove_staple, post_rename_shopping_list, post_replace,
+	post_restore, post_servings, post_shift_meals, post_shopping_list_item, post_staple,
+	post_undo_change, post_unplan_meal,
 };
-use rest::{get_meals, get_planned_meals, get_shopping_list, post_replace};
 use ws::meals_events_stream;
 
 pub fn htmx_routes() -> Vec<Route> {
@@ -17,21 +34,83 @@ pub fn htmx_routes() -> Vec<Route> {
 		get_add_recipe,
 		get_style,
 		post_checkbox,
+		post_shopping_list_add_item,
+		post_shopping_list_edit_item,
+		post_shopping_list_remove_item,
+		post_shopping_list_rename,
+		post_shopping_list_merge,
+		post_update_servings,
+		get_printable_shopping_list,
+		get_pantry,
+		post_pantry,
+		post_pantry_remove,
+		get_staples,
+		post_staples,
+		post_staples_remove,
+		post_extra_items,
+		post_extra_items_remove,
+		get_recipes,
+		get_statistics,
+		get_cook_with,
+		get_nutrition,
 		get_login,
 		post_login,
 		post_add_ingredient,
 		post_add_step,
 		post_add_meal,
 		get_parsed_recipe,
+		post_undo,
+		post_redo,
 	]
 }
 
 pub fn rest_routes() -> Vec<Route> {
 	routes![
 		get_meals,
+		get_journal,
 		get_planned_meals,
 		get_shopping_list,
+		get_shopping_list_by_id,
+		get_shopping_list_text,
+		get_shopping_list_markdown,
+		get_meal_statistics,
+		get_search,
 		post_replace,
+		post_plan_meal,
+		post_unplan_meal,
+		post_move_meal,
+		post_nutrition,
+		post_shift_meals,
+		post_leftovers,
+		post_servings,
+		post_complete_meal,
+		post_shopping_list_item,
+		post_add_shopping_list_item,
+		post_edit_shopping_list_item,
+		post_remove_shopping_list_item,
+		post_rename_shopping_list,
+		post_merge_shopping_lists,
+		post_generate_shopping_list,
+		post_generate_shopping_list_for_meal,
+		post_add_shopping_list,
+		post_prune_shopping_list,
+		post_recipe,
+		post_recipes,
+		post_import_recipes,
+		get_export_recipes,
+		post_delete_recipe,
+		post_density,
+		post_catalog_entry,
+		post_delete_catalog_entry,
+		post_pantry_item,
+		post_remove_pantry_item,
+		post_staple,
+		post_remove_staple,
+		post_extra_item,
+		post_remove_extra_item,
+		post_restore,
+		post_undo_change,
+		post_redo_change,
 	]
 }
 
@@ -1,36 +1,603 @@
-use meals_database::MealPlan;
+use chrono::{Local, NaiveDate};
+use meals_database::{
+	export_recipes, import_recipes, shopping_list_to_markdown, shopping_list_to_text, CatalogEntry,
+	CatalogEntryRequest, ChangeResponse, DeleteMealRequest, DensityRequest,
+	EditShoppingListItemRequest, ExtraItemRequest, LeftoversRequest, MealFilter, MealInfo,
+	MealPlan, MealPlanChange, MealPlanSnapshot, MealStub, MergeShoppingListsRequest,
+	MoveMealRequest, NutritionEntry, PantryItem, PantryItemRequest, PlannedMealRequest,
+	RecipeFormat, RemoveShoppingListItemRequest, RenameShoppingListRequest, ServingsRequest,
+	ShiftMealsRequest, ShoppingListInfo, ShoppingListItem, ShoppingListItemRequest,
+	ShoppingListRequest, Staple, StapleRequest,
+};
 use rocket::{
-	get, post,
+	get,
+	http::ContentType,
+	post,
 	serde::json::{json, Json, Value},
 	State,
 };
-use serde::Serialize;
+use uuid::Uuid;
+
+use crate::{
+	auth::RestUser,
+	revision::{IfMatch, Revisioned},
+	Context, Error, Result,
+};
 
-use crate::{auth::RestUser, Context, Result};
+fn respond(revision: u64, change: Option<MealPlanChange>) -> Revisioned {
+	Revisioned::new(json!(ChangeResponse { change, revision }), revision)
+}
 
-#[derive(Debug, Serialize)]
-pub struct Response {}
+async fn apply(
+	context: &Context,
+	revision: IfMatch,
+	change: MealPlanChange,
+	user: &RestUser,
+) -> Result<Revisioned> {
+	let revision = context
+		.meals_database
+		.write()
+		.await
+		.apply_checked(revision.0, change.clone(), &user.name)
+		.await?;
+
+	Ok(respond(revision, Some(change)))
+}
 
 #[post("/meals/replace", data = "<meal_plan>")]
 pub async fn post_replace(
 	context: &State<Context>,
 	meal_plan: Json<MealPlan>,
-	_user: RestUser,
-) -> Result<Value> {
-	context
+	revision: IfMatch,
+	user: RestUser,
+) -> Result<Revisioned> {
+	let revision = context
 		.meals_database
 		.write()
 		.await
-		.replace(meal_plan.into_inner());
+		.replace(revision.0, meal_plan.into_inner(), &user.name)
+		.await?;
+
+	Ok(respond(revision, None))
+}
+
+#[post("/meals/plan", data = "<meal_stub>")]
+pub async fn post_plan_meal(
+	context: &State<Context>,
+	meal_stub: Json<MealStub>,
+	revision: IfMatch,
+	user: RestUser,
+) -> Result<Revisioned> {
+	let change = MealPlanChange::MealPlanned {
+		meal_stub: meal_stub.into_inner(),
+	};
+
+	apply(context, revision, change, &user).await
+}
+
+#[post("/meals/unplan", data = "<request>")]
+pub async fn post_unplan_meal(
+	context: &State<Context>,
+	request: Json<PlannedMealRequest>,
+	revision: IfMatch,
+	user: RestUser,
+) -> Result<Revisioned> {
+	let change = MealPlanChange::MealUnplanned {
+		date: request.date,
+		id: request.id,
+	};
+
+	apply(context, revision, change, &user).await
+}
+
+#[post("/meals/move", data = "<request>")]
+pub async fn post_move_meal(
+	context: &State<Context>,
+	request: Json<MoveMealRequest>,
+	revision: IfMatch,
+	user: RestUser,
+) -> Result<Revisioned> {
+	let change = MealPlanChange::MealMoved {
+		date: request.date,
+		id: request.id,
+		new_date: request.new_date,
+	};
+
+	apply(context, revision, change, &user).await
+}
+
+#[post("/meals/shift", data = "<request>")]
+pub async fn post_shift_meals(
+	context: &State<Context>,
+	request: Json<ShiftMealsRequest>,
+	revision: IfMatch,
+	user: RestUser,
+) -> Result<Revisioned> {
+	let change = MealPlanChange::MealsShifted { days: request.days };
+	apply(context, revision, change, &user).await
+}
+
+#[post("/meals/leftovers", data = "<request>")]
+pub async fn post_leftovers(
+	context: &State<Context>,
+	request: Json<LeftoversRequest>,
+	revision: IfMatch,
+	user: RestUser,
+) -> Result<Revisioned> {
+	let change = MealPlanChange::LeftoversSet {
+		date: request.date,
+		id: request.id,
+		leftovers: request.leftovers,
+	};
+
+	apply(context, revision, change, &user).await
+}
+
+#[post("/meals/servings", data = "<request>")]
+pub async fn post_servings(
+	context: &State<Context>,
+	request: Json<ServingsRequest>,
+	revision: IfMatch,
+	user: RestUser,
+) -> Result<Revisioned> {
+	let change = MealPlanChange::ServingsSet {
+		date: request.date,
+		id: request.id,
+		servings: request.servings,
+	};
+
+	apply(context, revision, change, &user).await
+}
+
+#[post("/meals/complete", data = "<request>")]
+pub async fn post_complete_meal(
+	context: &State<Context>,
+	request: Json<PlannedMealRequest>,
+	revision: IfMatch,
+	user: RestUser,
+) -> Result<Revisioned> {
+	let change = MealPlanChange::MealCompleted {
+		date: request.date,
+		id: request.id,
+	};
+
+	apply(context, revision, change, &user).await
+}
+
+#[post("/meals/shopping-list/item", data = "<request>")]
+pub async fn post_shopping_list_item(
+	context: &State<Context>,
+	request: Json<ShoppingListItemRequest>,
+	revision: IfMatch,
+	user: RestUser,
+) -> Result<Revisioned> {
+	let request = request.into_inner();
+	let change = MealPlanChange::ShoppingListItemSet {
+		have: request.have,
+		id: request.id,
+		shopping_list_id: request.shopping_list_id,
+	};
+
+	apply(context, revision, change, &user).await
+}
+
+#[post("/meals/shopping-list/item/add", data = "<request>")]
+pub async fn post_add_shopping_list_item(
+	context: &State<Context>,
+	request: Json<EditShoppingListItemRequest>,
+	revision: IfMatch,
+	user: RestUser,
+) -> Result<Revisioned> {
+	let request = request.into_inner();
+	let change = MealPlanChange::ShoppingListItemAdded {
+		item: request.item,
+		shopping_list_id: request.shopping_list_id,
+	};
+
+	apply(context, revision, change, &user).await
+}
+
+#[post("/meals/shopping-list/item/edit", data = "<request>")]
+pub async fn post_edit_shopping_list_item(
+	context: &State<Context>,
+	request: Json<EditShoppingListItemRequest>,
+	revision: IfMatch,
+	user: RestUser,
+) -> Result<Revisioned> {
+	let request = request.into_inner();
+	let change = MealPlanChange::ShoppingListItemEdited {
+		item: request.item,
+		shopping_list_id: request.shopping_list_id,
+	};
 
-	Ok(json!(Response {}))
+	apply(context, revision, change, &user).await
+}
+
+#[post("/meals/shopping-list/item/remove", data = "<request>")]
+pub async fn post_remove_shopping_list_item(
+	context: &State<Context>,
+	request: Json<RemoveShoppingListItemRequest>,
+	revision: IfMatch,
+	user: RestUser,
+) -> Result<Revisioned> {
+	let request = request.into_inner();
+	let change = MealPlanChange::ShoppingListItemRemoved {
+		id: request.id,
+		shopping_list_id: request.shopping_list_id,
+	};
+
+	apply(context, revision, change, &user).await
+}
+
+#[post("/meals/shopping-list/rename", data = "<request>")]
+pub async fn post_rename_shopping_list(
+	context: &State<Context>,
+	request: Json<RenameShoppingListRequest>,
+	revision: IfMatch,
+	user: RestUser,
+) -> Result<Revisioned> {
+	let request = request.into_inner();
+	let change = MealPlanChange::ShoppingListRenamed {
+		name: request.name,
+		shopping_list_id: request.shopping_list_id,
+	};
+
+	apply(context, revision, change, &user).await
+}
+
+#[post("/meals/shopping-list/merge", data = "<request>")]
+pub async fn post_merge_shopping_lists(
+	context: &State<Context>,
+	request: Json<MergeShoppingListsRequest>,
+	revision: IfMatch,
+	user: RestUser,
+) -> Result<Revisioned> {
+	let request = request.into_inner();
+	let change = MealPlanChange::ShoppingListsMerged {
+		from_id: request.from_id,
+		into_id: request.into_id,
+	};
+
+	apply(context, revision, change, &user).await
+}
+
+#[post("/meals/shopping-list/generate")]
+pub async fn post_generate_shopping_list(
+	context: &State<Context>,
+	revision: IfMatch,
+	user: RestUser,
+) -> Result<Revisioned> {
+	let mut meals_database = context.meals_database.write().await;
+	meals_database.check_revision(revision.0)?;
+
+	let shopping_list = meals_database
+		.get()
+		.generate_shopping_list(Local::now().date_naive());
+	let Some(shopping_list) = shopping_list else {
+		return Ok(respond(revision.0, None));
+	};
+
+	let change = MealPlanChange::ShoppingListAdded { shopping_list };
+	let revision = meals_database.apply(change.clone(), &user.name).await?;
+
+	Ok(respond(revision, Some(change)))
+}
+
+#[post("/meals/shopping-list/generate-for-meal", data = "<request>")]
+pub async fn post_generate_shopping_list_for_meal(
+	context: &State<Context>,
+	request: Json<PlannedMealRequest>,
+	revision: IfMatch,
+	user: RestUser,
+) -> Result<Revisioned> {
+	let mut meals_database = context.meals_database.write().await;
+	meals_database.check_revision(revision.0)?;
+
+	let shopping_list = meals_database.get().generate_shopping_list_for_meal(
+		request.date,
+		request.id,
+		Local::now().date_naive(),
+	)?;
+
+	let change = MealPlanChange::ShoppingListAdded { shopping_list };
+	let revision = meals_database.apply(change.clone(), &user.name).await?;
+
+	Ok(respond(revision, Some(change)))
+}
+
+#[post("/meals/shopping-list/add", data = "<shopping_list>")]
+pub async fn post_add_shopping_list(
+	context: &State<Context>,
+	shopping_list: Json<ShoppingListInfo>,
+	revision: IfMatch,
+	user: RestUser,
+) -> Result<Revisioned> {
+	let change = MealPlanChange::ShoppingListAdded {
+		shopping_list: shopping_list.into_inner(),
+	};
+
+	apply(context, revision, change, &user).await
+}
+
+#[post("/meals/shopping-list/prune", data = "<request>")]
+pub async fn post_prune_shopping_list(
+	context: &State<Context>,
+	request: Json<ShoppingListRequest>,
+	revision: IfMatch,
+	user: RestUser,
+) -> Result<Revisioned> {
+	let change = MealPlanChange::ShoppingListPruned {
+		shopping_list_id: request.shopping_list_id,
+	};
+
+	apply(context, revision, change, &user).await
+}
+
+#[post("/meals/recipe", data = "<meal_info>")]
+pub async fn post_recipe(
+	context: &State<Context>,
+	meal_info: Json<MealInfo>,
+	revision: IfMatch,
+	user: RestUser,
+) -> Result<Revisioned> {
+	let change = MealPlanChange::MealSaved {
+		meal_info: meal_info.into_inner(),
+	};
+
+	apply(context, revision, change, &user).await
+}
+
+#[post("/meals/recipes", data = "<meals>")]
+pub async fn post_recipes(
+	context: &State<Context>,
+	meals: Json<Vec<MealInfo>>,
+	revision: IfMatch,
+	user: RestUser,
+) -> Result<Revisioned> {
+	let change = MealPlanChange::MealsImported {
+		meals: meals.into_inner(),
+	};
+
+	apply(context, revision, change, &user).await
+}
+
+#[post("/meals/recipes/import?<format>", data = "<text>")]
+pub async fn post_import_recipes(
+	context: &State<Context>,
+	format: &str,
+	text: String,
+	revision: IfMatch,
+	user: RestUser,
+) -> Result<Revisioned> {
+	let format = parse_recipe_format(format)?;
+	let meals = import_recipes(&text, format).map_err(|error| Error::PayloadProblem {
+		message: format!("Could not import recipes: {}", error),
+	})?;
+
+	apply(
+		context,
+		revision,
+		MealPlanChange::MealsImported { meals },
+		&user,
+	)
+	.await
+}
+
+#[post("/meals/recipe/delete", data = "<request>")]
+pub async fn post_delete_recipe(
+	context: &State<Context>,
+	request: Json<DeleteMealRequest>,
+	revision: IfMatch,
+	user: RestUser,
+) -> Result<Revisioned> {
+	let change = MealPlanChange::MealDeleted { id: request.id };
+	apply(context, revision, change, &user).await
+}
+
+#[post("/meals/density", data = "<request>")]
+pub async fn post_density(
+	context: &State<Context>,
+	request: Json<DensityRequest>,
+	revision: IfMatch,
+	user: RestUser,
+) -> Result<Revisioned> {
+	let request = request.into_inner();
+	let change = MealPlanChange::DensitySet {
+		grams_per_milliliter: request.grams_per_milliliter,
+		name: request.name,
+	};
+
+	apply(context, revision, change, &user).await
+}
+
+#[post("/meals/catalog", data = "<entry>")]
+pub async fn post_catalog_entry(
+	context: &State<Context>,
+	entry: Json<CatalogEntry>,
+	revision: IfMatch,
+	user: RestUser,
+) -> Result<Revisioned> {
+	let change = MealPlanChange::CatalogEntrySaved {
+		entry: entry.into_inner(),
+	};
+
+	apply(context, revision, change, &user).await
+}
+
+#[post("/meals/catalog/delete", data = "<request>")]
+pub async fn post_delete_catalog_entry(
+	context: &State<Context>,
+	request: Json<CatalogEntryRequest>,
+	revision: IfMatch,
+	user: RestUser,
+) -> Result<Revisioned> {
+	let change = MealPlanChange::CatalogEntryDeleted {
+		name: request.into_inner().name,
+	};
+
+	apply(context, revision, change, &user).await
+}
+
+#[post("/meals/pantry", data = "<item>")]
+pub async fn post_pantry_item(
+	context: &State<Context>,
+	item: Json<PantryItem>,
+	revision: IfMatch,
+	user: RestUser,
+) -> Result<Revisioned> {
+	let change = MealPlanChange::PantryItemSet {
+		item: item.into_inner(),
+	};
+
+	apply(context, revision, change, &user).await
+}
+
+#[post("/meals/pantry/remove", data = "<request>")]
+pub async fn post_remove_pantry_item(
+	context: &State<Context>,
+	request: Json<PantryItemRequest>,
+	revision: IfMatch,
+	user: RestUser,
+) -> Result<Revisioned> {
+	let change = MealPlanChange::PantryItemRemoved {
+		name: request.into_inner().name,
+	};
+
+	apply(context, revision, change, &user).await
+}
+
+#[post("/meals/nutrition", data = "<entries>")]
+pub async fn post_nutrition(
+	context: &State<Context>,
+	entries: Json<Vec<NutritionEntry>>,
+	revision: IfMatch,
+	user: RestUser,
+) -> Result<Revisioned> {
+	let change = MealPlanChange::NutritionImported {
+		entries: entries.into_inner(),
+	};
+
+	apply(context, revision, change, &user).await
+}
+
+#[post("/meals/staples", data = "<staple>")]
+pub async fn post_staple(
+	context: &State<Context>,
+	staple: Json<Staple>,
+	revision: IfMatch,
+	user: RestUser,
+) -> Result<Revisioned> {
+	let change = MealPlanChange::StapleSaved {
+		staple: staple.into_inner(),
+	};
+
+	apply(context, revision, change, &user).await
+}
+
+#[post("/meals/staples/remove", data = "<request>")]
+pub async fn post_remove_staple(
+	context: &State<Context>,
+	request: Json<StapleRequest>,
+	revision: IfMatch,
+	user: RestUser,
+) -> Result<Revisioned> {
+	let change = MealPlanChange::StapleRemoved {
+		name: request.into_inner().name,
+	};
+
+	apply(context, revision, change, &user).await
+}
+
+#[post("/meals/extra-items", data = "<item>")]
+pub async fn post_extra_item(
+	context: &State<Context>,
+	item: Json<ShoppingListItem>,
+	revision: IfMatch,
+	user: RestUser,
+) -> Result<Revisioned> {
+	let change = MealPlanChange::ExtraItemAdded {
+		item: item.into_inner(),
+	};
+
+	apply(context, revision, change, &user).await
+}
+
+#[post("/meals/extra-items/remove", data = "<request>")]
+pub async fn post_remove_extra_item(
+	context: &State<Context>,
+	request: Json<ExtraItemRequest>,
+	revision: IfMatch,
+	user: RestUser,
+) -> Result<Revisioned> {
+	let change = MealPlanChange::ExtraItemRemoved {
+		name: request.into_inner().name,
+	};
+
+	apply(context, revision, change, &user).await
+}
+
+#[post("/meals/restore", data = "<snapshot>")]
+pub async fn post_restore(
+	context: &State<Context>,
+	snapshot: Json<MealPlanSnapshot>,
+	revision: IfMatch,
+	user: RestUser,
+) -> Result<Revisioned> {
+	let change = MealPlanChange::SnapshotRestored {
+		snapshot: snapshot.into_inner(),
+	};
+
+	apply(context, revision, change, &user).await
+}
+
+#[post("/meals/undo")]
+pub async fn post_undo_change(
+	context: &State<Context>,
+	revision: IfMatch,
+	user: RestUser,
+) -> Result<Revisioned> {
+	let mut meals_database = context.meals_database.write().await;
+	meals_database.check_revision(revision.0)?;
+
+	let (change, revision) = meals_database.undo(&user.name).await?;
+	Ok(respond(revision, Some(change)))
+}
+
+#[post("/meals/redo")]
+pub async fn post_redo_change(
+	context: &State<Context>,
+	revision: IfMatch,
+	user: RestUser,
+) -> Result<Revisioned> {
+	let mut meals_database = context.meals_database.write().await;
+	meals_database.check_revision(revision.0)?;
+
+	let (change, revision) = meals_database.redo(&user.name).await?;
+	Ok(respond(revision, Some(change)))
+}
+
+#[get("/meals/journal?<limit>")]
+pub async fn get_journal(
+	context: &State<Context>,
+	limit: Option<usize>,
+	_user: RestUser,
+) -> Result<Value> {
+	let meals_database = context.meals_database.read().await;
+	let journal = meals_database.journal();
+	let limit = limit.unwrap_or(50).min(journal.len());
+
+	Ok(json!({
+		"can_redo": meals_database.can_redo(),
+		"can_undo": meals_database.can_undo(),
+		"entries": journal[journal.len() - limit..],
+	}))
 }
 
 #[get("/meals/all")]
-pub async fn get_meals(context: &State<Context>, _user: RestUser) -> Result<Value> {
+pub async fn get_meals(context: &State<Context>, _user: RestUser) -> Result<Revisioned> {
 	let meal_plan = context.meals_database.read().await;
 	let meal_plan = meal_plan.get();
-	Ok(json!(&*meal_plan))
+	Ok(Revisioned::new(json!(&*meal_plan), meal_plan.revision))
 }
 
 #[get("/meals/shopping-list")]
@@ -40,9 +607,114 @@ pub async fn get_shopping_list(context: &State<Context>, _user: RestUser) -> Res
 	Ok(json!(meal_plan.shopping_list))
 }
 
+fn parse_shopping_list_id(shopping_list_id: &str) -> Result<Uuid> {
+	Uuid::parse_str(shopping_list_id).map_err(|error| Error::PayloadProblem {
+		message: format!("Invalid shopping list id: {}", error),
+	})
+}
+
+#[get("/meals/shopping-list/<shopping_list_id>")]
+pub async fn get_shopping_list_by_id(
+	context: &State<Context>,
+	shopping_list_id: &str,
+	_user: RestUser,
+) -> Result<Value> {
+	let shopping_list_id = parse_shopping_list_id(shopping_list_id)?;
+	let meal_plan = context.meals_database.read().await;
+	let meal_plan = meal_plan.get();
+	Ok(json!(meal_plan.shopping_list_by_id(shopping_list_id)?))
+}
+
+#[get("/meals/shopping-list/<shopping_list_id>/text")]
+pub async fn get_shopping_list_text(
+	context: &State<Context>,
+	shopping_list_id: &str,
+	_user: RestUser,
+) -> Result<String> {
+	let shopping_list_id = parse_shopping_list_id(shopping_list_id)?;
+	let meal_plan = context.meals_database.read().await;
+	let meal_plan = meal_plan.get();
+	let shopping_list = meal_plan.shopping_list_by_id(shopping_list_id)?;
+	Ok(shopping_list_to_text(&meal_plan, shopping_list))
+}
+
+#[get("/meals/shopping-list/<shopping_list_id>/markdown")]
+pub async fn get_shopping_list_markdown(
+	context: &State<Context>,
+	shopping_list_id: &str,
+	_user: RestUser,
+) -> Result<(ContentType, String)> {
+	let shopping_list_id = parse_shopping_list_id(shopping_list_id)?;
+	let meal_plan = context.meals_database.read().await;
+	let meal_plan = meal_plan.get();
+	let shopping_list = meal_plan.shopping_list_by_id(shopping_list_id)?;
+	Ok((
+		ContentType::Markdown,
+		shopping_list_to_markdown(&meal_plan, shopping_list),
+	))
+}
+
+#[get("/meals/recipes/export?<format>")]
+pub async fn get_export_recipes(
+	context: &State<Context>,
+	format: &str,
+	_user: RestUser,
+) -> Result<(ContentType, String)> {
+	let format = parse_recipe_format(format)?;
+	let meal_plan = context.meals_database.read().await;
+	let meal_plan = meal_plan.get();
+	let content_type = match format {
+		RecipeFormat::Markdown => ContentType::Markdown,
+		RecipeFormat::JsonLd | RecipeFormat::Paprika => ContentType::JSON,
+	};
+
+	Ok((
+		content_type,
+		export_recipes(meal_plan.filter_meals(&MealFilter::default()), format),
+	))
+}
+
 #[get("/meals/planned-meals")]
 pub async fn get_planned_meals(context: &State<Context>, _user: RestUser) -> Result<Value> {
 	let meal_plan = context.meals_database.read().await;
 	let meal_plan = meal_plan.get();
 	Ok(json!(meal_plan.planned_meals))
 }
+
+fn parse_recipe_format(format: &str) -> Result<RecipeFormat> {
+	RecipeFormat::try_from(format).map_err(|_| Error::PayloadProblem {
+		message: format!("Invalid recipe format: {}", format),
+	})
+}
+
+fn parse_date(date: Option<&str>) -> Result<Option<NaiveDate>> {
+	date.map(|date| {
+		date.parse::<NaiveDate>()
+			.map_err(|error| Error::PayloadProblem {
+				message: format!("Invalid date: {}", error),
+			})
+	})
+	.transpose()
+}
+
+#[get("/meals/statistics?<from>&<to>")]
+pub async fn get_meal_statistics(
+	context: &State<Context>,
+	from: Option<&str>,
+	to: Option<&str>,
+	_user: RestUser,
+) -> Result<Value> {
+	let from = parse_date(from)?;
+	let to = parse_date(to)?;
+
+	let meal_plan = context.meals_database.read().await;
+	let meal_plan = meal_plan.get();
+	Ok(json!(meal_plan.statistics(from, to)))
+}
+
+#[get("/meals/search?<query>")]
+pub async fn get_search(context: &State<Context>, query: &str, _user: RestUser) -> Result<Value> {
+	let meal_plan = context.meals_database.read().await;
+	let meal_plan = meal_plan.get();
+	Ok(json!(meal_plan.search(query)))
+}
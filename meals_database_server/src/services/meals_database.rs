@@ -1,45 +1,232 @@
-use meals_database::{Database, MealPlan, MealPlanMessage};
-use std::sync::{RwLockReadGuard, RwLockWriteGuard};
+use chrono::Local;
+use meals_database::{
+	Database, DatabaseConfig, DatabaseError, Journal, JournalAction, JournalEntry, MealPlan,
+	MealPlanChange, MealPlanMessage, MealPlanSnapshot,
+};
+use std::{
+	collections::HashSet,
+	sync::{Arc, RwLockReadGuard},
+};
 use tokio::sync::broadcast::{self, Receiver, Sender};
 
+use crate::{Error, Result};
+
 pub struct MealsDatabaseService {
-	database: Database<MealPlan>,
+	database: Arc<dyn Database>,
+	journal: Journal,
 	sender: Sender<MealPlanMessage>,
 }
 
 impl MealsDatabaseService {
-	pub fn new() -> Self {
+	pub async fn new() -> std::result::Result<Self, DatabaseError> {
 		let (sender, _) = broadcast::channel::<MealPlanMessage>(16);
 
-		let mut database = Database::new("meals-database.json");
-		database.load();
+		let config = DatabaseConfig::from_env()?.unwrap_or(DatabaseConfig::Json {
+			path: "meals-database.json".into(),
+		});
+
+		log::info!("Opening meals database {:?}", config);
+
+		let database = config.open().await?;
+
+		let journal_path = std::env::var("BANSHEELONG2_MEALS_JOURNAL_PATH")
+			.unwrap_or_else(|_| "meals-journal.jsonl".into());
+
+		log::info!("Opening meals journal '{}'", journal_path);
+
+		let journal = Journal::open(&journal_path)?;
 
-		MealsDatabaseService { database, sender }
+		Ok(MealsDatabaseService {
+			database,
+			journal,
+			sender,
+		})
 	}
 
-	pub fn replace(&mut self, new_meal_plan: MealPlan) {
-		let mut meal_plan = self.database.get_mut();
-		*meal_plan = new_meal_plan;
-		drop(meal_plan);
+	pub async fn replace(
+		&mut self,
+		revision: u64,
+		new_meal_plan: MealPlan,
+		user: &str,
+	) -> Result<u64> {
+		self.check_revision(revision)?;
 
-		self.database.save();
-		self.sender.send(MealPlanMessage::Update).unwrap();
+		let mut meal_ids = self
+			.database
+			.get()
+			.all_meals
+			.keys()
+			.cloned()
+			.collect::<HashSet<_>>();
+
+		meal_ids.extend(new_meal_plan.all_meals.keys().cloned());
+
+		let before = self.database.get().snapshot(meal_ids.iter().cloned(), true);
+		self.database.replace(new_meal_plan).await?;
+		let after = self.database.get().snapshot(meal_ids, true);
+
+		let revision = self.record(JournalAction::Replace, None, before, after, user);
+		self.broadcast(MealPlanMessage::Update);
+
+		Ok(revision)
 	}
 
-	pub fn get(&self) -> RwLockReadGuard<MealPlan> {
-		self.database.get()
+	pub async fn apply(&mut self, change: MealPlanChange, user: &str) -> Result<u64> {
+		let before = self
+			.database
+			.get()
+			.snapshot(change.meal_ids(), change.touches_nutrition());
+		self.database.apply(change.clone()).await?;
+		let after = self
+			.database
+			.get()
+			.snapshot(change.meal_ids(), change.touches_nutrition());
+
+		let revision = self.record(
+			JournalAction::Change,
+			Some(change.clone()),
+			before,
+			after,
+			user,
+		);
+
+		self.broadcast(MealPlanMessage::Changed {
+			change: Box::new(change),
+			revision,
+		});
+
+		Ok(revision)
 	}
 
-	pub fn get_mut(&self) -> RwLockWriteGuard<MealPlan> {
-		self.database.get_mut()
+	pub async fn apply_checked(
+		&mut self,
+		revision: u64,
+		change: MealPlanChange,
+		user: &str,
+	) -> Result<u64> {
+		self.check_revision(revision)?;
+		self.apply(change, user).await
 	}
-	
-	pub fn save(&self) {
-		self.database.save();
-		self.sender.send(MealPlanMessage::Update).unwrap();
+
+	/// Restores the meal plan to how it was before the most recent change that hasn't been undone.
+	pub async fn undo(&mut self, user: &str) -> Result<(MealPlanChange, u64)> {
+		let Some(entry) = self.journal.next_undo().cloned() else {
+			return Err(Error::NotFound {
+				message: "Nothing to undo".into(),
+			});
+		};
+
+		self.restore(JournalAction::Undo, entry.change, entry.before, user)
+			.await
+	}
+
+	/// Reapplies the most recently undone change.
+	pub async fn redo(&mut self, user: &str) -> Result<(MealPlanChange, u64)> {
+		let Some(entry) = self.journal.next_redo().cloned() else {
+			return Err(Error::NotFound {
+				message: "Nothing to redo".into(),
+			});
+		};
+
+		self.restore(JournalAction::Redo, entry.change, entry.after, user)
+			.await
+	}
+
+	pub fn journal(&self) -> &[JournalEntry] {
+		self.journal.entries()
+	}
+
+	pub fn can_undo(&self) -> bool {
+		self.journal.can_undo()
+	}
+
+	pub fn can_redo(&self) -> bool {
+		self.journal.can_redo()
+	}
+
+	pub fn check_revision(&self, revision: u64) -> Result<()> {
+		let current_revision = self.database.get().revision;
+		if revision != current_revision {
+			return Err(Error::Conflict {
+				message: format!(
+					"Meal plan is at revision {}, but the write was based on revision {}",
+					current_revision, revision
+				),
+			});
+		}
+
+		Ok(())
+	}
+
+	pub fn get(&self) -> RwLockReadGuard<'_, MealPlan> {
+		self.database.get()
 	}
 
 	pub fn subscribe(&self) -> Receiver<MealPlanMessage> {
 		self.sender.subscribe()
 	}
+
+	async fn restore(
+		&mut self,
+		action: JournalAction,
+		journaled_change: Option<MealPlanChange>,
+		snapshot: MealPlanSnapshot,
+		user: &str,
+	) -> Result<(MealPlanChange, u64)> {
+		let change = MealPlanChange::SnapshotRestored { snapshot };
+
+		let before = self
+			.database
+			.get()
+			.snapshot(change.meal_ids(), change.touches_nutrition());
+		self.database.apply(change.clone()).await?;
+		let after = self
+			.database
+			.get()
+			.snapshot(change.meal_ids(), change.touches_nutrition());
+
+		let revision = self.record(action, journaled_change, before, after, user);
+		self.broadcast(MealPlanMessage::Changed {
+			change: Box::new(change.clone()),
+			revision,
+		});
+
+		Ok((change, revision))
+	}
+
+	fn record(
+		&mut self,
+		action: JournalAction,
+		change: Option<MealPlanChange>,
+		before: MealPlanSnapshot,
+		after: MealPlanSnapshot,
+		user: &str,
+	) -> u64 {
+		let revision = self.database.get().revision;
+		let (before, after) = MealPlanSnapshot::difference(before, after);
+
+		let entry = JournalEntry {
+			action,
+			after,
+			before,
+			change,
+			revision,
+			timestamp: Local::now(),
+			user: user.into(),
+		};
+
+		// the write already went through, so a journal failure shouldn't fail the request
+		if let Err(error) = self.journal.record(entry) {
+			log::error!("Could not write journal entry: {}", error);
+		}
+
+		revision
+	}
+
+	fn broadcast(&self, message: MealPlanMessage) {
+		// sending only fails when no websocket clients are connected
+		if self.sender.send(message).is_err() {
+			log::debug!("No subscribers for meal plan message");
+		}
+	}
 }